@@ -2,7 +2,8 @@
 set -e
 
 echo "🔨 Building Rust WebAssembly target..."
-cargo build --release --target wasm32-unknown-unknown
+# ktx2tool 依赖原生库（zstd），只构建应用本身
+cargo build --release --target wasm32-unknown-unknown --bin iced_web_app
 
 echo "📦 Generating JavaScript bindings..."
 wasm-bindgen --target web --out-dir public \
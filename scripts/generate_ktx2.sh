@@ -1,27 +1,29 @@
 #!/bin/bash
 # KTX2 生成脚本
-# 从 PNG 文件生成 KTX2 纹理
+# 从 PNG 文件生成 KTX2 纹理；输入为目录时批量转换（ktx2tool batch）
 
 set -e
 
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
 PROJECT_DIR="$(dirname "$SCRIPT_DIR")"
-BINARY="$PROJECT_DIR/target/release/ktx2_generator"
+BINARY="$PROJECT_DIR/target/release/ktx2tool"
 
 # 检查二进制文件是否存在
 if [ ! -f "$BINARY" ]; then
-    echo "📦 编译 KTX2 生成器..."
+    echo "📦 编译 KTX2 工具..."
     cd "$PROJECT_DIR"
-    cargo build --bin ktx2_generator --release
+    cargo build --bin ktx2tool --release
 fi
 
 # 检查参数
 if [ $# -lt 2 ]; then
-    echo "用法: $0 <输入 PNG> <输出 KTX2>"
+    echo "用法: $0 <输入 PNG> <输出 KTX2> [ktx2tool create 选项...]"
+    echo "      $0 <输入目录> <输出目录> [ktx2tool batch 选项...]"
     echo ""
     echo "示例:"
     echo "  $0 public/1.png public/1.ktx2"
     echo "  $0 texture.png texture.ktx2"
+    echo "  $0 assets/ public/textures/ --config presets.json"
     echo ""
     echo "提示: 输出文件名建议使用 .ktx2 或 .ktx2 扩展名"
     exit 1
@@ -30,6 +32,15 @@ fi
 INPUT="$1"
 OUTPUT="$2"
 
+# 输入为目录时批量转换
+if [ -d "$INPUT" ]; then
+    echo "🚀 开始批量生成 KTX2 文件..."
+    "$BINARY" batch "$INPUT" "$OUTPUT" "${@:3}"
+    echo ""
+    echo "✅ 完成! KTX2 文件和 manifest.json 已保存到: $OUTPUT"
+    exit 0
+fi
+
 # 检查输入文件
 if [ ! -f "$INPUT" ]; then
     echo "❌ 错误: 输入文件不存在: $INPUT"
@@ -38,7 +49,7 @@ fi
 
 # 运行生成器
 echo "🚀 开始生成 KTX2 文件..."
-"$BINARY" "$INPUT" "$OUTPUT"
+"$BINARY" create "$INPUT" "$OUTPUT" "${@:3}"
 
 echo ""
 echo "✅ 完成! KTX2 文件已保存到: $OUTPUT"
@@ -0,0 +1,184 @@
+//! Alpha 处理模块
+//!
+//! 转换工具在写入和生成 mip 之前对 RGBA8 像素做预处理：
+//! - 预乘 Alpha（sRGB 数据先解码到线性空间再相乘），结果记录在 DFD 标志中
+//! - 把颜色扩散到完全透明的区域，避免过滤时透明像素的任意 RGB 造成黑边
+//!
+//! 加载带预乘标志的 KTX2 时用 `unpremultiply` 还原为直通 Alpha，
+//! 16 位和浮点数据对应 `unpremultiply16` 和 `unpremultiply_f32`。
+//! 该模块只依赖 ktx2 和 `color_space`
+
+use crate::color_space::{self, ColorSpace};
+
+/// 预乘 Alpha：RGB 乘以 Alpha，sRGB 数据在线性空间中相乘
+pub fn premultiply(rgba: &mut [u8], color_space: ColorSpace) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        if alpha == 255 {
+            continue;
+        }
+        let factor = alpha as f32 / 255.0;
+        for c in &mut pixel[..3] {
+            *c = scale(*c, factor, color_space);
+        }
+    }
+}
+
+/// 还原预乘 Alpha，完全透明的像素 RGB 置 0
+pub fn unpremultiply(rgba: &mut [u8], color_space: ColorSpace) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        match pixel[3] {
+            255 => {}
+            0 => pixel[..3].fill(0),
+            alpha => {
+                let factor = 255.0 / alpha as f32;
+                for c in &mut pixel[..3] {
+                    *c = scale(*c, factor, color_space);
+                }
+            }
+        }
+    }
+}
+
+/// 还原 16 位数据的预乘 Alpha（16 位格式都是线性的），完全透明的像素 RGB 置 0
+pub fn unpremultiply16(rgba: &mut [u16]) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        match pixel[3] {
+            u16::MAX => {}
+            0 => pixel[..3].fill(0),
+            alpha => {
+                let factor = u16::MAX as f32 / alpha as f32;
+                for c in &mut pixel[..3] {
+                    *c = (*c as f32 * factor).round().clamp(0.0, u16::MAX as f32) as u16;
+                }
+            }
+        }
+    }
+}
+
+/// 还原浮点数据的预乘 Alpha（浮点数据都是线性的），Alpha 不大于 0 的像素 RGB 置 0
+pub fn unpremultiply_f32(rgba: &mut [f32]) {
+    for pixel in rgba.chunks_exact_mut(4) {
+        let alpha = pixel[3];
+        if alpha > 0.0 {
+            pixel[..3].iter_mut().for_each(|c| *c /= alpha);
+        } else {
+            pixel[..3].fill(0.0);
+        }
+    }
+}
+
+/// KTX2 的 DFD 是否带有预乘 Alpha 标志
+pub fn is_premultiplied_ktx2(bytes: &[u8]) -> bool {
+    let Ok(reader) = ktx2::Reader::new(bytes) else {
+        return false;
+    };
+    reader
+        .dfd_blocks()
+        .find(|block| block.header.vendor_id == 0 && block.header.descriptor_type == 0)
+        .and_then(|block| ktx2::DfdBlockBasic::parse(block.data).ok())
+        .is_some_and(|basic| basic.header.flags.contains(ktx2::DataFormatFlags::ALPHA_PREMULTIPLIED))
+}
+
+/// 把颜色扩散到完全透明的像素（Alpha 保持为 0）
+///
+/// 每一轮把已有颜色像素的 8 邻域平均色填入相邻的透明像素，直到填满整张图。
+/// 整张图都透明时不做任何修改
+pub fn bleed(rgba: &mut [u8], width: u32, height: u32) {
+    let (width, height) = (width as usize, height as usize);
+    if rgba.len() != width * height * 4 {
+        return;
+    }
+
+    let mut filled: Vec<bool> = rgba.chunks_exact(4).map(|pixel| pixel[3] > 0).collect();
+    let mut frontier: Vec<usize> = (0..filled.len())
+        .filter(|&i| !filled[i] && neighbors(i, width, height).any(|n| filled[n]))
+        .collect();
+
+    while !frontier.is_empty() {
+        let colors: Vec<[u8; 3]> = frontier
+            .iter()
+            .map(|&i| {
+                let mut sum = [0u32; 3];
+                let mut count = 0;
+                for n in neighbors(i, width, height).filter(|&n| filled[n]) {
+                    for c in 0..3 {
+                        sum[c] += rgba[n * 4 + c] as u32;
+                    }
+                    count += 1;
+                }
+                sum.map(|s| (s / count.max(1)) as u8)
+            })
+            .collect();
+
+        for (&i, color) in frontier.iter().zip(&colors) {
+            rgba[i * 4..i * 4 + 3].copy_from_slice(color);
+            filled[i] = true;
+        }
+
+        let mut next: Vec<usize> = frontier
+            .iter()
+            .flat_map(|&i| neighbors(i, width, height))
+            .filter(|&n| !filled[n])
+            .collect();
+        next.sort_unstable();
+        next.dedup();
+        frontier = next;
+    }
+}
+
+/// 像素 `index` 的 8 邻域下标
+fn neighbors(index: usize, width: usize, height: usize) -> impl Iterator<Item = usize> {
+    let (x, y) = ((index % width) as isize, (index / width) as isize);
+    (-1..=1isize)
+        .flat_map(move |dy| (-1..=1isize).map(move |dx| (x + dx, y + dy)))
+        .filter(move |&(nx, ny)| {
+            (nx, ny) != (x, y) && nx >= 0 && ny >= 0 && (nx as usize) < width && (ny as usize) < height
+        })
+        .map(move |(nx, ny)| ny as usize * width + nx as usize)
+}
+
+/// 按色彩空间缩放一个颜色分量
+fn scale(value: u8, factor: f32, color_space: ColorSpace) -> u8 {
+    match color_space {
+        ColorSpace::Srgb => color_space::linear_to_srgb(color_space::srgb_to_linear(value) * factor),
+        ColorSpace::Linear => (value as f32 * factor).round().clamp(0.0, 255.0) as u8,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_premultiply_round_trip() {
+        let mut rgba = vec![200, 100, 50, 128, 10, 20, 30, 0, 1, 2, 3, 255];
+        premultiply(&mut rgba, ColorSpace::Linear);
+        assert_eq!(rgba, vec![100, 50, 25, 128, 0, 0, 0, 0, 1, 2, 3, 255]);
+
+        unpremultiply(&mut rgba, ColorSpace::Linear);
+        assert_eq!(rgba, vec![199, 100, 50, 128, 0, 0, 0, 0, 1, 2, 3, 255]);
+
+        let mut rgba16 = vec![16384, 8192, 0, 32768, 500, 500, 500, 0, 1, 2, 3, 65535];
+        unpremultiply16(&mut rgba16);
+        assert_eq!(rgba16, vec![32768, 16384, 0, 32768, 0, 0, 0, 0, 1, 2, 3, 65535]);
+
+        let mut rgba32f = vec![0.25, 0.5, 2.0, 0.5, 1.0, 1.0, 1.0, 0.0];
+        unpremultiply_f32(&mut rgba32f);
+        assert_eq!(rgba32f, vec![0.5, 1.0, 4.0, 0.5, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_bleed_fills_transparent_pixels() {
+        // 3x1：左侧红色不透明，其余完全透明
+        let mut rgba = vec![255, 0, 0, 255, 0, 0, 0, 0, 9, 9, 9, 0];
+        bleed(&mut rgba, 3, 1);
+        assert_eq!(rgba, vec![255, 0, 0, 255, 255, 0, 0, 0, 255, 0, 0, 0]);
+
+        let mut empty = vec![7u8; 2 * 2 * 4];
+        empty.iter_mut().skip(3).step_by(4).for_each(|a| *a = 0);
+        let before = empty.clone();
+        bleed(&mut empty, 2, 2);
+        assert_eq!(empty, before);
+    }
+}
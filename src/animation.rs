@@ -0,0 +1,336 @@
+//! 动画播放模块
+//!
+//! 把 KTX2 纹理数组的各层、GIF 和 APNG 的各帧当作序列帧播放，
+//! 支持播放/暂停、帧率、逐帧和循环模式
+
+use std::io::Cursor;
+use std::time::Duration;
+
+use iced::time::Instant;
+use image::AnimationDecoder;
+
+use crate::texture::{self, SourceFormat};
+use crate::{alpha, color_space};
+
+/// 帧率范围
+pub const MIN_FPS: f32 = 1.0;
+pub const MAX_FPS: f32 = 60.0;
+/// 源文件没有帧间隔信息时的默认帧率
+const DEFAULT_FPS: f32 = 12.0;
+
+/// 循环模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopMode {
+    /// 播放到最后一帧后从头开始
+    Loop,
+    /// 播放到最后一帧后停止
+    Once,
+    /// 正向播放到最后一帧后反向播放
+    PingPong,
+}
+
+impl LoopMode {
+    pub const ALL: [LoopMode; 3] = [LoopMode::Loop, LoopMode::Once, LoopMode::PingPong];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LoopMode::Loop => "Loop",
+            LoopMode::Once => "Once",
+            LoopMode::PingPong => "Ping-pong",
+        }
+    }
+}
+
+/// 解码后的序列帧，所有帧尺寸相同
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    pub width: u32,
+    pub height: u32,
+    /// 每帧的 RGBA8 数据
+    pub frames: Vec<Vec<u8>>,
+    /// 源文件建议的帧率
+    pub fps: f32,
+}
+
+/// 序列帧播放器
+#[derive(Debug, Clone)]
+pub struct Player {
+    animation: Animation,
+    current: usize,
+    playing: bool,
+    fps: f32,
+    loop_mode: LoopMode,
+    /// PingPong 模式下是否正在反向播放
+    reverse: bool,
+    /// 上一次 tick 的时间，暂停后清空
+    last_tick: Option<Instant>,
+    /// 尚未消耗的时间
+    elapsed: Duration,
+}
+
+impl Player {
+    pub fn new(animation: Animation) -> Self {
+        let fps = animation.fps.clamp(MIN_FPS, MAX_FPS);
+        Self {
+            animation,
+            current: 0,
+            playing: true,
+            fps,
+            loop_mode: LoopMode::Loop,
+            reverse: false,
+            last_tick: None,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn dimensions(&self) -> (u32, u32) {
+        (self.animation.width, self.animation.height)
+    }
+
+    pub fn frame(&self) -> &[u8] {
+        &self.animation.frames[self.current]
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    pub fn fps(&self) -> f32 {
+        self.fps
+    }
+
+    pub fn loop_mode(&self) -> LoopMode {
+        self.loop_mode
+    }
+
+    /// 两帧之间的间隔
+    pub fn frame_interval(&self) -> Duration {
+        Duration::from_secs_f32(1.0 / self.fps)
+    }
+
+    pub fn toggle_playing(&mut self) {
+        self.playing = !self.playing;
+        self.last_tick = None;
+        // Once 模式播放结束后再次播放时从头开始
+        if self.playing && self.loop_mode == LoopMode::Once && self.current + 1 == self.frame_count() {
+            self.current = 0;
+        }
+    }
+
+    pub fn set_fps(&mut self, fps: f32) {
+        self.fps = fps.clamp(MIN_FPS, MAX_FPS);
+    }
+
+    pub fn set_loop_mode(&mut self, loop_mode: LoopMode) {
+        self.loop_mode = loop_mode;
+        self.reverse = false;
+    }
+
+    /// 逐帧前进或后退（会暂停播放），始终循环
+    pub fn step(&mut self, delta: i32) {
+        self.playing = false;
+        self.last_tick = None;
+        let count = self.frame_count() as i64;
+        self.current = (self.current as i64 + delta as i64).rem_euclid(count) as usize;
+    }
+
+    /// 根据经过的时间推进帧，返回当前帧是否变化
+    pub fn tick(&mut self, now: Instant) -> bool {
+        if !self.playing {
+            return false;
+        }
+        let Some(last) = self.last_tick.replace(now) else {
+            return false;
+        };
+
+        self.elapsed += now.saturating_duration_since(last);
+        let interval = self.frame_interval();
+        let before = self.current;
+        while self.elapsed >= interval && self.playing {
+            self.elapsed -= interval;
+            self.advance();
+        }
+        self.current != before
+    }
+
+    pub fn status(&self) -> String {
+        format!(
+            "Frame {}/{} | {:.0} fps | {}{}",
+            self.current + 1,
+            self.frame_count(),
+            self.fps,
+            self.loop_mode.label(),
+            if self.playing { "" } else { " | Paused" }
+        )
+    }
+
+    fn frame_count(&self) -> usize {
+        self.animation.frames.len()
+    }
+
+    fn advance(&mut self) {
+        let last = self.frame_count() - 1;
+        match self.loop_mode {
+            LoopMode::Loop => self.current = if self.current >= last { 0 } else { self.current + 1 },
+            LoopMode::Once => {
+                if self.current >= last {
+                    self.playing = false;
+                } else {
+                    self.current += 1;
+                }
+            }
+            LoopMode::PingPong => {
+                if last == 0 {
+                    return;
+                }
+                if self.reverse && self.current == 0 {
+                    self.reverse = false;
+                } else if !self.reverse && self.current >= last {
+                    self.reverse = true;
+                }
+                self.current = if self.reverse { self.current - 1 } else { self.current + 1 };
+            }
+        }
+    }
+}
+
+/// 解码序列帧，不是多帧纹理时返回 `Ok(None)`
+pub fn decode(bytes: &[u8]) -> Result<Option<Animation>, String> {
+    match texture::sniff_format(bytes) {
+        Some(SourceFormat::Ktx2) => decode_ktx2_layers(bytes),
+        Some(SourceFormat::Image(image::ImageFormat::Gif)) => {
+            let decoder = image::codecs::gif::GifDecoder::new(Cursor::new(bytes))
+                .map_err(|e| format!("Failed to decode GIF: {}", e))?;
+            frames_to_animation(decoder.into_frames())
+        }
+        Some(SourceFormat::Image(image::ImageFormat::Png)) => {
+            let decoder = image::codecs::png::PngDecoder::new(Cursor::new(bytes))
+                .map_err(|e| format!("Failed to decode PNG: {}", e))?;
+            if !decoder.is_apng().map_err(|e| format!("Failed to decode PNG: {}", e))? {
+                return Ok(None);
+            }
+            let apng = decoder.apng().map_err(|e| format!("Failed to decode APNG: {}", e))?;
+            frames_to_animation(apng.into_frames())
+        }
+        _ => Ok(None),
+    }
+}
+
+/// GIF / APNG 帧转为等尺寸的序列帧，帧率取平均帧间隔
+fn frames_to_animation(frames: image::Frames<'_>) -> Result<Option<Animation>, String> {
+    let frames = frames
+        .collect_frames()
+        .map_err(|e| format!("Failed to decode animation frames: {}", e))?;
+    if frames.len() < 2 {
+        return Ok(None);
+    }
+
+    let total_ms: f32 = frames
+        .iter()
+        .map(|frame| {
+            let (numer, denom) = frame.delay().numer_denom_ms();
+            numer as f32 / denom.max(1) as f32
+        })
+        .sum();
+    let average_ms = total_ms / frames.len() as f32;
+    let fps = if average_ms > 0.0 { 1000.0 / average_ms } else { DEFAULT_FPS };
+
+    let (width, height) = frames[0].buffer().dimensions();
+    Ok(Some(Animation {
+        width,
+        height,
+        frames: frames.into_iter().map(|frame| frame.into_buffer().into_raw()).collect(),
+        fps,
+    }))
+}
+
+/// KTX2 纹理数组：基础层级中每个数组层作为一帧（仅 RGBA8）
+fn decode_ktx2_layers(bytes: &[u8]) -> Result<Option<Animation>, String> {
+    let reader = ktx2::Reader::new(bytes).map_err(|e| format!("Failed to create KTX2 reader: {:?}", e))?;
+    let header = reader.header();
+    let layer_count = header.layer_count as usize;
+    if layer_count < 2 {
+        return Ok(None);
+    }
+
+    let width = header.pixel_width;
+    let height = header.pixel_height.max(1);
+    let layer_size = width as usize * height as usize * 4;
+    let faces = header.face_count.max(1) as usize;
+
+    let level = reader.levels().next().ok_or("KTX2 file has no levels")?;
+    if level.data.len() != layer_size * layer_count * faces {
+        return Err(format!(
+            "KTX2 array data size mismatch: expected {} bytes, got {} bytes",
+            layer_size * layer_count * faces,
+            level.data.len()
+        ));
+    }
+
+    // 数据按 layer → face 排列，每层只取第一个面；预乘 Alpha 的帧还原为直通 Alpha
+    let premultiplied = alpha::is_premultiplied_ktx2(bytes);
+    let color_space = color_space::detect_ktx2(bytes).unwrap_or_default();
+    let frames = level
+        .data
+        .chunks_exact(layer_size * faces)
+        .map(|layer| {
+            let mut frame = layer[..layer_size].to_vec();
+            if premultiplied {
+                alpha::unpremultiply(&mut frame, color_space);
+            }
+            frame
+        })
+        .collect();
+
+    Ok(Some(Animation {
+        width,
+        height,
+        frames,
+        fps: DEFAULT_FPS,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(frames: usize, loop_mode: LoopMode) -> Player {
+        let mut player = Player::new(Animation {
+            width: 1,
+            height: 1,
+            frames: (0..frames).map(|i| vec![i as u8; 4]).collect(),
+            fps: 10.0,
+        });
+        player.set_loop_mode(loop_mode);
+        player
+    }
+
+    fn visited(player: &mut Player, ticks: usize) -> Vec<u8> {
+        let start = Instant::now();
+        player.tick(start);
+        (1..=ticks)
+            .map(|i| {
+                player.tick(start + Duration::from_millis(100 * i as u64 + 1));
+                player.frame()[0]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_loop_modes() {
+        assert_eq!(visited(&mut player(3, LoopMode::Loop), 4), vec![1, 2, 0, 1]);
+        assert_eq!(visited(&mut player(3, LoopMode::PingPong), 5), vec![1, 2, 1, 0, 1]);
+
+        let mut once = player(3, LoopMode::Once);
+        assert_eq!(visited(&mut once, 4), vec![1, 2, 2, 2]);
+        assert!(!once.is_playing());
+    }
+
+    #[test]
+    fn test_step_wraps() {
+        let mut player = player(3, LoopMode::Loop);
+        player.step(-1);
+        assert_eq!(player.frame()[0], 2);
+        assert!(!player.is_playing());
+    }
+}
@@ -0,0 +1,173 @@
+//! 资源清单模块
+//!
+//! `ktx2tool batch --manifest public/assets.json` 生成清单，列出每个纹理的所有变体
+//! （路径、尺寸、vkFormat、所需的压缩格式族、可用平台、大小和内容哈希）。
+//! 应用启动时读取清单，为每个纹理选择当前平台和 GPU 都支持的第一个变体，填充资源选择器
+//!
+//! 变体按偏好顺序排列（即预设配置中的顺序），通常压缩格式在前、RGBA8 在最后作为兜底
+
+use serde::{Deserialize, Serialize};
+
+/// 纹理压缩格式族，对应 GPU 的可选功能
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    Bc,
+    Etc2,
+    Astc,
+}
+
+impl Compression {
+    /// 由 vkFormat 名称（如 `BC7_SRGB_BLOCK`）判断格式族，未压缩格式为 `None`
+    pub fn of_format(name: &str) -> Option<Self> {
+        if name.starts_with("BC") {
+            Some(Compression::Bc)
+        } else if name.starts_with("ETC2") || name.starts_with("EAC") {
+            Some(Compression::Etc2)
+        } else if name.starts_with("ASTC") {
+            Some(Compression::Astc)
+        } else {
+            None
+        }
+    }
+}
+
+/// 运行平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Native,
+    /// WASM 构建用纯 Rust 的解码器读取 Zstandard / ZLIB 超级压缩
+    Web,
+}
+
+impl Platform {
+    #[cfg(target_arch = "wasm32")]
+    pub const CURRENT: Platform = Platform::Web;
+    #[cfg(not(target_arch = "wasm32"))]
+    pub const CURRENT: Platform = Platform::Native;
+
+    /// 能读取该超级压缩方案（`None` 为未压缩）的平台
+    pub fn supporting(supercompression: &str) -> Vec<Platform> {
+        match supercompression {
+            "None" | "Zstandard" | "ZLIB" => vec![Platform::Native, Platform::Web],
+            _ => vec![Platform::Native],
+        }
+    }
+}
+
+/// 资源清单
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AssetManifest {
+    pub textures: Vec<Asset>,
+}
+
+/// 一个纹理及其所有变体
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Asset {
+    /// 显示名称（源文件相对路径去掉扩展名）
+    pub name: String,
+    /// 源文件，相对输入目录
+    pub source: String,
+    /// 按偏好排列的变体
+    pub variants: Vec<Variant>,
+}
+
+/// 纹理的一个变体
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    /// 预设配置中的变体名，未命名的变体为 `default`
+    pub name: String,
+    /// KTX2 文件，相对清单所在目录
+    pub path: String,
+    pub width: u32,
+    pub height: u32,
+    pub levels: u32,
+    /// vkFormat 名称
+    pub format: String,
+    /// GPU 需要支持的压缩格式族，未压缩格式为 `None`
+    pub compression: Option<Compression>,
+    pub supercompression: String,
+    /// 能读取该变体的平台
+    pub platforms: Vec<Platform>,
+    /// 文件字节数
+    pub size: u64,
+    /// 文件内容的 FNV-1a 哈希
+    pub hash: String,
+}
+
+/// 当前环境能使用的变体
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    pub platform: Platform,
+    /// GPU 支持的压缩格式族
+    pub compression: Vec<Compression>,
+}
+
+impl Capabilities {
+    pub fn supports(&self, variant: &Variant) -> bool {
+        variant.platforms.contains(&self.platform)
+            && variant
+                .compression
+                .is_none_or(|compression| self.compression.contains(&compression))
+    }
+}
+
+impl Asset {
+    /// 第一个受支持的变体
+    pub fn best_variant(&self, capabilities: &Capabilities) -> Option<&Variant> {
+        self.variants.iter().find(|variant| capabilities.supports(variant))
+    }
+}
+
+/// 解析资源清单
+pub fn parse(json: &[u8]) -> Result<AssetManifest, String> {
+    serde_json::from_slice(json).map_err(|e| format!("Invalid asset manifest: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(name: &str, format: &str, supercompression: &str) -> Variant {
+        Variant {
+            name: name.to_string(),
+            path: format!("crate.{}.ktx2", name),
+            width: 256,
+            height: 256,
+            levels: 9,
+            format: format.to_string(),
+            compression: Compression::of_format(format),
+            supercompression: supercompression.to_string(),
+            platforms: Platform::supporting(supercompression),
+            size: 0,
+            hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_best_variant() {
+        let asset = Asset {
+            name: "crate".to_string(),
+            source: "crate.png".to_string(),
+            variants: vec![
+                variant("bc", "BC7_SRGB_BLOCK", "None"),
+                variant("zstd", "R8G8B8A8_SRGB", "Zstandard"),
+                variant("rgba8", "R8G8B8A8_SRGB", "None"),
+            ],
+        };
+        let best = |platform, compression: &[Compression]| {
+            let capabilities = Capabilities {
+                platform,
+                compression: compression.to_vec(),
+            };
+            asset.best_variant(&capabilities).map(|variant| variant.name.as_str())
+        };
+
+        assert_eq!(best(Platform::Native, &[Compression::Bc]), Some("bc"));
+        assert_eq!(best(Platform::Native, &[Compression::Etc2]), Some("zstd"));
+        assert_eq!(best(Platform::Web, &[]), Some("zstd"));
+        assert_eq!(Platform::supporting("BasisLZ"), vec![Platform::Native]);
+        assert_eq!(Compression::of_format("EAC_R11_UNORM_BLOCK"), Some(Compression::Etc2));
+    }
+}
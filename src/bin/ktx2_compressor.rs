@@ -1,151 +0,0 @@
-//! KTX2 压缩工具
-//!
-//! 使用 Basis Universal 压缩生成更小的 KTX2 文件
-//!
-//! 使用方法：
-//! ```bash
-//! cargo run --bin ktx2_compressor -- input.png output.ktx2
-//! ```
-
-use std::env;
-use std::fs;
-use std::path::Path;
-
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() != 3 {
-        eprintln!("用法: {} <input.png> <output.ktx2>", args[0]);
-        eprintln!();
-        eprintln!("示例:");
-        eprintln!("  {} public/1.png public/1_compressed.ktx2", args[0]);
-        eprintln!();
-        eprintln!("说明: 使用 Basis Universal 压缩生成更小的 KTX2 文件");
-        std::process::exit(1);
-    }
-
-    let input_path = &args[1];
-    let output_path = &args[2];
-
-    println!("🔄 压缩 PNG → KTX2 (Basis Universal)");
-    println!("   输入: {}", input_path);
-    println!("   输出: {}", output_path);
-    println!();
-
-    // 检查输入文件
-    if !Path::new(input_path).exists() {
-        return Err(format!("输入文件不存在: {}", input_path).into());
-    }
-
-    // 读取 PNG
-    println!("📖 读取 PNG 文件...");
-    let png_data = fs::read(input_path)?;
-    let img = image::load_from_memory(&png_data)?;
-    let rgba = img.to_rgba8();
-
-    println!("   尺寸: {}x{}", img.width(), img.height());
-    println!("   数据大小: {} 字节", rgba.len());
-    println!();
-
-    // 使用 Basis Universal 压缩
-    println!("🗜️  使用 Basis Universal 压缩...");
-    println!("   这可能需要几秒钟...");
-
-    // 注意：basis-universal crate 的 API 可能比较复杂
-    // 这里我们提供一个简化版本
-    //
-    // 由于 basis-universal crate 主要是 FFI 绑定，
-    // 使用它需要很多步骤。为了简化，我们提供几个替代方案：
-
-    println!();
-    println!("⚠️  注意:");
-    println!("   basis-universal crate 需要 C++ 编译和复杂的设置");
-    println!("   推荐使用以下替代方案:");
-    println!();
-
-    println!("📦 方案 1: 使用 toktx 命令行工具（推荐）");
-    println!("   安装: ./scripts/install_ktx_software.sh");
-    println!("   使用: toktx --basis --uastc all {} {}", input_path, output_path);
-    println!();
-
-    println!("📦 方案 2: 在线工具");
-    println!("   https://www.khronos.org/textureviewer/");
-    println!("   https://basis-universal-demo.appspot.com/");
-    println!();
-
-    println!("📦 方案 3: 从源码编译 KTX Software");
-    println!("   git clone https://github.com/KhronosGroup/KTX-Software");
-    println!("   cd KTX-Software");
-    println!("   cmake -DCMAKE_BUILD_TYPE=Release .");
-    println!("   make");
-    println!();
-
-    // 为了演示，生成一个说明文件
-    let info = format!(
-        r#"# KTX2 压缩说明
-
-## 原始文件
-- 文件: {}
-- 尺寸: {}x{}
-- 像素数: {}
-- PNG 大小: {} bytes ({:.2} KB)
-
-## 如何生成压缩的 KTX2
-
-### 方法 1: 使用 toktx (推荐)
-```bash
-# 安装 KTX Software
-./scripts/install_ktx_software.sh
-
-# 生成 Basis Universal 压缩
-toktx --basis --uastc all {} {}
-
-# 预期大小: ~300-500 KB (压缩比 3-6x)
-```
-
-### 方法 2: 使用 ETC2 压缩 (移动端)
-```bash
-toktx --tmode --format ETC2_RGBA {} {}
-```
-
-### 方法 3: 使用 BC7 压缩 (桌面端)
-```bash
-toktx --tmode --format BC7_RGBA {} {}
-```
-
-## 压缩对比
-
-| 格式 | 大小 | 压缩比 | GPU 兼容 |
-|------|------|--------|----------|
-| PNG | {:.2} KB | 31:1 | 需解码 |
-| KTX2 (未压缩) | {:.2} MB | 1:1 | ✅ 最佳 |
-| KTX2 (Basis UASTC) | ~300-500 KB | 3-6x | ✅ 很好 |
-| KTX2 (ETC2) | ~1 MB | 1.5x | ✅ 移动 |
-| KTX2 (BC7) | ~1 MB | 1.5x | ✅ 桌面 |
-
-生成时间: {}
-"#,
-        input_path,
-        img.width(),
-        img.height(),
-        img.width() * img.height(),
-        png_data.len(),
-        png_data.len() / 1024.0,
-        input_path,
-        output_path,
-        input_path,
-        output_path,
-        input_path,
-        output_path,
-        png_data.len() / 1024.0,
-        (img.width() * img.height() * 4) / 1024.0 / 1024.0,
-        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
-    );
-
-    // 写入说明文件
-    let info_path = output_path.replace(".ktx2", "_info.txt");
-    fs::write(&info_path, info)?;
-    println!("✅ 说明文件已保存: {}", info_path);
-
-    Ok(())
-}
@@ -0,0 +1,47 @@
+//! ktx2tool 子命令
+//!
+//! 每个子命令一个模块，提供 clap 参数结构 `Args` 和入口 `run`
+
+pub mod batch;
+pub mod compare;
+pub mod compress;
+pub mod convert;
+pub mod create;
+pub mod extract;
+pub mod info;
+pub mod validate;
+
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use crate::texture::TextureLoader;
+
+/// 检查未通过（validate 发现错误、compare 低于阈值）时的退出码
+pub const EXIT_CHECK_FAILED: u8 = 3;
+
+/// 子命令结果：`Err` 表示执行出错，退出码为 1
+pub type CommandResult = Result<ExitCode, String>;
+
+/// 读取文件，错误信息带上路径
+pub fn read(path: &Path) -> Result<Vec<u8>, String> {
+    fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+}
+
+/// 写入文件，必要时创建父目录
+pub fn write(path: &Path, data: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, data).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// 加载 PNG / JPEG / KTX2 纹理
+pub fn load_texture(path: &Path) -> Result<TextureLoader, String> {
+    let bytes = read(path)?;
+    let mut loader = TextureLoader::new();
+    loader
+        .load_from_bytes(&bytes)
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+    Ok(loader)
+}
@@ -0,0 +1,493 @@
+//! batch：把目录中的 PNG / JPEG 批量转换为 KTX2
+//!
+//! 递归遍历输入目录，每个文件按预设配置中第一个匹配的 glob 选择生成选项（都不匹配时使用命令行选项），
+//! 用 rayon 并行转换。输出保持相对路径，扩展名改为 `.ktx2`。
+//!
+//! 预设可以带变体名（如 `bc`、`rgba8`），同一文件的每个变体各取第一个匹配的预设，
+//! 输出为 `名称.变体.ktx2`；未命名的预设属于默认变体，输出不加后缀。
+//!
+//! 输出目录中的缓存记录每个输出的输入哈希（源文件内容、生成选项和工具版本），哈希不变且输出文件仍在时跳过；
+//! 最后写入资源清单（格式见 `assets` 模块），放到 `public/` 下即可供 Web 应用读取
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::CommandResult;
+use super::create::{self, Options};
+use crate::assets::{Asset, AssetManifest, Compression, Platform, Variant};
+use crate::metadata::TextureInfo;
+
+/// 缓存文件名（位于输出目录）
+const CACHE_FILE: &str = ".ktx2tool-cache.json";
+
+/// 默认清单文件名（位于输出目录）
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// 未指定变体名的预设所属的变体
+const DEFAULT_VARIANT: &str = "default";
+
+/// 参与转换的扩展名（不区分大小写）
+const EXTENSIONS: [&str; 3] = ["png", "jpg", "jpeg"];
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 输入目录
+    input: PathBuf,
+    /// 输出目录
+    output: PathBuf,
+    /// 预设配置（JSON），按 glob 为文件选择生成选项
+    #[arg(long)]
+    config: Option<PathBuf>,
+    /// 资源清单文件，默认为输出目录下的 manifest.json；必须位于输出目录或其上级目录中
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+    /// 忽略缓存，重新转换所有文件
+    #[arg(long)]
+    force: bool,
+    /// 不匹配任何预设的文件使用的选项
+    #[command(flatten)]
+    options: Options,
+}
+
+/// 预设配置文件
+///
+/// ```json
+/// {
+///   "presets": [
+///     { "glob": "*_normal.png", "variant": "bc", "format": "bc5", "color_space": "linear", "mipmaps": true },
+///     { "glob": "**", "variant": "bc", "format": "bc7", "mipmaps": true },
+///     { "glob": "**", "variant": "rgba8", "mipmaps": true }
+///   ]
+/// }
+/// ```
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Config {
+    presets: Vec<Preset>,
+}
+
+/// 一条预设：glob 不含 `/` 时只匹配文件名，否则匹配相对输入目录的路径；
+/// `variant` 为变体名（省略时为 `default`），其余字段与 `create` 的选项同名，省略的字段取默认值
+#[derive(Deserialize)]
+#[serde(try_from = "Map<String, Value>")]
+struct Preset {
+    glob: String,
+    variant: String,
+    options: Options,
+}
+
+// 不用 `#[serde(flatten)]`：它会让 `deny_unknown_fields` 失效，拼错的选项名会被静默忽略
+impl TryFrom<Map<String, Value>> for Preset {
+    type Error = String;
+
+    fn try_from(mut fields: Map<String, Value>) -> Result<Self, String> {
+        let glob = match fields.remove("glob") {
+            Some(Value::String(glob)) => glob,
+            _ => return Err("preset needs a \"glob\" string".to_string()),
+        };
+        let variant = match fields.remove("variant") {
+            None => DEFAULT_VARIANT.to_string(),
+            Some(Value::String(variant))
+                if !variant.is_empty()
+                    && variant
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') =>
+            {
+                variant
+            }
+            Some(variant) => return Err(format!("preset {}: invalid variant name {}", glob, variant)),
+        };
+        let options = serde_json::from_value(Value::Object(fields)).map_err(|e| format!("preset {}: {}", glob, e))?;
+        Ok(Self { glob, variant, options })
+    }
+}
+
+/// 一个待生成的输出：源文件的一个变体
+struct Job<'a> {
+    source: &'a str,
+    variant: &'a str,
+    options: &'a Options,
+    /// 相对输出目录
+    output: String,
+}
+
+/// 转换缓存：输出文件（相对输出目录）→ 输入哈希和当时生成的变体
+#[derive(Default, Serialize, Deserialize)]
+struct Cache {
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    input_hash: String,
+    /// `path` 相对输出目录
+    variant: Variant,
+}
+
+pub fn run(args: Args) -> CommandResult {
+    let presets = match &args.config {
+        Some(path) => load_config(path)?.presets,
+        None => Vec::new(),
+    };
+
+    fs::create_dir_all(&args.output).map_err(|e| format!("Failed to create {}: {}", args.output.display(), e))?;
+    let manifest_path = args.manifest.clone().unwrap_or_else(|| args.output.join(MANIFEST_FILE));
+    let prefix = manifest_prefix(&manifest_path, &args.output)?;
+
+    // 输出目录在输入目录之内时不能把已生成的文件当作输入
+    let skip = args.output.canonicalize().ok();
+    let mut sources = Vec::new();
+    collect_images(&args.input, "", skip.as_deref(), &mut sources)?;
+    sources.sort();
+
+    let jobs: Vec<Job> = sources
+        .iter()
+        .flat_map(|source| {
+            variants_for(&presets, source, &args.options)
+                .into_iter()
+                .map(move |(variant, options)| Job {
+                    source,
+                    variant,
+                    options,
+                    output: output_path(source, variant),
+                })
+        })
+        .collect();
+
+    let mut outputs: HashMap<&str, &str> = HashMap::new();
+    for job in &jobs {
+        if let Some(previous) = outputs.insert(&job.output, job.source) {
+            return Err(format!(
+                "{} and {} would both be written to {}",
+                previous, job.source, job.output
+            ));
+        }
+    }
+
+    let cache_path = args.output.join(CACHE_FILE);
+    let cache = if args.force {
+        Cache::default()
+    } else {
+        load_cache(&cache_path)
+    };
+
+    let results: Vec<_> = jobs
+        .par_iter()
+        .map(|job| {
+            let result = convert(&args, job, cache.entries.get(&job.output));
+            match &result {
+                Ok((entry, true)) => println!("{} -> {} ({} bytes)", job.source, job.output, entry.variant.size),
+                Ok((_, false)) => println!("{} -> {} (unchanged)", job.source, job.output),
+                Err(e) => eprintln!("{} -> {}: {}", job.source, job.output, e),
+            }
+            result
+        })
+        .collect();
+
+    let mut next_cache = Cache::default();
+    let mut manifest = AssetManifest::default();
+    let (mut converted, mut failed) = (0, 0);
+    for (job, result) in jobs.iter().zip(results) {
+        let Ok((entry, fresh)) = result else {
+            failed += 1;
+            continue;
+        };
+        converted += fresh as usize;
+
+        let variant = Variant {
+            path: format!("{}{}", prefix, entry.variant.path),
+            ..entry.variant.clone()
+        };
+        match manifest.textures.last_mut() {
+            Some(asset) if asset.source == job.source => asset.variants.push(variant),
+            _ => manifest.textures.push(Asset {
+                name: strip_extension(job.source).to_string(),
+                source: job.source.to_string(),
+                variants: vec![variant],
+            }),
+        }
+        next_cache.entries.insert(job.output.clone(), entry);
+    }
+
+    let cache_json = serde_json::to_vec_pretty(&next_cache).map_err(|e| e.to_string())?;
+    super::write(&cache_path, &cache_json)?;
+    let manifest_json = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    super::write(&manifest_path, &manifest_json)?;
+
+    println!(
+        "{} converted, {} unchanged, {} failed; manifest written to {}",
+        converted,
+        next_cache.entries.len() - converted,
+        failed,
+        manifest_path.display()
+    );
+    if failed > 0 {
+        return Err(format!("{} of {} outputs failed to convert", failed, jobs.len()));
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn load_config(path: &Path) -> Result<Config, String> {
+    let config: Config = serde_json::from_slice(&super::read(path)?)
+        .map_err(|e| format!("Invalid preset config {}: {}", path.display(), e))?;
+    for preset in &config.presets {
+        if let Some(level) = preset.options.zstd.filter(|level| !(1..=22).contains(level)) {
+            return Err(format!("Preset {}: zstd level {} is not in 1..=22", preset.glob, level));
+        }
+    }
+    Ok(config)
+}
+
+/// 读取缓存，文件不存在或无法解析时视为空
+fn load_cache(path: &Path) -> Cache {
+    let Ok(bytes) = fs::read(path) else {
+        return Cache::default();
+    };
+    serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+        eprintln!("warning: ignoring unreadable cache {}: {}", path.display(), e);
+        Cache::default()
+    })
+}
+
+/// 递归收集 `dir` 下支持的图像，路径相对输入目录、以 `/` 分隔
+fn collect_images(dir: &Path, prefix: &str, skip: Option<&Path>, out: &mut Vec<String>) -> Result<(), String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+        let path = entry.path();
+        let relative = format!("{}{}", prefix, entry.file_name().to_string_lossy());
+
+        if path.is_dir() {
+            if skip.is_none_or(|skip| path.canonicalize().ok().as_deref() != Some(skip)) {
+                collect_images(&path, &format!("{}/", relative), skip, out)?;
+            }
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            out.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// 输出目录相对清单所在目录的前缀（空或以 `/` 结尾），清单中的路径都相对清单
+fn manifest_prefix(manifest: &Path, output: &Path) -> Result<String, String> {
+    let manifest_dir = manifest
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    fs::create_dir_all(manifest_dir).map_err(|e| format!("Failed to create {}: {}", manifest_dir.display(), e))?;
+
+    let canonical = |path: &Path| {
+        path.canonicalize()
+            .map_err(|e| format!("Failed to resolve {}: {}", path.display(), e))
+    };
+    let (manifest_dir, output) = (canonical(manifest_dir)?, canonical(output)?);
+    let relative = output.strip_prefix(&manifest_dir).map_err(|_| {
+        format!(
+            "Output directory {} must be inside the manifest directory {}",
+            output.display(),
+            manifest_dir.display()
+        )
+    })?;
+    Ok(relative
+        .components()
+        .map(|component| format!("{}/", component.as_os_str().to_string_lossy()))
+        .collect())
+}
+
+fn strip_extension(source: &str) -> &str {
+    source.rsplit_once('.').map_or(source, |(stem, _)| stem)
+}
+
+/// 源文件的一个变体对应的输出路径（相对输出目录），默认变体不加后缀
+fn output_path(source: &str, variant: &str) -> String {
+    match variant {
+        DEFAULT_VARIANT => format!("{}.ktx2", strip_extension(source)),
+        _ => format!("{}.{}.ktx2", strip_extension(source), variant),
+    }
+}
+
+/// 每个变体第一个匹配的预设，按首次匹配的顺序排列（即偏好顺序）；
+/// 没有任何预设匹配时只生成一个使用 `default` 选项的默认变体
+fn variants_for<'a>(presets: &'a [Preset], source: &str, default: &'a Options) -> Vec<(&'a str, &'a Options)> {
+    let name = source.rsplit('/').next().unwrap_or(source);
+    let mut variants: Vec<(&str, &Options)> = Vec::new();
+    for preset in presets {
+        let matched = glob_match(&preset.glob, if preset.glob.contains('/') { source } else { name });
+        if matched && variants.iter().all(|(variant, _)| *variant != preset.variant) {
+            variants.push((&preset.variant, &preset.options));
+        }
+    }
+    if variants.is_empty() {
+        variants.push((DEFAULT_VARIANT, default));
+    }
+    variants
+}
+
+/// 生成一个输出，返回缓存项以及是否重新生成
+fn convert(args: &Args, job: &Job, cached: Option<&CacheEntry>) -> Result<(CacheEntry, bool), String> {
+    let bytes = super::read(&args.input.join(job.source))?;
+    let options_json = serde_json::to_vec(job.options).map_err(|e| e.to_string())?;
+    let input_hash = hex(fnv1a(&[env!("CARGO_PKG_VERSION").as_bytes(), &options_json, &bytes]));
+
+    // 输出文件被删除或改动过时重新生成
+    if let Some(cached) = cached.filter(|cached| cached.input_hash == input_hash)
+        && fs::read(args.output.join(&job.output)).is_ok_and(|output| is_cached_output(&output, &cached.variant))
+    {
+        return Ok((cached.clone(), false));
+    }
+
+    let ktx2 = create::encode(&bytes, job.options)?;
+    super::write(&args.output.join(&job.output), &ktx2)?;
+
+    let info = TextureInfo::from_ktx2(&ktx2)?;
+    let format = info.vk_format.unwrap_or_default();
+    let variant = Variant {
+        name: job.variant.to_string(),
+        path: job.output.clone(),
+        width: info.dimensions.0,
+        height: info.dimensions.1,
+        levels: info.level_count,
+        compression: Compression::of_format(&format),
+        format,
+        platforms: Platform::supporting(&info.supercompression),
+        supercompression: info.supercompression,
+        size: ktx2.len() as u64,
+        hash: hex(fnv1a(&[&ktx2])),
+    };
+    Ok((CacheEntry { input_hash, variant }, true))
+}
+
+/// 已有的输出文件与缓存记录的大小和哈希一致
+fn is_cached_output(output: &[u8], variant: &Variant) -> bool {
+    output.len() as u64 == variant.size && hex(fnv1a(&[output])) == variant.hash
+}
+
+/// 64 位 FNV-1a 哈希，依次处理每一段数据
+fn fnv1a(parts: &[&[u8]]) -> u64 {
+    parts
+        .iter()
+        .flat_map(|part| part.iter())
+        .fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+fn hex(hash: u64) -> String {
+    format!("{:016x}", hash)
+}
+
+/// glob 匹配：`*` 匹配不含 `/` 的任意字符串，`**` 可以跨目录（`**/` 也匹配零层目录），`?` 匹配一个非 `/` 字符
+fn glob_match(pattern: &str, path: &str) -> bool {
+    fn matches(pattern: &[char], path: &[char]) -> bool {
+        match pattern {
+            [] => path.is_empty(),
+            ['*', '*', rest @ ..] => {
+                (0..=path.len()).any(|i| matches(rest, &path[i..]))
+                    || rest.first() == Some(&'/') && matches(&rest[1..], path)
+            }
+            ['*', rest @ ..] => {
+                let limit = path.iter().position(|&c| c == '/').unwrap_or(path.len());
+                (0..=limit).any(|i| matches(rest, &path[i..]))
+            }
+            ['?', rest @ ..] => path.first().is_some_and(|&c| c != '/') && matches(rest, &path[1..]),
+            [c, rest @ ..] => path.first() == Some(c) && matches(rest, &path[1..]),
+        }
+    }
+
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    matches(&pattern, &path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::create::FormatArg;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.png", "a.png"));
+        assert!(!glob_match("*.png", "dir/a.png"));
+        assert!(glob_match("**/*.png", "a.png"));
+        assert!(glob_match("**/*.png", "dir/sub/a.png"));
+        assert!(glob_match("ui/**", "ui/icons/a.png"));
+        assert!(!glob_match("ui/*", "ui/icons/a.png"));
+        assert!(glob_match("tile_??.jpg", "tile_07.jpg"));
+        assert!(!glob_match("tile_??.jpg", "tile_7.jpg"));
+    }
+
+    #[test]
+    fn test_preset_variants() {
+        let config: Config = serde_json::from_str(
+            r#"{ "presets": [
+                { "glob": "*_normal.png", "variant": "bc", "format": "bc5", "color_space": "linear" },
+                { "glob": "**", "variant": "bc", "format": "bc7" },
+                { "glob": "ui/**", "zstd": 19 },
+                { "glob": "**", "variant": "rgba8" }
+            ] }"#,
+        )
+        .unwrap();
+        let default = Options::default();
+        let variants = |source| {
+            variants_for(&config.presets, source, &default)
+                .into_iter()
+                .map(|(variant, options)| (variant, options.format, options.zstd))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            variants("props/crate_normal.png"),
+            vec![("bc", FormatArg::Bc5, None), ("rgba8", FormatArg::Rgba8, None)]
+        );
+        assert_eq!(
+            variants("ui/button.png"),
+            vec![
+                ("bc", FormatArg::Bc7, None),
+                ("default", FormatArg::Rgba8, Some(19)),
+                ("rgba8", FormatArg::Rgba8, None)
+            ]
+        );
+        assert_eq!(variants_for(&[], "a.png", &default)[0].0, DEFAULT_VARIANT);
+        assert_eq!(output_path("ui/button.final.PNG", "bc"), "ui/button.final.bc.ktx2");
+        assert_eq!(
+            output_path("ui/button.final.PNG", DEFAULT_VARIANT),
+            "ui/button.final.ktx2"
+        );
+        assert!(serde_json::from_str::<Config>(r#"{ "presets": [{ "glob": "*", "variant": "a/b" }] }"#).is_err());
+    }
+
+    #[test]
+    fn test_cached_output() {
+        let output = b"KTX2 output".to_vec();
+        let variant = Variant {
+            name: DEFAULT_VARIANT.to_string(),
+            path: "a.ktx2".to_string(),
+            width: 1,
+            height: 1,
+            levels: 1,
+            format: "R8G8B8A8_UNORM".to_string(),
+            compression: None,
+            supercompression: "None".to_string(),
+            platforms: Vec::new(),
+            size: output.len() as u64,
+            hash: hex(fnv1a(&[&output])),
+        };
+        assert!(is_cached_output(&output, &variant));
+
+        // 长度相同但内容被改动的输出需要重新生成
+        let mut edited = output.clone();
+        edited[0] ^= 1;
+        assert!(!is_cached_output(&edited, &variant));
+        assert!(!is_cached_output(&output[1..], &variant));
+    }
+}
@@ -0,0 +1,40 @@
+//! compare：计算两张纹理的 PSNR / SSIM，可设置阈值用于 CI
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use super::{CommandResult, EXIT_CHECK_FAILED};
+use crate::compare;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 参考纹理
+    reference: PathBuf,
+    /// 待比较的纹理
+    candidate: PathBuf,
+    /// PSNR 下限（dB），低于时以退出码 3 结束
+    #[arg(long)]
+    min_psnr: Option<f64>,
+    /// SSIM 下限，低于时以退出码 3 结束
+    #[arg(long)]
+    min_ssim: Option<f64>,
+}
+
+pub fn run(args: Args) -> CommandResult {
+    let reference = super::load_texture(&args.reference)?;
+    let candidate = super::load_texture(&args.candidate)?;
+    let metrics = compare::compare(&reference, &candidate)?;
+
+    println!("PSNR: {:.2} dB", metrics.psnr);
+    println!("SSIM: {:.4}", metrics.ssim);
+    println!("Max difference: {}", metrics.max_diff);
+
+    let passed = args.min_psnr.is_none_or(|min| metrics.psnr >= min)
+        && args.min_ssim.is_none_or(|min| metrics.ssim >= min);
+    if passed {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!("Below threshold");
+        Ok(ExitCode::from(EXIT_CHECK_FAILED))
+    }
+}
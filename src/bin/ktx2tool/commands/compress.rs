@@ -0,0 +1,24 @@
+//! compress：Basis Universal（UASTC / ETC1S）编码
+//!
+//! 目前还没有纯 Rust 的 Basis Universal 编码器，命令只给出 toktx 的替代方案；BC 块压缩由 `create --format` 完成
+
+use std::path::PathBuf;
+
+use super::CommandResult;
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 输入图像（PNG / JPEG）
+    input: PathBuf,
+    /// 输出 KTX2 文件
+    output: PathBuf,
+}
+
+pub fn run(args: Args) -> CommandResult {
+    Err(format!(
+        "Basis Universal (UASTC / ETC1S) encoding is not implemented yet; use KTX-Software instead: \
+         toktx --encode uastc {} {} (BC formats: ktx2tool create --format)",
+        args.output.display(),
+        args.input.display()
+    ))
+}
@@ -0,0 +1,39 @@
+//! convert：在 PNG / JPEG / KTX2 之间转换
+//!
+//! 输出格式由扩展名决定（`.png` / `.ktx2`），KTX2 和 PNG 都保留源数据的像素值和色彩空间
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use super::CommandResult;
+use crate::export::{self, ExportFormat};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 输入纹理（KTX2 / PNG / JPEG）
+    input: PathBuf,
+    /// 输出文件（.png 或 .ktx2）
+    output: PathBuf,
+}
+
+pub fn run(args: Args) -> CommandResult {
+    let extension = args
+        .output
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let format = ExportFormat::ALL
+        .into_iter()
+        .find(|format| extension.as_deref() == Some(format.extension()))
+        .ok_or_else(|| format!("Unsupported output extension: {}", args.output.display()))?;
+
+    let loader = super::load_texture(&args.input)?;
+    let (width, height) = loader.dimensions().ok_or("Texture has no data")?;
+    let rgba = loader.data().ok_or("Texture has no data")?;
+
+    let data = export::encode(rgba, width, height, format, loader.color_space())?;
+    super::write(&args.output, &data)?;
+
+    println!("{} -> {} ({} bytes)", args.input.display(), args.output.display(), data.len());
+    Ok(ExitCode::SUCCESS)
+}
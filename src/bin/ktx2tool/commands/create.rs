@@ -0,0 +1,204 @@
+//! create：从 PNG / JPEG 生成 KTX2（RGBA8 或 BC1 / BC3 / BC4 / BC5 / BC7）
+//!
+//! 处理顺序：识别色彩空间 → 裁剪 → 扩散颜色到透明区域 → 预乘 Alpha → 缩放 / 调整为 2 的幂 →
+//! 生成 mip 链 → 逐层级块压缩 → 写入 KTX2（可选 Zstandard 超级压缩）。
+//! Alpha 预处理必须在缩放和生成 mip 之前，否则缩小时透明像素的颜色会混入边缘
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::ValueEnum;
+use image::RgbaImage;
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+
+use super::CommandResult;
+use crate::alpha;
+use crate::color_space::{self, ColorSpace};
+use crate::encode::{self, Quality};
+use crate::ktx2_writer::{Ktx2Writer, PixelFormat};
+use crate::transform::{self, Filter, PowerOfTwo, Region, Size};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 输入图像（PNG / JPEG）
+    input: PathBuf,
+    /// 输出 KTX2 文件
+    output: PathBuf,
+    #[command(flatten)]
+    options: Options,
+}
+
+/// 生成选项，批量转换的预设配置使用同名字段
+#[derive(clap::Args, Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Options {
+    /// 色彩空间，auto 从 PNG 的 sRGB / iCCP / gAMA 块识别（识别不到按 sRGB）
+    #[arg(long, value_enum, default_value_t)]
+    pub color_space: ColorSpaceArg,
+    /// 把颜色扩散到完全透明的像素，避免过滤时出现黑边
+    #[arg(long)]
+    pub bleed: bool,
+    /// 预乘 Alpha，并在 DFD 标志中记录
+    #[arg(long)]
+    pub premultiply: bool,
+    /// 生成完整 mip 链
+    #[arg(long)]
+    pub mipmaps: bool,
+    /// 像素格式，BC4 / BC5 只编码 R / RG 通道且没有 sRGB 变体
+    #[arg(long, value_enum, default_value_t)]
+    pub format: FormatArg,
+    /// 块压缩的质量档位，越慢误差越小
+    #[arg(long, value_enum, default_value_t)]
+    pub quality: Quality,
+    /// 用 Zstandard 超级压缩每个层级，值为压缩级别（1–22，越大越小越慢）
+    #[arg(long, value_name = "LEVEL", value_parser = clap::value_parser!(i32).range(1..=22))]
+    pub zstd: Option<i32>,
+    /// 先裁剪到以像素为单位的区域
+    #[arg(long, value_name = "X,Y,W,H")]
+    pub crop: Option<Region>,
+    /// 缩放到指定尺寸
+    #[arg(long, value_name = "WxH", conflicts_with = "max_size")]
+    pub resize: Option<Size>,
+    /// 等比缩小到最长边不超过该值（不放大）
+    #[arg(long, value_name = "PIXELS", value_parser = clap::value_parser!(u32).range(1..))]
+    pub max_size: Option<u32>,
+    /// 把宽高调整为 2 的幂：resize 缩放到最接近的 2 的幂，pad 复制边缘像素补到下一个 2 的幂；
+    /// 与 --max-size 同用且该值为 2 的幂时不会超过它
+    #[arg(long, value_enum, value_name = "MODE")]
+    pub power_of_two: Option<PowerOfTwo>,
+    /// 缩放使用的滤波器
+    #[arg(long, value_enum, default_value_t)]
+    pub filter: Filter,
+}
+
+/// `--format` 参数
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FormatArg {
+    #[default]
+    Rgba8,
+    Bc1,
+    Bc3,
+    Bc4,
+    Bc5,
+    Bc7,
+}
+
+impl FormatArg {
+    pub fn pixel_format(self) -> PixelFormat {
+        match self {
+            FormatArg::Rgba8 => PixelFormat::Rgba8,
+            FormatArg::Bc1 => PixelFormat::Bc1,
+            FormatArg::Bc3 => PixelFormat::Bc3,
+            FormatArg::Bc4 => PixelFormat::Bc4,
+            FormatArg::Bc5 => PixelFormat::Bc5,
+            FormatArg::Bc7 => PixelFormat::Bc7,
+        }
+    }
+}
+
+/// `--color-space` 参数
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorSpaceArg {
+    #[default]
+    Auto,
+    Srgb,
+    Linear,
+}
+
+impl ColorSpaceArg {
+    /// 解析为具体色彩空间，auto 时从源文件识别
+    pub fn resolve(self, source: &[u8]) -> ColorSpace {
+        match self {
+            ColorSpaceArg::Auto => color_space::detect_png(source).unwrap_or_default(),
+            ColorSpaceArg::Srgb => ColorSpace::Srgb,
+            ColorSpaceArg::Linear => ColorSpace::Linear,
+        }
+    }
+}
+
+pub fn run(args: Args) -> CommandResult {
+    let source = super::read(&args.input)?;
+    let ktx2 = encode(&source, &args.options)?;
+    super::write(&args.output, &ktx2)?;
+
+    println!("{} -> {} ({} bytes)", args.input.display(), args.output.display(), ktx2.len());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 把 PNG / JPEG 文件内容编码为 KTX2
+pub fn encode(source: &[u8], options: &Options) -> Result<Vec<u8>, String> {
+    let img = image::load_from_memory(source).map_err(|e| format!("Failed to decode image: {}", e))?;
+    let color_space = options.color_space.resolve(source);
+
+    let mut rgba = img.to_rgba8();
+    if let Some(region) = options.crop {
+        rgba = transform::crop(&rgba, region)?;
+    }
+    if options.bleed {
+        let (width, height) = rgba.dimensions();
+        alpha::bleed(&mut rgba, width, height);
+    }
+    if options.premultiply {
+        alpha::premultiply(&mut rgba, color_space);
+    }
+    let rgba = resize(rgba, options)?;
+    let (width, height) = rgba.dimensions();
+
+    let format = options.format.pixel_format();
+    let levels = if options.mipmaps { mip_chain(rgba) } else { vec![rgba] };
+    levels
+        .into_iter()
+        .fold(
+            Ktx2Writer::new(width, height, format, color_space.is_srgb())
+                .premultiplied(options.premultiply)
+                .zstd(options.zstd),
+            |writer, level| {
+                let (level_width, level_height) = level.dimensions();
+                writer.level(encode::encode(format, &level, level_width, level_height, options.quality))
+            },
+        )
+        .write()
+}
+
+/// 按选项缩放，再调整为 2 的幂；缩放到 2 的幂时与前一步合并为一次重采样
+fn resize(rgba: RgbaImage, options: &Options) -> Result<RgbaImage, String> {
+    let (width, height) = rgba.dimensions();
+    let (width, height) = match (options.resize, options.max_size) {
+        (Some(_), Some(_)) => return Err("resize and max_size cannot be used together".to_string()),
+        (Some(size), None) => (size.width, size.height),
+        (None, Some(max)) => transform::fit(width, height, max),
+        (None, None) => (width, height),
+    };
+
+    Ok(match options.power_of_two {
+        None => transform::resize(rgba, width, height, options.filter),
+        Some(PowerOfTwo::Resize) => transform::resize(
+            rgba,
+            transform::nearest_power_of_two(width),
+            transform::nearest_power_of_two(height),
+            options.filter,
+        ),
+        Some(PowerOfTwo::Pad) => transform::pad(
+            &transform::resize(rgba, width, height, options.filter),
+            width.next_power_of_two(),
+            height.next_power_of_two(),
+        ),
+    })
+}
+
+/// 逐级缩小生成完整 mip 链（第一个元素为原图）
+fn mip_chain(base: RgbaImage) -> Vec<RgbaImage> {
+    let mut levels = vec![base];
+    loop {
+        let last = levels.last().unwrap();
+        let (width, height) = last.dimensions();
+        if width == 1 && height == 1 {
+            break levels;
+        }
+        let next = image::imageops::resize(last, (width / 2).max(1), (height / 2).max(1), FilterType::Triangle);
+        levels.push(next);
+    }
+}
@@ -0,0 +1,295 @@
+//! extract：把 KTX2 的每个层级 / 层 / 面解码为单独的图像文件
+//!
+//! 支持 Zstandard / ZLIB 超压缩和 BC1–BC5、BC7、ETC2 / EAC、ASTC（LDR）块压缩格式（见 `decode` 模块）。
+//! 像素值原样写出：8 位和 16 位数据写入 PNG（用 sRGB 块或 gAMA 1.0 标记色彩空间），浮点数据写入 EXR；
+//! 带预乘 Alpha 标志的纹理对所有像素类型都还原为直通 Alpha。文件名由模板决定，占位符：
+//! `{name}` 输入文件名（不含扩展名）、`{level}`、`{layer}`、`{face}`、`{slice}`（3D 纹理的深度切片）、
+//! `{ext}`（png 或 exr）。默认模板为 `{name}_L{level}_F{face}.{ext}`，纹理数组再加上 `_A{layer}`，
+//! 3D 纹理再加上 `_S{slice}`
+//!
+//! 旧版生成器布局的文件（层级索引在 DFD 之后）只能取出第 0 层
+
+use std::collections::HashSet;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use super::CommandResult;
+use crate::alpha;
+use crate::color_space::{self, ColorSpace};
+use crate::decode::{self, Pixels};
+use crate::export::{self, ExportFormat};
+
+/// 默认文件名模板，数组和 3D 纹理见 `default_template`
+const DEFAULT_TEMPLATE: &str = "{name}_L{level}_F{face}.{ext}";
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 输入 KTX2 文件
+    input: PathBuf,
+    /// 输出目录，默认为输入文件所在目录
+    #[arg(short, long)]
+    out_dir: Option<PathBuf>,
+    /// 文件名模板，可用 {name} {level} {layer} {face} {slice} {ext}；
+    /// 默认为 {name}_L{level}_F{face}.{ext}，数组和 3D 纹理自动加上层号和切片号
+    #[arg(long)]
+    template: Option<String>,
+    /// 只取出指定层级
+    #[arg(long)]
+    level: Option<u32>,
+}
+
+/// 解码后的一张图像及其在纹理中的位置
+struct Slice {
+    level: u32,
+    layer: u32,
+    face: u32,
+    slice: u32,
+    width: u32,
+    height: u32,
+    pixels: Pixels,
+}
+
+pub fn run(args: Args) -> CommandResult {
+    let bytes = super::read(&args.input)?;
+    let context = |e: String| format!("{}: {}", args.input.display(), e);
+
+    let slices = match decode_slices(&bytes, args.level).map_err(context)? {
+        Some(slices) => slices,
+        None => {
+            eprintln!(
+                "warning: {} uses the legacy level index layout, extracting level 0 only",
+                args.input.display()
+            );
+            legacy_slice(&args.input)?
+        }
+    };
+
+    let color_space = color_space::detect_ktx2(&bytes).unwrap_or_default();
+    let premultiplied = alpha::is_premultiplied_ktx2(&bytes);
+    let name = args
+        .input
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "texture".to_string());
+    let out_dir = args
+        .out_dir
+        .clone()
+        .or_else(|| args.input.parent().map(Path::to_path_buf))
+        .unwrap_or_default();
+
+    let template = args.template.clone().unwrap_or_else(|| default_template(&slices));
+
+    // 先生成全部文件名，模板缺少占位符导致重名时不写任何文件
+    let mut names = HashSet::new();
+    let mut outputs = Vec::with_capacity(slices.len());
+    for slice in &slices {
+        let file_name = expand_template(&template, &name, slice);
+        if !names.insert(file_name.clone()) {
+            return Err(format!(
+                "Template {:?} maps several images to {}; add {{level}}, {{layer}}, {{face}} or {{slice}}",
+                template, file_name
+            ));
+        }
+        outputs.push(out_dir.join(file_name));
+    }
+
+    for (slice, output) in slices.into_iter().zip(&outputs) {
+        let data = encode(slice, color_space, premultiplied)?;
+        super::write(output, &data)?;
+        println!("{}", output.display());
+    }
+    println!("{} images extracted from {}", outputs.len(), args.input.display());
+    Ok(ExitCode::SUCCESS)
+}
+
+/// 按规范布局解码所有图像；层级数据与格式大小不符（旧布局）时返回 `None`
+fn decode_slices(bytes: &[u8], only_level: Option<u32>) -> Result<Option<Vec<Slice>>, String> {
+    let reader = ktx2::Reader::new(bytes).map_err(|e| format!("Invalid KTX2: {:?}", e))?;
+    let header = reader.header();
+    let format = header
+        .format
+        .ok_or("Basis Universal (UASTC / ETC1S) textures cannot be decoded yet")?;
+    if decode::block_layout(format).is_none() {
+        return Err(format!("Unsupported format {:?}", format));
+    }
+    if let Some(level) = only_level
+        && level >= header.level_count.max(1)
+    {
+        return Err(format!("Level {} does not exist", level));
+    }
+
+    let layers = header.layer_count.max(1);
+    let faces = header.face_count.max(1);
+    let mut slices = Vec::new();
+
+    for (level, data) in (0u32..).zip(reader.levels()) {
+        if only_level.is_some_and(|only| only != level) {
+            continue;
+        }
+        let width = decode::level_extent(header.pixel_width, level);
+        let height = decode::level_extent(header.pixel_height, level);
+        let depth = decode::level_extent(header.pixel_depth, level);
+        let too_large = || format!("level {}: {}x{}x{} is too large", level, width, height, depth);
+        let image_size = decode::image_size(format, width, height).ok_or_else(too_large)?;
+        let expected = decode::level_size(&header, level).ok_or_else(too_large)?;
+
+        if header.supercompression_scheme.is_none() && data.data.len() as u64 != expected {
+            return Ok(None);
+        }
+        let data = decode::inflate(
+            header.supercompression_scheme,
+            data.data,
+            data.uncompressed_byte_length,
+            expected,
+        )
+        .map_err(|e| format!("level {}: {}", level, e))?;
+        if data.len() as u64 != expected {
+            return Err(format!("level {}: {} bytes, expected {}", level, data.len(), expected));
+        }
+
+        // 层级内按 层 → 面 → 深度切片 的顺序排列
+        let mut images = data.chunks_exact(image_size);
+        for layer in 0..layers {
+            for face in 0..faces {
+                for slice in 0..depth {
+                    let image = images.next().unwrap_or_default();
+                    let pixels = decode::decode(format, image, width, height)
+                        .map_err(|e| format!("level {} layer {} face {}: {}", level, layer, face, e))?;
+                    slices.push(Slice {
+                        level,
+                        layer,
+                        face,
+                        slice,
+                        width,
+                        height,
+                        pixels,
+                    });
+                }
+            }
+        }
+    }
+    Ok(Some(slices))
+}
+
+/// 旧布局只有一个 RGBA8 层级，交给查看器的加载器处理
+fn legacy_slice(input: &Path) -> Result<Vec<Slice>, String> {
+    let loader = super::load_texture(input)?;
+    let (width, height) = loader.dimensions().ok_or("Texture has no data")?;
+    let rgba = loader.data().ok_or("Texture has no data")?;
+    Ok(vec![Slice {
+        level: 0,
+        layer: 0,
+        face: 0,
+        slice: 0,
+        width,
+        height,
+        pixels: Pixels::Rgba8(rgba.to_vec()),
+    }])
+}
+
+/// 未指定模板时使用的默认模板：有多个层时加上 `{layer}`，有深度切片时加上 `{slice}`
+fn default_template(slices: &[Slice]) -> String {
+    let mut template = DEFAULT_TEMPLATE.trim_end_matches(".{ext}").to_string();
+    if slices.iter().any(|slice| slice.layer > 0) {
+        template.push_str("_A{layer}");
+    }
+    if slices.iter().any(|slice| slice.slice > 0) {
+        template.push_str("_S{slice}");
+    }
+    template + ".{ext}"
+}
+
+/// 展开文件名模板
+fn expand_template(template: &str, name: &str, slice: &Slice) -> String {
+    let extension = match slice.pixels {
+        Pixels::Rgba32F(_) => "exr",
+        _ => "png",
+    };
+    template
+        .replace("{name}", name)
+        .replace("{level}", &slice.level.to_string())
+        .replace("{layer}", &slice.layer.to_string())
+        .replace("{face}", &slice.face.to_string())
+        .replace("{slice}", &slice.slice.to_string())
+        .replace("{ext}", extension)
+}
+
+/// 编码为 PNG / EXR，像素值不做色彩空间转换
+fn encode(slice: Slice, color_space: ColorSpace, premultiplied: bool) -> Result<Vec<u8>, String> {
+    let (width, height) = (slice.width, slice.height);
+    match slice.pixels {
+        Pixels::Rgba8(mut rgba) => {
+            if premultiplied {
+                alpha::unpremultiply(&mut rgba, color_space);
+            }
+            export::encode(&rgba, width, height, ExportFormat::Png, color_space)
+        }
+        Pixels::Rgba16(mut rgba) => {
+            if premultiplied {
+                alpha::unpremultiply16(&mut rgba);
+            }
+            export::encode_png16(&rgba, width, height, color_space)
+        }
+        Pixels::Rgba32F(mut rgba) => {
+            if premultiplied {
+                alpha::unpremultiply_f32(&mut rgba);
+            }
+            let image = image::Rgba32FImage::from_raw(width, height, rgba)
+                .ok_or("Pixel data does not match dimensions")?;
+            let mut out = Cursor::new(Vec::new());
+            image
+                .write_to(&mut out, image::ImageFormat::OpenExr)
+                .map_err(|e| format!("Failed to encode EXR: {}", e))?;
+            Ok(out.into_inner())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_template() {
+        let slice = Slice {
+            level: 2,
+            layer: 1,
+            face: 5,
+            slice: 0,
+            width: 1,
+            height: 1,
+            pixels: Pixels::Rgba32F(vec![0.0; 4]),
+        };
+        assert_eq!(expand_template(DEFAULT_TEMPLATE, "sky", &slice), "sky_L2_F5.exr");
+        assert_eq!(
+            expand_template(&default_template(std::slice::from_ref(&slice)), "sky", &slice),
+            "sky_L2_F5_A1.exr"
+        );
+        assert_eq!(
+            expand_template("{name}/{layer}/{level}.{ext}", "sky", &slice),
+            "sky/1/2.exr"
+        );
+    }
+
+    #[test]
+    fn test_encode_keeps_values() {
+        // 线性数据原样写入 PNG 并标记 gAMA 1.0，不转换为 sRGB 编码
+        let slice = |pixels| Slice {
+            level: 0,
+            layer: 0,
+            face: 0,
+            slice: 0,
+            width: 1,
+            height: 1,
+            pixels,
+        };
+        let png = encode(slice(Pixels::Rgba8(vec![10, 128, 200, 255])), ColorSpace::Linear, false).unwrap();
+        assert_eq!(color_space::detect_png(&png), Some(ColorSpace::Linear));
+        assert_eq!(image::load_from_memory(&png).unwrap().into_rgba8().as_raw(), &[10, 128, 200, 255]);
+
+        // 预乘 Alpha 对 16 位数据同样还原
+        let png = encode(slice(Pixels::Rgba16(vec![1000, 0, 0, 32768])), ColorSpace::Linear, true).unwrap();
+        assert_eq!(image::load_from_memory(&png).unwrap().into_rgba16().as_raw(), &[2000, 0, 0, 32768]);
+    }
+}
@@ -0,0 +1,67 @@
+//! info：显示纹理的头部、数据段位置、层级索引、DFD 和键值数据
+//!
+//! 文本输出与查看器的元数据面板一致；`--json` 输出数组，每个文件一项，供资源管线的面板使用
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde::Serialize;
+
+use super::CommandResult;
+use crate::metadata::TextureInfo;
+use crate::texture::{self, SourceFormat};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 输入纹理（KTX2 / PNG / JPEG）
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+    /// 输出 JSON
+    #[arg(long)]
+    json: bool,
+}
+
+/// 单个文件的信息
+#[derive(Serialize)]
+struct FileInfo {
+    path: String,
+    #[serde(flatten)]
+    info: TextureInfo,
+}
+
+pub fn run(args: Args) -> CommandResult {
+    let mut files = Vec::new();
+    for path in &args.inputs {
+        let bytes = super::read(path)?;
+        let info = texture_info(&bytes).map_err(|e| format!("{}: {}", path.display(), e))?;
+        files.push(FileInfo {
+            path: path.display().to_string(),
+            info,
+        });
+    }
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&files).map_err(|e| e.to_string())?;
+        println!("{}", json);
+    } else {
+        for file in &files {
+            println!("{}", file.path);
+            for line in file.info.lines() {
+                println!("  {}", line);
+            }
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn texture_info(bytes: &[u8]) -> Result<TextureInfo, String> {
+    match texture::sniff_format(bytes) {
+        Some(SourceFormat::Ktx2) => TextureInfo::from_ktx2(bytes),
+        Some(SourceFormat::Image(format)) => {
+            let img = image::load_from_memory_with_format(bytes, format)
+                .map_err(|e| format!("Failed to decode image: {}", e))?;
+            Ok(TextureInfo::from_image(format, (img.width(), img.height()), bytes.len()))
+        }
+        None => Err("Unrecognized texture format".to_string()),
+    }
+}
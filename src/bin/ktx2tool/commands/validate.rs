@@ -0,0 +1,81 @@
+//! validate：按 KTX 2.0 规范检查文件
+//!
+//! 文本输出每个问题的严重程度、字节偏移和说明；`--json` 输出数组，每个文件一项，供 CI 使用
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use serde::Serialize;
+
+use super::{CommandResult, EXIT_CHECK_FAILED};
+use crate::validator::{self, Issue, Severity};
+
+#[derive(clap::Args)]
+pub struct Args {
+    /// 要检查的 KTX2 文件
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+    /// 输出 JSON
+    #[arg(long)]
+    json: bool,
+    /// 把警告也视为检查未通过
+    #[arg(long)]
+    strict: bool,
+}
+
+/// 单个文件的检查结果
+#[derive(Serialize)]
+struct FileReport {
+    path: String,
+    valid: bool,
+    errors: usize,
+    warnings: usize,
+    issues: Vec<Issue>,
+}
+
+pub fn run(args: Args) -> CommandResult {
+    let mut reports = Vec::new();
+    for path in &args.inputs {
+        let bytes = super::read(path)?;
+        let issues = validator::validate(&bytes);
+        let errors = issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+        let warnings = issues.len() - errors;
+        reports.push(FileReport {
+            path: path.display().to_string(),
+            valid: errors == 0 && (!args.strict || warnings == 0),
+            errors,
+            warnings,
+            issues,
+        });
+    }
+
+    if args.json {
+        let json = serde_json::to_string_pretty(&reports).map_err(|e| e.to_string())?;
+        println!("{}", json);
+    } else {
+        for report in &reports {
+            println!(
+                "{}: {} ({} errors, {} warnings)",
+                report.path,
+                if report.valid { "OK" } else { "FAILED" },
+                report.errors,
+                report.warnings
+            );
+            for issue in &report.issues {
+                println!(
+                    "  {:<7} @{:#06x} ({}): {}",
+                    issue.severity.label(),
+                    issue.offset,
+                    issue.offset,
+                    issue.message
+                );
+            }
+        }
+    }
+
+    if reports.iter().all(|report| report.valid) {
+        Ok(ExitCode::SUCCESS)
+    } else {
+        Ok(ExitCode::from(EXIT_CHECK_FAILED))
+    }
+}
@@ -0,0 +1,203 @@
+//! BC 块压缩编码
+//!
+//! 把 RGBA8 图像按 4x4 块编码为 BC1 / BC3 / BC4 / BC5 / BC7，块之间用 rayon 并行。
+//! 图像边缘不足 4x4 的块用边缘像素补齐。
+//!
+//! 端点先取主成分方向上的投影范围，再按质量档位做若干轮最小二乘优化；
+//! BC7 使用模式 0–3、6、7（不使用带通道旋转的模式 4、5），慢速档会尝试更多模式和分区
+
+mod bc;
+mod bc7;
+
+use clap::ValueEnum;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::ktx2_writer::PixelFormat;
+
+/// 一个 4x4 块的 RGBA8 纹素，按行排列
+type Block = [[u8; 4]; 16];
+
+/// 编码质量档位
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Quality {
+    Fast,
+    #[default]
+    Normal,
+    Slow,
+}
+
+impl Quality {
+    /// 端点最小二乘优化的轮数
+    fn iterations(self) -> usize {
+        match self {
+            Quality::Fast => 1,
+            Quality::Normal => 3,
+            Quality::Slow => 8,
+        }
+    }
+}
+
+/// 把 RGBA8 图像编码为 `format` 的块数据，RGBA8 原样返回
+pub fn encode(format: PixelFormat, rgba: &[u8], width: u32, height: u32, quality: Quality) -> Vec<u8> {
+    if format == PixelFormat::Rgba8 {
+        return rgba.to_vec();
+    }
+
+    let blocks_x = width.div_ceil(4) as usize;
+    let block_bytes = format.block_bytes();
+    let mut out = vec![0u8; format.level_size(width, height)];
+
+    out.par_chunks_mut(block_bytes).enumerate().for_each(|(index, dst)| {
+        let block = fetch_block(rgba, width, height, index % blocks_x * 4, index / blocks_x * 4);
+        match format {
+            PixelFormat::Bc1 => dst.copy_from_slice(&bc::bc1(&block, quality)),
+            PixelFormat::Bc3 => dst.copy_from_slice(&bc::bc3(&block, quality)),
+            PixelFormat::Bc4 => dst.copy_from_slice(&bc::bc4(&channel(&block, 0), quality)),
+            PixelFormat::Bc5 => {
+                dst[..8].copy_from_slice(&bc::bc4(&channel(&block, 0), quality));
+                dst[8..].copy_from_slice(&bc::bc4(&channel(&block, 1), quality));
+            }
+            PixelFormat::Bc7 => dst.copy_from_slice(&bc7::bc7(&block, quality)),
+            PixelFormat::Rgba8 => unreachable!(),
+        }
+    });
+    out
+}
+
+/// 取出左上角为 (x, y) 的块，超出图像的纹素取最近的边缘像素
+fn fetch_block(rgba: &[u8], width: u32, height: u32, x: usize, y: usize) -> Block {
+    let (width, height) = (width as usize, height as usize);
+    std::array::from_fn(|i| {
+        let px = (x + i % 4).min(width - 1);
+        let py = (y + i / 4).min(height - 1);
+        let offset = (py * width + px) * 4;
+        [rgba[offset], rgba[offset + 1], rgba[offset + 2], rgba[offset + 3]]
+    })
+}
+
+fn channel(block: &Block, channel: usize) -> [u8; 16] {
+    block.map(|texel| texel[channel])
+}
+
+/// 点集的均值和主成分方向（幂迭代），所有点相同时方向为 0
+fn principal_axis<const N: usize>(points: &[[f32; N]]) -> ([f32; N], [f32; N]) {
+    let count = points.len().max(1) as f32;
+    let mut mean = [0.0; N];
+    for point in points {
+        for c in 0..N {
+            mean[c] += point[c] / count;
+        }
+    }
+
+    let mut covariance = [[0.0f32; N]; N];
+    for point in points {
+        for i in 0..N {
+            for j in 0..N {
+                covariance[i][j] += (point[i] - mean[i]) * (point[j] - mean[j]);
+            }
+        }
+    }
+
+    let mut axis = [1.0; N];
+    for _ in 0..8 {
+        let mut next = [0.0; N];
+        for i in 0..N {
+            for j in 0..N {
+                next[i] += covariance[i][j] * axis[j];
+            }
+        }
+        let length = next.iter().map(|v| v * v).sum::<f32>().sqrt();
+        if length < 1e-6 {
+            return (mean, [0.0; N]);
+        }
+        axis = next.map(|v| v / length);
+    }
+    (mean, axis)
+}
+
+/// 沿主成分方向的投影范围作为初始端点
+fn initial_endpoints<const N: usize>(points: &[[f32; N]]) -> [[f32; N]; 2] {
+    let (mean, axis) = principal_axis(points);
+    let (mut low, mut high) = (0.0f32, 0.0f32);
+    for point in points {
+        let t: f32 = (0..N).map(|c| (point[c] - mean[c]) * axis[c]).sum();
+        low = low.min(t);
+        high = high.max(t);
+    }
+    let at = |t: f32| std::array::from_fn(|c| (mean[c] + axis[c] * t).clamp(0.0, 255.0));
+    [at(low), at(high)]
+}
+
+/// 已知每个点的插值权重（0 为第一个端点，1 为第二个）时，用最小二乘求端点
+///
+/// 所有权重相同（方程退化）时返回 `None`
+fn least_squares<const N: usize>(points: &[[f32; N]], weights: &[f32]) -> Option<[[f32; N]; 2]> {
+    let (mut aa, mut ab, mut bb) = (0.0f32, 0.0f32, 0.0f32);
+    let (mut ax, mut bx) = ([0.0f32; N], [0.0f32; N]);
+    for (point, &w) in points.iter().zip(weights) {
+        let (a, b) = (1.0 - w, w);
+        aa += a * a;
+        ab += a * b;
+        bb += b * b;
+        for c in 0..N {
+            ax[c] += a * point[c];
+            bx[c] += b * point[c];
+        }
+    }
+
+    let det = aa * bb - ab * ab;
+    if det.abs() < 1e-6 {
+        return None;
+    }
+    let e0 = std::array::from_fn(|c| ((bb * ax[c] - ab * bx[c]) / det).clamp(0.0, 255.0));
+    let e1 = std::array::from_fn(|c| ((aa * bx[c] - ab * ax[c]) / det).clamp(0.0, 255.0));
+    Some([e0, e1])
+}
+
+/// 两个颜色的平方误差
+fn distance<const N: usize>(a: &[u8; N], b: &[u8; N]) -> u32 {
+    a.iter().zip(b).map(|(&x, &y)| (x as i32 - y as i32).pow(2) as u32).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::{self, Pixels};
+
+    /// 8x8：每个块内颜色沿一条直线变化，右半边半透明
+    fn sample_image() -> Vec<u8> {
+        (0..64u32)
+            .flat_map(|i| {
+                let (x, y) = (i % 8, i / 8);
+                let t = (x % 4 + y % 4 * 4) as u8;
+                let alpha = if x < 4 { 255 } else { 128 + t * 4 };
+                [t * 15, 250 - t * 12, 40 + t * 6, alpha]
+            })
+            .collect()
+    }
+
+    fn max_error(format: PixelFormat, quality: Quality, channels: usize) -> u8 {
+        let rgba = sample_image();
+        let encoded = encode(format, &rgba, 8, 8, quality);
+        let Ok(Pixels::Rgba8(decoded)) = decode::decode(format.vk_format(false), &encoded, 8, 8) else {
+            panic!("decode failed");
+        };
+        rgba.chunks_exact(4)
+            .zip(decoded.chunks_exact(4))
+            .flat_map(|(a, b)| (0..channels).map(move |c| a[c].abs_diff(b[c])))
+            .max()
+            .unwrap_or(0)
+    }
+
+    #[test]
+    fn test_round_trip_error() {
+        // 每块 16 个不同的值：BC7 的 16 级插值几乎无损，BC1 只有 4 级、BC4 只有 8 级
+        assert!(max_error(PixelFormat::Bc7, Quality::Fast, 4) <= 4);
+        assert!(max_error(PixelFormat::Bc7, Quality::Slow, 4) <= 4);
+        assert!(max_error(PixelFormat::Bc1, Quality::Normal, 3) <= 40);
+        assert!(max_error(PixelFormat::Bc3, Quality::Normal, 4) <= 40);
+        assert!(max_error(PixelFormat::Bc5, Quality::Slow, 2) <= 16);
+    }
+}
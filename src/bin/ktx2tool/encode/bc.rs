@@ -0,0 +1,129 @@
+//! BC1 / BC3 / BC4 编码（BC5 由两个 BC4 块组成）
+
+use super::{Block, Quality, distance, initial_endpoints, least_squares};
+use crate::decode::bc::{bc1_palette, bc4_palette};
+
+/// BC1 四色模式中调色板下标对应的插值权重
+const BC1_WEIGHTS: [f32; 4] = [0.0, 1.0, 1.0 / 3.0, 2.0 / 3.0];
+
+/// 编码 BC1 颜色块（忽略 Alpha，始终使用四色模式）
+pub fn bc1(block: &Block, quality: Quality) -> [u8; 8] {
+    let points: Vec<[f32; 3]> = block.iter().map(|t| [t[0] as f32, t[1] as f32, t[2] as f32]).collect();
+    let colors: Vec<[u8; 3]> = block.iter().map(|t| [t[0], t[1], t[2]]).collect();
+
+    let mut endpoints = initial_endpoints(&points);
+    let mut best = ([0u8; 8], u32::MAX);
+    for _ in 0..quality.iterations() {
+        let (encoded, error, indices) = bc1_block(&colors, endpoints);
+        if error < best.1 {
+            best = (encoded, error);
+        }
+        let weights: Vec<f32> = indices.iter().map(|&i| BC1_WEIGHTS[i]).collect();
+        match least_squares(&points, &weights) {
+            Some(next) => endpoints = next,
+            None => break,
+        }
+    }
+    best.0
+}
+
+/// 编码 BC3：BC4 格式的 Alpha 加 BC1 颜色
+pub fn bc3(block: &Block, quality: Quality) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    out[..8].copy_from_slice(&bc4(&block.map(|t| t[3]), quality));
+    out[8..].copy_from_slice(&bc1(block, quality));
+    out
+}
+
+/// 编码 BC4 单通道块
+///
+/// 快速档只用最小 / 最大值的 8 插值模式；其余档位还会尝试带 0 / 255 的 6 插值模式，
+/// 慢速档再在端点附近搜索
+pub fn bc4(values: &[u8; 16], quality: Quality) -> [u8; 8] {
+    let (min, max) = (*values.iter().min().unwrap(), *values.iter().max().unwrap());
+    let mut best = bc4_block(values, max, min);
+    if quality == Quality::Fast {
+        return best.0;
+    }
+
+    // 6 插值模式：端点只覆盖 0 和 255 以外的值
+    let inner = values.iter().filter(|&&v| v != 0 && v != 255);
+    if let (Some(&low), Some(&high)) = (inner.clone().min(), inner.max()) {
+        let candidate = bc4_block(values, low, high);
+        if candidate.1 < best.1 {
+            best = candidate;
+        }
+    }
+
+    if quality == Quality::Slow {
+        let (e0, e1) = (best.0[0] as i32, best.0[1] as i32);
+        for d0 in -3..=3 {
+            for d1 in -3..=3 {
+                let candidate = bc4_block(values, (e0 + d0).clamp(0, 255) as u8, (e1 + d1).clamp(0, 255) as u8);
+                if candidate.1 < best.1 {
+                    best = candidate;
+                }
+            }
+        }
+    }
+    best.0
+}
+
+/// 给定端点生成 BC4 块，返回编码结果和平方误差
+fn bc4_block(values: &[u8; 16], e0: u8, e1: u8) -> ([u8; 8], u32) {
+    let palette = bc4_palette(e0, e1);
+    let mut indices = 0u64;
+    let mut error = 0;
+    for (i, &value) in values.iter().enumerate() {
+        let (index, e) = (0..8)
+            .map(|p| (p, distance(&[value], &[palette[p]])))
+            .min_by_key(|&(_, e)| e)
+            .unwrap();
+        indices |= (index as u64) << (3 * i);
+        error += e;
+    }
+
+    let mut out = [0u8; 8];
+    out[0] = e0;
+    out[1] = e1;
+    out[2..].copy_from_slice(&indices.to_le_bytes()[..6]);
+    (out, error)
+}
+
+/// 浮点颜色量化为 RGB565
+fn quantize565(color: [f32; 3]) -> u16 {
+    let r = (color[0] * 31.0 / 255.0).round() as u16;
+    let g = (color[1] * 63.0 / 255.0).round() as u16;
+    let b = (color[2] * 31.0 / 255.0).round() as u16;
+    (r << 11) | (g << 5) | b
+}
+
+/// 给定端点生成 BC1 块，返回编码结果、平方误差和每个纹素的调色板下标
+fn bc1_block(colors: &[[u8; 3]], endpoints: [[f32; 3]; 2]) -> ([u8; 8], u32, Vec<usize>) {
+    let (mut c0, mut c1) = (quantize565(endpoints[0]), quantize565(endpoints[1]));
+    // 四色模式要求 c0 > c1；相等时所有纹素都用下标 0
+    if c0 < c1 {
+        std::mem::swap(&mut c0, &mut c1);
+    }
+    let palette = bc1_palette(c0, c1);
+    let usable = if c0 == c1 { 1 } else { 4 };
+
+    let mut indices = Vec::with_capacity(16);
+    let mut bits = 0u32;
+    let mut error = 0;
+    for (i, color) in colors.iter().enumerate() {
+        let (index, e) = (0..usable)
+            .map(|p| (p, distance(color, &[palette[p][0], palette[p][1], palette[p][2]])))
+            .min_by_key(|&(_, e)| e)
+            .unwrap();
+        bits |= (index as u32) << (2 * i);
+        error += e;
+        indices.push(index);
+    }
+
+    let mut out = [0u8; 8];
+    out[..2].copy_from_slice(&c0.to_le_bytes());
+    out[2..4].copy_from_slice(&c1.to_le_bytes());
+    out[4..].copy_from_slice(&bits.to_le_bytes());
+    (out, error, indices)
+}
@@ -0,0 +1,261 @@
+//! BC7 编码
+//!
+//! 每个候选模式：挑选误差估计最小的分区，逐子集拟合端点、选择 P 位并分配索引，
+//! 最后把锚点索引最高位为 1 的子集交换端点，再按位打包。所有候选中取误差最小的结果
+
+use super::{Block, Quality, distance, initial_endpoints, least_squares, principal_axis};
+use crate::decode::bc::{
+    BC7_MODES, Bc7Mode, bc7_anchor, bc7_interpolate, bc7_is_anchor, bc7_subset, bc7_weights, expand,
+};
+
+/// 编码一个 BC7 块
+pub fn bc7(block: &Block, quality: Quality) -> [u8; 16] {
+    let opaque = block.iter().all(|texel| texel[3] == 255);
+    // 模式 0–3 没有 Alpha，只用于不透明块
+    let modes: &[usize] = match (quality, opaque) {
+        (Quality::Fast, _) => &[6],
+        (Quality::Normal, true) => &[6, 1],
+        (Quality::Normal, false) => &[6, 7],
+        (Quality::Slow, true) => &[6, 1, 3, 0, 2],
+        (Quality::Slow, false) => &[6, 7],
+    };
+    let partitions = match quality {
+        Quality::Slow => 4,
+        _ => 1,
+    };
+
+    let mut best = ([0u8; 16], u32::MAX);
+    for &mode in modes {
+        for partition in best_partitions(block, &BC7_MODES[mode], partitions) {
+            let candidate = encode_mode(block, mode, partition, quality.iterations());
+            if candidate.1 < best.1 {
+                best = candidate;
+            }
+        }
+    }
+    best.0
+}
+
+/// 按“各子集到其主成分直线的距离平方和”估计误差，返回最好的 `count` 个分区
+fn best_partitions(block: &Block, mode: &Bc7Mode, count: usize) -> Vec<usize> {
+    if mode.subsets == 1 {
+        return vec![0];
+    }
+
+    let mut scored: Vec<(f32, usize)> = (0..1usize << mode.partition_bits)
+        .map(|partition| {
+            let score = (0..mode.subsets)
+                .map(|subset| {
+                    let points = subset_points(block, mode.subsets, partition, subset);
+                    let (mean, axis) = principal_axis(&points);
+                    points
+                        .iter()
+                        .map(|point| {
+                            let d: [f32; 4] = std::array::from_fn(|c| point[c] - mean[c]);
+                            let along: f32 = (0..4).map(|c| d[c] * axis[c]).sum();
+                            d.iter().map(|v| v * v).sum::<f32>() - along * along
+                        })
+                        .sum::<f32>()
+                })
+                .sum();
+            (score, partition)
+        })
+        .collect();
+    scored.sort_by(|a, b| a.0.total_cmp(&b.0));
+    scored.into_iter().take(count).map(|(_, partition)| partition).collect()
+}
+
+fn subset_points(block: &Block, subsets: usize, partition: usize, subset: usize) -> Vec<[f32; 4]> {
+    (0..16)
+        .filter(|&texel| bc7_subset(subsets, partition, texel) == subset)
+        .map(|texel| block[texel].map(|v| v as f32))
+        .collect()
+}
+
+/// 一个子集量化后的端点
+#[derive(Clone, Copy, Default)]
+struct Endpoints {
+    /// 写入块中的端点值（不含 P 位）
+    raw: [[u32; 4]; 2],
+    pbits: [u32; 2],
+    /// 解码后的 8 位端点颜色
+    colors: [[u8; 4]; 2],
+}
+
+/// 用指定模式和分区编码，返回编码结果和平方误差
+fn encode_mode(block: &Block, mode_index: usize, partition: usize, iterations: usize) -> ([u8; 16], u32) {
+    let mode = &BC7_MODES[mode_index];
+    let weights = bc7_weights(mode.index_bits);
+    let mut endpoints = [Endpoints::default(); 3];
+    let mut indices = [0u32; 16];
+    let mut error = 0;
+
+    for (subset, subset_endpoints) in endpoints.iter_mut().enumerate().take(mode.subsets) {
+        let texels: Vec<usize> = (0..16)
+            .filter(|&texel| bc7_subset(mode.subsets, partition, texel) == subset)
+            .collect();
+        let points: Vec<[f32; 4]> = texels.iter().map(|&t| block[t].map(|v| v as f32)).collect();
+
+        let mut fit = initial_endpoints(&points);
+        let mut best: Option<(u32, Endpoints, Vec<u32>)> = None;
+        for _ in 0..iterations {
+            let quantized = quantize(mode, fit);
+            let (subset_indices, subset_error) = assign(block, &texels, &quantized.colors, weights);
+            let improved = best.as_ref().is_none_or(|(e, _, _)| subset_error < *e);
+            if improved {
+                best = Some((subset_error, quantized, subset_indices.clone()));
+            }
+
+            let w: Vec<f32> = subset_indices.iter().map(|&i| weights[i as usize] as f32 / 64.0).collect();
+            match least_squares(&points, &w) {
+                Some(next) => fit = next,
+                None => break,
+            }
+        }
+
+        let (subset_error, quantized, subset_indices) = best.unwrap_or_default();
+        *subset_endpoints = quantized;
+        error += subset_error;
+        for (&texel, &index) in texels.iter().zip(&subset_indices) {
+            indices[texel] = index;
+        }
+    }
+
+    // 锚点索引的最高位不存储，必须为 0：否则交换端点并翻转该子集的索引
+    let max_index = (1u32 << mode.index_bits) - 1;
+    for (subset, subset_endpoints) in endpoints.iter_mut().enumerate().take(mode.subsets) {
+        let anchor = bc7_anchor(mode.subsets, partition, subset);
+        if indices[anchor] > max_index / 2 {
+            subset_endpoints.raw.swap(0, 1);
+            subset_endpoints.pbits.swap(0, 1);
+            for (texel, index) in indices.iter_mut().enumerate() {
+                if bc7_subset(mode.subsets, partition, texel) == subset {
+                    *index = max_index - *index;
+                }
+            }
+        }
+    }
+
+    (pack(mode_index, partition, &endpoints[..mode.subsets], &indices), error)
+}
+
+/// 量化浮点端点，按模式选择每个端点或每个子集的 P 位
+fn quantize(mode: &Bc7Mode, fit: [[f32; 4]; 2]) -> Endpoints {
+    let bits = |channel: usize| if channel < 3 { mode.color_bits } else { mode.alpha_bits };
+
+    // 给定 P 位时量化一个端点，返回写入值、解码值和误差
+    let endpoint = |target: [f32; 4], pbit: Option<u32>| {
+        let mut raw = [0u32; 4];
+        let mut color = [255u8; 4];
+        for channel in 0..4 {
+            let bits = bits(channel);
+            if bits == 0 {
+                continue;
+            }
+            let value = target[channel];
+            (raw[channel], color[channel]) = match pbit {
+                Some(p) => {
+                    let max = (1u32 << (bits + 1)) - 1;
+                    let v = ((value * max as f32 / 255.0 - p as f32) / 2.0)
+                        .round()
+                        .clamp(0.0, ((1 << bits) - 1) as f32) as u32;
+                    (v, expand((v << 1) | p, bits + 1))
+                }
+                None => {
+                    let max = (1u32 << bits) - 1;
+                    let v = (value * max as f32 / 255.0).round().clamp(0.0, max as f32) as u32;
+                    (v, expand(v, bits))
+                }
+            };
+        }
+        let error: f32 = (0..4).map(|c| (color[c] as f32 - target[c]).powi(2)).sum();
+        (raw, color, error)
+    };
+
+    let best_pbit = |targets: &[[f32; 4]]| {
+        (0..2).min_by(|&a, &b| {
+            let error = |p| targets.iter().map(|&t| endpoint(t, Some(p)).2).sum::<f32>();
+            error(a).total_cmp(&error(b))
+        })
+    };
+    let pbits: [Option<u32>; 2] = if mode.endpoint_pbits {
+        [best_pbit(&fit[..1]), best_pbit(&fit[1..])]
+    } else if mode.shared_pbits {
+        [best_pbit(&fit); 2]
+    } else {
+        [None; 2]
+    };
+
+    let [(raw0, color0, _), (raw1, color1, _)] = [endpoint(fit[0], pbits[0]), endpoint(fit[1], pbits[1])];
+    Endpoints {
+        raw: [raw0, raw1],
+        pbits: pbits.map(Option::unwrap_or_default),
+        colors: [color0, color1],
+    }
+}
+
+/// 为子集中的每个纹素选择最近的插值颜色
+fn assign(block: &Block, texels: &[usize], colors: &[[u8; 4]; 2], weights: &[u32]) -> (Vec<u32>, u32) {
+    let palette: Vec<[u8; 4]> = weights
+        .iter()
+        .map(|&w| std::array::from_fn(|c| bc7_interpolate(colors[0][c], colors[1][c], w)))
+        .collect();
+
+    let mut error = 0;
+    let indices = texels
+        .iter()
+        .map(|&texel| {
+            let (index, e) = palette
+                .iter()
+                .enumerate()
+                .map(|(i, color)| (i as u32, distance(&block[texel], color)))
+                .min_by_key(|&(_, e)| e)
+                .unwrap();
+            error += e;
+            index
+        })
+        .collect();
+    (indices, error)
+}
+
+/// 按 BC7 位布局打包（从最低位开始）
+fn pack(mode_index: usize, partition: usize, endpoints: &[Endpoints], indices: &[u32; 16]) -> [u8; 16] {
+    let mode = &BC7_MODES[mode_index];
+    let mut bits = 0u128;
+    let mut position = 0;
+    let mut write = |value: u32, count: u32| {
+        bits |= (value as u128) << position;
+        position += count;
+    };
+
+    write(1 << mode_index, mode_index as u32 + 1);
+    write(partition as u32, mode.partition_bits);
+    for channel in 0..3 {
+        for subset in endpoints {
+            write(subset.raw[0][channel], mode.color_bits);
+            write(subset.raw[1][channel], mode.color_bits);
+        }
+    }
+    if mode.alpha_bits > 0 {
+        for subset in endpoints {
+            write(subset.raw[0][3], mode.alpha_bits);
+            write(subset.raw[1][3], mode.alpha_bits);
+        }
+    }
+    if mode.endpoint_pbits {
+        for subset in endpoints {
+            write(subset.pbits[0], 1);
+            write(subset.pbits[1], 1);
+        }
+    } else if mode.shared_pbits {
+        for subset in endpoints {
+            write(subset.pbits[0], 1);
+        }
+    }
+    for (texel, &index) in indices.iter().enumerate() {
+        let anchor = bc7_is_anchor(mode.subsets, partition, texel);
+        write(index, mode.index_bits - anchor as u32);
+    }
+
+    bits.to_le_bytes()
+}
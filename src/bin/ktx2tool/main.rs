@@ -0,0 +1,74 @@
+//! KTX2 命令行工具
+//!
+//! 取代原来分散的 `ktx2_generator`、`png_to_ktx2`、`ktx2_compressor`、`debug_ktx2` 等工具：
+//!
+//! ```bash
+//! cargo run --bin ktx2tool -- create input.png output.ktx2 --mipmaps
+//! cargo run --bin ktx2tool -- info output.ktx2
+//! cargo run --bin ktx2tool -- batch assets/ public/textures/ --config presets.json
+//! cargo run --bin ktx2tool -- --help
+//! ```
+//!
+//! 退出码：0 成功，1 执行出错，2 参数错误，3 检查未通过（validate / compare）
+
+mod commands;
+mod encode;
+mod transform;
+mod validator;
+
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use iced_web_app::{alpha, assets, color_space, compare, decode, export, ktx2_writer, metadata, texture};
+
+/// KTX2 纹理工具
+#[derive(Parser)]
+#[command(name = "ktx2tool", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 从 PNG / JPEG 生成 KTX2（RGBA8 或 BC 块压缩）
+    Create(commands::create::Args),
+    /// 显示纹理的头部、DFD、键值数据和层级信息
+    Info(commands::info::Args),
+    /// 按 KTX 2.0 规范检查文件，报告每个问题的字节偏移和严重程度
+    Validate(commands::validate::Args),
+    /// 把 KTX2 的每个层级 / 层 / 面解码为 PNG（浮点格式为 EXR）
+    Extract(commands::extract::Args),
+    /// 在 PNG / KTX2 之间转换，格式由输出文件扩展名决定
+    Convert(commands::convert::Args),
+    /// 计算两张纹理的 PSNR / SSIM
+    Compare(commands::compare::Args),
+    /// 按预设配置把目录中的图像批量转换为 KTX2，跳过未改动的文件并写入清单
+    Batch(commands::batch::Args),
+    /// Basis Universal（UASTC / ETC1S）编码，尚未内置，只提示 toktx 的等价命令
+    Compress(commands::compress::Args),
+}
+
+fn main() -> ExitCode {
+    // 参数错误时 clap 以退出码 2 结束
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Create(args) => commands::create::run(args),
+        Command::Info(args) => commands::info::run(args),
+        Command::Validate(args) => commands::validate::run(args),
+        Command::Extract(args) => commands::extract::run(args),
+        Command::Convert(args) => commands::convert::run(args),
+        Command::Compare(args) => commands::compare::run(args),
+        Command::Batch(args) => commands::batch::run(args),
+        Command::Compress(args) => commands::compress::run(args),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! 几何变换：裁剪、缩放和补边
+//!
+//! `create` 先裁剪，Alpha 预处理之后再缩放（指定尺寸或限制最长边）并调整为 2 的幂。
+//! 补边复制最后一行 / 列的像素（边缘延伸），过滤和块压缩时不会混入其他颜色
+
+use std::fmt;
+use std::str::FromStr;
+
+use clap::ValueEnum;
+use image::RgbaImage;
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+
+/// 缩放滤波器
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Filter {
+    Nearest,
+    Triangle,
+    CatmullRom,
+    Gaussian,
+    #[default]
+    Lanczos3,
+}
+
+impl Filter {
+    fn filter_type(self) -> FilterType {
+        match self {
+            Filter::Nearest => FilterType::Nearest,
+            Filter::Triangle => FilterType::Triangle,
+            Filter::CatmullRom => FilterType::CatmullRom,
+            Filter::Gaussian => FilterType::Gaussian,
+            Filter::Lanczos3 => FilterType::Lanczos3,
+        }
+    }
+}
+
+/// 调整为 2 的幂的方式
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PowerOfTwo {
+    /// 缩放到最接近的 2 的幂
+    Resize,
+    /// 向上补边到下一个 2 的幂
+    Pad,
+}
+
+/// 裁剪区域，写作 `X,Y,W,H`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FromStr for Region {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let values: Vec<u32> = s
+            .split(',')
+            .map(|value| value.trim().parse())
+            .collect::<Result<_, _>>()
+            .map_err(|_| format!("Invalid region '{}', expected X,Y,W,H", s))?;
+        match values[..] {
+            [x, y, width, height] if width > 0 && height > 0 => Ok(Region { x, y, width, height }),
+            _ => Err(format!("Invalid region '{}', expected X,Y,W,H with non-zero size", s)),
+        }
+    }
+}
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{},{},{},{}", self.x, self.y, self.width, self.height)
+    }
+}
+
+impl TryFrom<String> for Region {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        s.parse()
+    }
+}
+
+impl From<Region> for String {
+    fn from(region: Region) -> String {
+        region.to_string()
+    }
+}
+
+/// 目标尺寸，写作 `WxH`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Size {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl FromStr for Size {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, String> {
+        let size = s
+            .split_once(['x', 'X'])
+            .and_then(|(width, height)| Some((width.trim().parse().ok()?, height.trim().parse().ok()?)));
+        match size {
+            Some((width, height)) if width > 0 && height > 0 => Ok(Size { width, height }),
+            _ => Err(format!("Invalid size '{}', expected WxH with non-zero size", s)),
+        }
+    }
+}
+
+impl fmt::Display for Size {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{}", self.width, self.height)
+    }
+}
+
+impl TryFrom<String> for Size {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, String> {
+        s.parse()
+    }
+}
+
+impl From<Size> for String {
+    fn from(size: Size) -> String {
+        size.to_string()
+    }
+}
+
+/// 裁剪到区域，区域超出图像时返回错误
+pub fn crop(image: &RgbaImage, region: Region) -> Result<RgbaImage, String> {
+    let (width, height) = image.dimensions();
+    let inside = region.x.checked_add(region.width).is_some_and(|right| right <= width)
+        && region
+            .y
+            .checked_add(region.height)
+            .is_some_and(|bottom| bottom <= height);
+    if !inside {
+        return Err(format!("Crop region {} exceeds the {}x{} image", region, width, height));
+    }
+    Ok(image::imageops::crop_imm(image, region.x, region.y, region.width, region.height).to_image())
+}
+
+/// 等比缩小到最长边不超过 `max`，不放大
+pub fn fit(width: u32, height: u32, max: u32) -> (u32, u32) {
+    let longest = width.max(height);
+    let max = max.max(1);
+    if longest <= max {
+        return (width, height);
+    }
+    let scale = |value: u32| ((value as u64 * max as u64 + longest as u64 / 2) / longest as u64).max(1) as u32;
+    (scale(width), scale(height))
+}
+
+/// 最接近的 2 的幂，距离相等时取较大的
+pub fn nearest_power_of_two(value: u32) -> u32 {
+    let value = value.max(1);
+    let up = value.next_power_of_two();
+    let down = (up / 2).max(1);
+    if value - down < up - value { down } else { up }
+}
+
+/// 缩放到指定尺寸，尺寸不变时原样返回
+pub fn resize(image: RgbaImage, width: u32, height: u32, filter: Filter) -> RgbaImage {
+    if image.dimensions() == (width, height) {
+        return image;
+    }
+    image::imageops::resize(&image, width, height, filter.filter_type())
+}
+
+/// 向右下方补边到指定尺寸，新增像素取最近的边缘像素
+pub fn pad(image: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+    let (right, bottom) = (image.width() - 1, image.height() - 1);
+    RgbaImage::from_fn(width, height, |x, y| *image.get_pixel(x.min(right), y.min(bottom)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sizes() {
+        assert_eq!(nearest_power_of_two(3412), 4096);
+        assert_eq!(nearest_power_of_two(1362), 1024);
+        assert_eq!(nearest_power_of_two(3), 4);
+        assert_eq!(nearest_power_of_two(256), 256);
+        assert_eq!(nearest_power_of_two(1), 1);
+
+        assert_eq!(fit(3412, 1362, 1024), (1024, 409));
+        assert_eq!(fit(100, 50, 1024), (100, 50));
+        assert_eq!(fit(4000, 1, 64), (64, 1));
+
+        assert_eq!(
+            "0,8,16,4".parse(),
+            Ok(Region {
+                x: 0,
+                y: 8,
+                width: 16,
+                height: 4
+            })
+        );
+        assert!("0,8,0,4".parse::<Region>().is_err());
+        assert_eq!(
+            "512x256".parse(),
+            Ok(Size {
+                width: 512,
+                height: 256
+            })
+        );
+        assert!("512".parse::<Size>().is_err());
+    }
+
+    #[test]
+    fn test_crop_and_pad() {
+        let image = RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+
+        let cropped = crop(
+            &image,
+            Region {
+                x: 1,
+                y: 0,
+                width: 2,
+                height: 2,
+            },
+        )
+        .unwrap();
+        assert_eq!(cropped.get_pixel(0, 1).0, [1, 1, 0, 255]);
+        assert!(
+            crop(
+                &image,
+                Region {
+                    x: 2,
+                    y: 0,
+                    width: 2,
+                    height: 2
+                }
+            )
+            .is_err()
+        );
+
+        let padded = pad(&image, 4, 4);
+        assert_eq!(padded.dimensions(), (4, 4));
+        assert_eq!(padded.get_pixel(3, 3).0, [2, 1, 0, 255]);
+        assert_eq!(padded.get_pixel(0, 2).0, [0, 1, 0, 255]);
+    }
+}
@@ -0,0 +1,969 @@
+//! KTX 2.0 规范检查
+//!
+//! 不借助 ktx2 crate 的 `Reader`（它会直接拒绝很多不合规的文件），而是读取原始字节，
+//! 逐项检查头部、层级索引、DFD、键值数据和超级压缩全局数据。
+//! 每个问题带有字节偏移和严重程度：Error 表示违反规范，Warning 表示可疑但允许
+
+use serde::Serialize;
+
+use crate::decode;
+use crate::texture::KTX2_IDENTIFIER;
+
+const HEADER_LENGTH: usize = 80;
+const LEVEL_INDEX_LENGTH: usize = 24;
+
+/// 头部字段的字节偏移
+const VK_FORMAT: usize = 12;
+const TYPE_SIZE: usize = 16;
+const PIXEL_WIDTH: usize = 20;
+const PIXEL_HEIGHT: usize = 24;
+const PIXEL_DEPTH: usize = 28;
+const LAYER_COUNT: usize = 32;
+const FACE_COUNT: usize = 36;
+const LEVEL_COUNT: usize = 40;
+const SUPERCOMPRESSION_SCHEME: usize = 44;
+const DFD_BYTE_OFFSET: usize = 48;
+const DFD_BYTE_LENGTH: usize = 52;
+const KVD_BYTE_OFFSET: usize = 56;
+const KVD_BYTE_LENGTH: usize = 60;
+const SGD_BYTE_OFFSET: usize = 64;
+const SGD_BYTE_LENGTH: usize = 72;
+
+/// 超级压缩方案
+const SCHEME_NONE: u32 = 0;
+const SCHEME_BASIS_LZ: u32 = 1;
+const SCHEME_ZLIB: u32 = 3;
+
+/// DFD 颜色模型
+const MODEL_RGBSDA: u8 = 1;
+const MODEL_BC1A: u8 = 128;
+const MODEL_ETC2: u8 = 161;
+const MODEL_ASTC: u8 = 162;
+const MODEL_ETC1S: u8 = 163;
+const MODEL_UASTC: u8 = 166;
+
+/// DFD 传输函数
+const TRANSFER_LINEAR: u8 = 1;
+const TRANSFER_SRGB: u8 = 2;
+
+/// 规范定义的 `KTX` 前缀键
+const KNOWN_KEYS: [&str; 10] = [
+    "KTXanimData",
+    "KTXastcDecodeMode",
+    "KTXcubemapIncomplete",
+    "KTXdxgiFormat__",
+    "KTXglFormat",
+    "KTXmetalPixelFormat",
+    "KTXorientation",
+    "KTXswizzle",
+    "KTXwriter",
+    "KTXwriterScParams",
+];
+
+/// 问题严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// 一个规范问题
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Issue {
+    /// 问题所在的字节偏移
+    pub offset: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// 检查整个文件，按发现顺序返回所有问题
+pub fn validate(bytes: &[u8]) -> Vec<Issue> {
+    let mut validator = Validator {
+        bytes,
+        issues: Vec::new(),
+    };
+    validator.run();
+    validator.issues
+}
+
+/// 头部字段
+struct Header {
+    vk_format: u32,
+    type_size: u32,
+    width: u32,
+    height: u32,
+    depth: u32,
+    layers: u32,
+    faces: u32,
+    levels: u32,
+    scheme: u32,
+    dfd_offset: u32,
+    dfd_length: u32,
+    kvd_offset: u32,
+    kvd_length: u32,
+    sgd_offset: u64,
+    sgd_length: u64,
+}
+
+impl Header {
+    fn parse(bytes: &[u8]) -> Self {
+        let u32_at = |offset| read_u32(bytes, offset).unwrap_or(0);
+        let u64_at = |offset| read_u64(bytes, offset).unwrap_or(0);
+        Self {
+            vk_format: u32_at(VK_FORMAT),
+            type_size: u32_at(TYPE_SIZE),
+            width: u32_at(PIXEL_WIDTH),
+            height: u32_at(PIXEL_HEIGHT),
+            depth: u32_at(PIXEL_DEPTH),
+            layers: u32_at(LAYER_COUNT),
+            faces: u32_at(FACE_COUNT),
+            levels: u32_at(LEVEL_COUNT),
+            scheme: u32_at(SUPERCOMPRESSION_SCHEME),
+            dfd_offset: u32_at(DFD_BYTE_OFFSET),
+            dfd_length: u32_at(DFD_BYTE_LENGTH),
+            kvd_offset: u32_at(KVD_BYTE_OFFSET),
+            kvd_length: u32_at(KVD_BYTE_LENGTH),
+            sgd_offset: u64_at(SGD_BYTE_OFFSET),
+            sgd_length: u64_at(SGD_BYTE_LENGTH),
+        }
+    }
+
+    fn level_count(&self) -> usize {
+        self.levels.max(1) as usize
+    }
+
+    /// 层级索引结束（即 DFD 应该开始）的位置
+    fn level_index_end(&self) -> usize {
+        HEADER_LENGTH + self.level_count() * LEVEL_INDEX_LENGTH
+    }
+}
+
+/// 层级索引项
+struct LevelIndex {
+    byte_offset: u64,
+    byte_length: u64,
+    uncompressed_byte_length: u64,
+}
+
+/// 从 vkFormat 名称推导出的格式属性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FormatInfo {
+    /// 纹素块的宽、高、深
+    block: [u32; 3],
+    /// 每个纹素块的字节数
+    block_bytes: u32,
+    /// 头部 typeSize 应有的值
+    type_size: u32,
+    srgb: bool,
+    color_model: u8,
+    /// 未压缩格式的通道数（DFD 采样数），块压缩格式为 None
+    channels: Option<usize>,
+}
+
+impl FormatInfo {
+    fn is_block_compressed(&self) -> bool {
+        self.color_model != MODEL_RGBSDA
+    }
+}
+
+struct Validator<'a> {
+    bytes: &'a [u8],
+    issues: Vec<Issue>,
+}
+
+impl Validator<'_> {
+    fn error(&mut self, offset: usize, message: impl Into<String>) {
+        self.push(offset, Severity::Error, message.into());
+    }
+
+    fn warning(&mut self, offset: usize, message: impl Into<String>) {
+        self.push(offset, Severity::Warning, message.into());
+    }
+
+    fn push(&mut self, offset: usize, severity: Severity, message: String) {
+        self.issues.push(Issue {
+            offset,
+            severity,
+            message,
+        });
+    }
+
+    fn run(&mut self) {
+        if self.bytes.len() < HEADER_LENGTH {
+            self.error(
+                0,
+                format!("File is {} bytes, shorter than the 80-byte header", self.bytes.len()),
+            );
+            return;
+        }
+        if !self.bytes.starts_with(&KTX2_IDENTIFIER) {
+            self.error(0, "Invalid KTX2 identifier");
+            return;
+        }
+
+        let header = Header::parse(self.bytes);
+        let format = ktx2::Format::new(header.vk_format).and_then(format_info);
+        self.check_header(&header, format);
+        let levels = self.check_level_index(&header, format);
+        self.check_dfd(&header, format);
+        self.check_kvd(&header);
+        self.check_sgd(&header);
+        self.check_level_placement(&header, &levels);
+    }
+
+    fn check_header(&mut self, header: &Header, format: Option<FormatInfo>) {
+        if header.vk_format == 0 {
+            if header.scheme != SCHEME_BASIS_LZ {
+                self.warning(
+                    VK_FORMAT,
+                    "vkFormat is VK_FORMAT_UNDEFINED; only BasisLZ or formats without a Vulkan equivalent should use it",
+                );
+            }
+        } else if header.scheme == SCHEME_BASIS_LZ {
+            self.error(VK_FORMAT, "BasisLZ supercompression requires vkFormat VK_FORMAT_UNDEFINED");
+        } else if format_name(header.vk_format).starts_with("Format(") {
+            self.warning(VK_FORMAT, format!("Unknown vkFormat {}", header.vk_format));
+        }
+
+        let expected_type_size = match format {
+            _ if header.vk_format == 0 => Some(1),
+            Some(info) => Some(info.type_size),
+            None => None,
+        };
+        if let Some(expected) = expected_type_size.filter(|&expected| expected != header.type_size) {
+            self.error(
+                TYPE_SIZE,
+                format!("typeSize is {}, expected {} for this vkFormat", header.type_size, expected),
+            );
+        }
+
+        if header.width == 0 {
+            self.error(PIXEL_WIDTH, "pixelWidth must not be 0");
+        }
+        if header.depth > 0 && header.height == 0 {
+            self.error(PIXEL_HEIGHT, "pixelHeight must not be 0 when pixelDepth is non-zero");
+        }
+
+        match header.faces {
+            1 => {}
+            6 => {
+                if header.width != header.height {
+                    self.error(PIXEL_WIDTH, "Cube map faces must be square");
+                }
+                if header.depth != 0 {
+                    self.error(PIXEL_DEPTH, "Cube maps must have pixelDepth 0");
+                }
+            }
+            faces => self.error(FACE_COUNT, format!("faceCount is {}, must be 1 or 6", faces)),
+        }
+
+        let max_levels = 32 - header.width.max(header.height).max(header.depth).max(1).leading_zeros();
+        if header.levels > max_levels {
+            self.error(
+                LEVEL_COUNT,
+                format!("levelCount {} exceeds the full mip chain of {} levels", header.levels, max_levels),
+            );
+        }
+        if header.levels == 0 && format.is_some_and(|info| info.is_block_compressed()) {
+            self.warning(
+                LEVEL_COUNT,
+                "levelCount 0 asks loaders to generate mipmaps, which is not possible for block-compressed formats",
+            );
+        }
+
+        match header.scheme {
+            0..=SCHEME_ZLIB => {}
+            0x0001_0000.. => self.warning(
+                SUPERCOMPRESSION_SCHEME,
+                format!("Vendor-specific supercompressionScheme {}", header.scheme),
+            ),
+            scheme => self.error(
+                SUPERCOMPRESSION_SCHEME,
+                format!("Reserved supercompressionScheme {}", scheme),
+            ),
+        }
+    }
+
+    /// 检查层级索引的每一项，返回解析出的索引（超出文件时为空）
+    fn check_level_index(&mut self, header: &Header, format: Option<FormatInfo>) -> Vec<LevelIndex> {
+        let count = header.level_count();
+        if header.level_index_end() > self.bytes.len() {
+            self.error(
+                HEADER_LENGTH,
+                format!("Level index for {} levels extends past the end of the file", count),
+            );
+            return Vec::new();
+        }
+
+        let levels: Vec<LevelIndex> = (0..count)
+            .map(|i| {
+                let offset = HEADER_LENGTH + i * LEVEL_INDEX_LENGTH;
+                LevelIndex {
+                    byte_offset: read_u64(self.bytes, offset).unwrap_or(0),
+                    byte_length: read_u64(self.bytes, offset + 8).unwrap_or(0),
+                    uncompressed_byte_length: read_u64(self.bytes, offset + 16).unwrap_or(0),
+                }
+            })
+            .collect();
+
+        // 未超级压缩时按 lcm(纹素块字节数, 4) 对齐，超级压缩后不要求对齐
+        let alignment = match format {
+            Some(info) if header.scheme == SCHEME_NONE => lcm(info.block_bytes as u64, 4),
+            _ => 1,
+        };
+
+        for (i, level) in levels.iter().enumerate() {
+            let offset = HEADER_LENGTH + i * LEVEL_INDEX_LENGTH;
+            let end = level.byte_offset.checked_add(level.byte_length);
+            if end.is_none_or(|end| end > self.bytes.len() as u64) {
+                self.error(
+                    offset,
+                    format!(
+                        "Level {} data ({} bytes at {}) extends past the end of the file",
+                        i, level.byte_length, level.byte_offset
+                    ),
+                );
+            }
+            if level.byte_length == 0 {
+                self.error(offset + 8, format!("Level {} byteLength is 0", i));
+            }
+            if !level.byte_offset.is_multiple_of(alignment) {
+                self.error(
+                    offset,
+                    format!(
+                        "Level {} byteOffset {} is not aligned to {} bytes",
+                        i, level.byte_offset, alignment
+                    ),
+                );
+            }
+
+            match header.scheme {
+                SCHEME_NONE if level.byte_length != level.uncompressed_byte_length => self.error(
+                    offset + 16,
+                    format!(
+                        "Level {} uncompressedByteLength {} differs from byteLength {} without supercompression",
+                        i, level.uncompressed_byte_length, level.byte_length
+                    ),
+                ),
+                SCHEME_BASIS_LZ if level.uncompressed_byte_length != 0 => self.error(
+                    offset + 16,
+                    format!("Level {} uncompressedByteLength must be 0 with BasisLZ", i),
+                ),
+                _ => {}
+            }
+
+            if let Some(info) = format.filter(|_| header.scheme != SCHEME_BASIS_LZ) {
+                let expected = expected_level_size(header, info, i as u32);
+                if expected != Some(level.uncompressed_byte_length) {
+                    self.error(
+                        offset + 16,
+                        format!(
+                            "Level {} uncompressedByteLength is {}, expected {} for {}x{} {}",
+                            i,
+                            level.uncompressed_byte_length,
+                            expected.map_or("more than 2^64 bytes".to_string(), |size| size.to_string()),
+                            decode::level_extent(header.width, i as u32),
+                            decode::level_extent(header.height, i as u32),
+                            format_name(header.vk_format)
+                        ),
+                    );
+                }
+            }
+        }
+
+        // 层级数据按从小到大存放，层级 i+1 必须位于层级 i 之前
+        for i in 1..levels.len() {
+            if levels[i].byte_offset >= levels[i - 1].byte_offset {
+                self.error(
+                    HEADER_LENGTH + i * LEVEL_INDEX_LENGTH,
+                    format!(
+                        "Level {} (offset {}) must be stored before level {} (offset {}); levels are ordered smallest first",
+                        i,
+                        levels[i].byte_offset,
+                        i - 1,
+                        levels[i - 1].byte_offset
+                    ),
+                );
+            }
+        }
+
+        levels
+    }
+
+    fn check_dfd(&mut self, header: &Header, format: Option<FormatInfo>) {
+        let dfd_offset = header.dfd_offset as usize;
+        let dfd_length = header.dfd_length as usize;
+        let expected_offset = header.level_index_end();
+        if dfd_offset != expected_offset {
+            self.error(
+                DFD_BYTE_OFFSET,
+                format!(
+                    "dfdByteOffset is {}, but the DFD must immediately follow the level index at {}",
+                    dfd_offset, expected_offset
+                ),
+            );
+        }
+        if !dfd_offset.is_multiple_of(4) {
+            self.error(DFD_BYTE_OFFSET, format!("dfdByteOffset {} is not 4-byte aligned", dfd_offset));
+        }
+        let end = dfd_offset + dfd_length;
+        if dfd_length < 4 + 8 || end > self.bytes.len() {
+            self.error(
+                DFD_BYTE_OFFSET,
+                format!("DFD ({} bytes at {}) is truncated or out of bounds", dfd_length, dfd_offset),
+            );
+            return;
+        }
+
+        let total_size = read_u32(self.bytes, dfd_offset).unwrap_or(0) as usize;
+        if total_size != dfd_length {
+            self.error(
+                dfd_offset,
+                format!("dfdTotalSize is {}, but dfdByteLength is {}", total_size, dfd_length),
+            );
+        }
+
+        // 遍历描述块，找到基本描述块
+        let mut basic = None;
+        let mut position = dfd_offset + 4;
+        while position + 8 <= end {
+            let word = read_u32(self.bytes, position).unwrap_or(0);
+            let version = read_u16(self.bytes, position + 4).unwrap_or(0);
+            let size = read_u16(self.bytes, position + 6).unwrap_or(0) as usize;
+            if size < 8 || position + size > end {
+                self.error(
+                    position + 6,
+                    format!("Descriptor block size {} is invalid or overruns the DFD", size),
+                );
+                return;
+            }
+            let (vendor_id, descriptor_type) = (word & 0x1FFFF, word >> 17);
+            if vendor_id == 0 && descriptor_type == 0 && basic.is_none() {
+                basic = Some((position, version, size));
+            }
+            position += size;
+        }
+        if position != end {
+            self.error(position, format!("{} trailing bytes after the last descriptor block", end - position));
+        }
+
+        let Some((block, version, size)) = basic else {
+            self.error(dfd_offset + 4, "DFD has no Khronos basic descriptor block");
+            return;
+        };
+        if version != 2 {
+            self.warning(
+                block + 4,
+                format!("Basic descriptor block version is {}, expected 2 (KDFS 1.3)", version),
+            );
+        }
+        if size < 24 || !(size - 24).is_multiple_of(16) {
+            self.error(
+                block + 6,
+                format!("Basic descriptor block size {} is not 24 + 16 × sample count", size),
+            );
+            return;
+        }
+        let samples = (size - 24) / 16;
+
+        // 基本描述块头部：颜色模型、原色、传输函数、标志、纹素块尺寸、每平面字节数
+        let fields = block + 8;
+        let color_model = self.bytes[fields];
+        let transfer_function = self.bytes[fields + 2];
+        let block_dimensions = [
+            self.bytes[fields + 4] as u32 + 1,
+            self.bytes[fields + 5] as u32 + 1,
+            self.bytes[fields + 6] as u32 + 1,
+        ];
+        let bytes_plane0 = self.bytes[fields + 8] as u32;
+
+        if header.vk_format == 0 {
+            if header.scheme == SCHEME_BASIS_LZ && color_model != MODEL_ETC1S {
+                self.error(
+                    fields,
+                    format!("BasisLZ requires color model ETC1S ({}), found {}", MODEL_ETC1S, color_model),
+                );
+            }
+            if header.scheme != SCHEME_BASIS_LZ && color_model == MODEL_ETC1S {
+                self.error(fields, "Color model ETC1S requires BasisLZ supercompression");
+            }
+            if header.scheme == SCHEME_BASIS_LZ && bytes_plane0 != 0 {
+                self.error(fields + 8, "bytesPlane0 must be 0 with BasisLZ");
+            }
+            if color_model == MODEL_UASTC && bytes_plane0 != 16 && header.scheme != SCHEME_BASIS_LZ {
+                self.error(fields + 8, format!("bytesPlane0 is {}, UASTC blocks are 16 bytes", bytes_plane0));
+            }
+            return;
+        }
+
+        let Some(info) = format else {
+            return;
+        };
+        let name = format_name(header.vk_format);
+        if color_model != info.color_model {
+            self.error(
+                fields,
+                format!("DFD color model is {}, expected {} for {}", color_model, info.color_model, name),
+            );
+        }
+        let expected_transfer = if info.srgb { TRANSFER_SRGB } else { TRANSFER_LINEAR };
+        if transfer_function != expected_transfer {
+            self.error(
+                fields + 2,
+                format!(
+                    "DFD transfer function is {}, expected {} ({}) for {}",
+                    transfer_function,
+                    expected_transfer,
+                    if info.srgb { "sRGB" } else { "linear" },
+                    name
+                ),
+            );
+        }
+        if block_dimensions != info.block {
+            self.error(
+                fields + 4,
+                format!(
+                    "DFD texel block is {}x{}x{}, expected {}x{}x{} for {}",
+                    block_dimensions[0],
+                    block_dimensions[1],
+                    block_dimensions[2],
+                    info.block[0],
+                    info.block[1],
+                    info.block[2],
+                    name
+                ),
+            );
+        }
+        // 超级压缩后的数据没有固定的块大小，bytesPlane0 为 0（unsized）
+        let expected_plane0 = if header.scheme == SCHEME_NONE { info.block_bytes } else { 0 };
+        if bytes_plane0 != expected_plane0 {
+            self.error(
+                fields + 8,
+                format!(
+                    "DFD bytesPlane0 is {}, expected {} for {}{}",
+                    bytes_plane0,
+                    expected_plane0,
+                    name,
+                    if header.scheme == SCHEME_NONE { "" } else { " with supercompression" }
+                ),
+            );
+        }
+        if let Some(channels) = info.channels.filter(|&channels| channels != samples) {
+            self.error(
+                fields + 16,
+                format!("DFD has {} samples, expected {} for {}", samples, channels, name),
+            );
+        }
+    }
+
+    fn check_kvd(&mut self, header: &Header) {
+        let kvd_offset = header.kvd_offset as usize;
+        let kvd_length = header.kvd_length as usize;
+        if kvd_length == 0 {
+            if kvd_offset != 0 {
+                self.warning(KVD_BYTE_OFFSET, "kvdByteOffset should be 0 when kvdByteLength is 0");
+            }
+            self.warning(KVD_BYTE_LENGTH, "KTXwriter is missing");
+            return;
+        }
+
+        let expected_offset = header.dfd_offset as usize + header.dfd_length as usize;
+        if kvd_offset != expected_offset {
+            self.error(
+                KVD_BYTE_OFFSET,
+                format!(
+                    "kvdByteOffset is {}, but key/value data must immediately follow the DFD at {}",
+                    kvd_offset, expected_offset
+                ),
+            );
+        }
+        let end = kvd_offset + kvd_length;
+        if end > self.bytes.len() {
+            self.error(
+                KVD_BYTE_OFFSET,
+                format!("Key/value data ({} bytes at {}) extends past the end of the file", kvd_length, kvd_offset),
+            );
+            return;
+        }
+
+        let bytes = self.bytes;
+        let mut previous: Option<&[u8]> = None;
+        let mut has_writer = false;
+        let mut position = kvd_offset;
+        while position < end {
+            let Some(length) = read_u32(self.bytes, position).filter(|_| position + 4 <= end) else {
+                self.error(position, "Truncated keyAndValueByteLength");
+                return;
+            };
+            let entry_end = position + 4 + length as usize;
+            if entry_end > end {
+                self.error(
+                    position,
+                    format!("Key/value entry of {} bytes overruns the key/value data", length),
+                );
+                return;
+            }
+
+            let entry = &bytes[position + 4..entry_end];
+            match entry.iter().position(|&b| b == 0) {
+                None => self.error(position + 4, "Key is not NUL-terminated"),
+                Some(0) => self.error(position + 4, "Key is empty"),
+                Some(nul) => {
+                    let key = &entry[..nul];
+                    match std::str::from_utf8(key) {
+                        Err(_) => self.error(position + 4, "Key is not valid UTF-8"),
+                        Ok(name) => {
+                            if name.starts_with("KTX") && !KNOWN_KEYS.contains(&name) {
+                                self.warning(position + 4, format!("Unknown reserved key {}", name));
+                            }
+                            has_writer |= name == "KTXwriter";
+                        }
+                    }
+                    match previous {
+                        Some(prev) if prev == key => self.error(
+                            position + 4,
+                            format!("Duplicate key {}", String::from_utf8_lossy(key)),
+                        ),
+                        Some(prev) if prev > key => self.error(
+                            position + 4,
+                            format!(
+                                "Keys are not sorted: {} follows {}",
+                                String::from_utf8_lossy(key),
+                                String::from_utf8_lossy(prev)
+                            ),
+                        ),
+                        _ => {}
+                    }
+                    previous = Some(key);
+                }
+            }
+
+            // 每项按 4 字节对齐，填充字节应为 0
+            let next = align(entry_end, 4).min(end);
+            if bytes[entry_end..next].iter().any(|&b| b != 0) {
+                self.warning(entry_end, "Key/value padding bytes are not zero");
+            }
+            position = next;
+        }
+
+        if !has_writer {
+            self.warning(kvd_offset, "KTXwriter is missing");
+        }
+    }
+
+    fn check_sgd(&mut self, header: &Header) {
+        if header.scheme != SCHEME_BASIS_LZ {
+            if header.sgd_length != 0 {
+                self.error(
+                    SGD_BYTE_LENGTH,
+                    format!(
+                        "Supercompression global data must be empty for supercompressionScheme {}",
+                        header.scheme
+                    ),
+                );
+            } else if header.sgd_offset != 0 {
+                self.warning(SGD_BYTE_OFFSET, "sgdByteOffset should be 0 when sgdByteLength is 0");
+            }
+            return;
+        }
+
+        if header.sgd_length == 0 {
+            self.error(SGD_BYTE_LENGTH, "BasisLZ requires supercompression global data");
+            return;
+        }
+        if !header.sgd_offset.is_multiple_of(8) {
+            self.error(
+                SGD_BYTE_OFFSET,
+                format!("sgdByteOffset {} is not 8-byte aligned", header.sgd_offset),
+            );
+        }
+        let start = header.sgd_offset as usize;
+        let length = header.sgd_length as usize;
+        if start.checked_add(length).is_none_or(|end| end > self.bytes.len()) {
+            self.error(
+                SGD_BYTE_OFFSET,
+                format!("Supercompression global data ({} bytes at {}) extends past the end of the file", length, start),
+            );
+            return;
+        }
+        if length < 20 {
+            self.error(start, "BasisLZ global data is shorter than its 20-byte header");
+            return;
+        }
+
+        // BasisLZ 全局数据：头部 + 每个图像的描述 + 端点/选择器/霍夫曼表/扩展数据
+        let sections: u64 = [4, 8, 12, 16]
+            .iter()
+            .map(|&field| read_u32(self.bytes, start + field).unwrap_or(0) as u64)
+            .sum();
+        let image_count: u64 = (0..header.level_count() as u32)
+            .map(|level| {
+                (header.layers.max(1) as u64 * header.faces as u64)
+                    .saturating_mul(decode::level_extent(header.depth, level) as u64)
+            })
+            .fold(0, u64::saturating_add);
+        let expected = image_count.saturating_mul(20).saturating_add(20 + sections);
+        if expected != header.sgd_length {
+            self.error(
+                SGD_BYTE_LENGTH,
+                format!(
+                    "BasisLZ global data is {} bytes, expected {} for {} images",
+                    header.sgd_length, expected, image_count
+                ),
+            );
+        }
+    }
+
+    /// 层级数据不能互相重叠，也不能与元数据重叠
+    fn check_level_placement(&mut self, header: &Header, levels: &[LevelIndex]) {
+        let metadata_end = [
+            header.level_index_end() as u64,
+            header.dfd_offset as u64 + header.dfd_length as u64,
+            header.kvd_offset as u64 + header.kvd_length as u64,
+            header.sgd_offset.saturating_add(header.sgd_length),
+        ]
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+
+        let mut order: Vec<usize> = (0..levels.len()).collect();
+        order.sort_by_key(|&i| levels[i].byte_offset);
+        for (n, &i) in order.iter().enumerate() {
+            let level = &levels[i];
+            if level.byte_length > 0 && level.byte_offset < metadata_end {
+                self.error(
+                    HEADER_LENGTH + i * LEVEL_INDEX_LENGTH,
+                    format!(
+                        "Level {} data at {} overlaps the header, index or metadata ending at {}",
+                        i, level.byte_offset, metadata_end
+                    ),
+                );
+            }
+            if let Some(&next) = order.get(n + 1)
+                && level
+                    .byte_offset
+                    .checked_add(level.byte_length)
+                    .is_none_or(|end| end > levels[next].byte_offset)
+            {
+                self.error(
+                    HEADER_LENGTH + next * LEVEL_INDEX_LENGTH,
+                    format!("Level {} data overlaps level {}", next, i),
+                );
+            }
+        }
+    }
+}
+
+/// 层级 `level` 未压缩时的字节数，超出 u64 时返回 `None`
+fn expected_level_size(header: &Header, info: FormatInfo, level: u32) -> Option<u64> {
+    let blocks = |size: u32, block: u32| decode::level_extent(size, level).div_ceil(block) as u64;
+    blocks(header.width, info.block[0])
+        .checked_mul(blocks(header.height, info.block[1]))?
+        .checked_mul(blocks(header.depth, info.block[2]))?
+        .checked_mul(info.block_bytes as u64)?
+        .checked_mul(header.layers.max(1) as u64)?
+        .checked_mul(header.faces.max(1) as u64)
+}
+
+/// 从 vkFormat 名称推导格式属性，深度/模板、多平面等不常见格式返回 None
+fn format_info(format: ktx2::Format) -> Option<FormatInfo> {
+    let name = format!("{:?}", format);
+    let srgb = name.contains("SRGB");
+
+    if name.ends_with("_BLOCK") {
+        let (color_model, block_bytes, block) = if let Some(rest) = name.strip_prefix("BC") {
+            let number: u8 = rest.chars().next()?.to_digit(10)? as u8;
+            let bytes = if matches!(number, 1 | 4) { 8 } else { 16 };
+            (MODEL_BC1A + number - 1, bytes, [4, 4, 1])
+        } else if name.starts_with("ETC2_") || name.starts_with("EAC_") {
+            let bytes = if name.contains("R8G8B8A8") || name.contains("R11G11") { 16 } else { 8 };
+            (MODEL_ETC2, bytes, [4, 4, 1])
+        } else if let Some(rest) = name.strip_prefix("ASTC_") {
+            let size = rest.split('_').next()?;
+            let mut dims = size.split('x').map(|d| d.parse::<u32>().ok());
+            let (w, h) = (dims.next()??, dims.next()??);
+            let d = dims.next().flatten().unwrap_or(1);
+            (MODEL_ASTC, 16, [w, h, d])
+        } else {
+            return None;
+        };
+        return Some(FormatInfo {
+            block,
+            block_bytes,
+            type_size: 1,
+            srgb,
+            color_model,
+            channels: None,
+        });
+    }
+
+    if name.starts_with('D') || name.starts_with('S') || name.starts_with("X8") || name.contains("PLANE") || name.starts_with("G8B8G8") {
+        return None;
+    }
+
+    // 例如 R8G8B8A8_UNORM、A2B10G10R10_UNORM_PACK32、R16G16B16A16_SFLOAT
+    let components = name.split('_').next()?;
+    let mut bits = Vec::new();
+    let mut digits = String::new();
+    for c in components.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+        } else {
+            if !digits.is_empty() {
+                bits.push(digits.parse::<u32>().ok()?);
+                digits.clear();
+            }
+            if !"RGBAEX".contains(c) {
+                return None;
+            }
+        }
+    }
+    bits.push(digits.parse::<u32>().ok()?);
+
+    let total_bits: u32 = bits.iter().sum();
+    let (block_bytes, type_size) = if let Some(pack) = name.rsplit("_PACK").next().filter(|_| name.contains("_PACK")) {
+        let bytes = pack.parse::<u32>().ok()? / 8;
+        (bytes, bytes)
+    } else if total_bits.is_multiple_of(8) && bits.iter().all(|&b| b == bits[0] && b.is_multiple_of(8)) {
+        (total_bits / 8, bits[0] / 8)
+    } else {
+        return None;
+    };
+
+    // 共享指数和带填充位的格式，DFD 采样数与分量数不一致
+    let channels = (!components.contains(['E', 'X'])).then_some(bits.len());
+    Some(FormatInfo {
+        block: [1, 1, 1],
+        block_bytes,
+        type_size,
+        srgb,
+        color_model: MODEL_RGBSDA,
+        channels,
+    })
+}
+
+fn format_name(vk_format: u32) -> String {
+    match ktx2::Format::new(vk_format) {
+        Some(format) => format!("{:?}", format),
+        None => "VK_FORMAT_UNDEFINED".to_string(),
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(bytes.get(offset..offset + 2)?.try_into().ok()?))
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?))
+}
+
+fn read_u64(bytes: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(bytes.get(offset..offset + 8)?.try_into().ok()?))
+}
+
+fn align(offset: usize, alignment: usize) -> usize {
+    offset.div_ceil(alignment) * alignment
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    let gcd = |mut a: u64, mut b: u64| {
+        while b != 0 {
+            (a, b) = (b, a % b);
+        }
+        a
+    };
+    a / gcd(a, b) * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ktx2_writer::Ktx2Writer;
+
+    fn sample_file() -> Vec<u8> {
+        Ktx2Writer::rgba8(4, 2, true)
+            .level(vec![0; 4 * 2 * 4])
+            .level(vec![0; 2 * 4])
+            .level(vec![0; 4])
+            .write()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_writer_output_is_valid() {
+        assert_eq!(validate(&sample_file()), Vec::new());
+        assert_eq!(
+            format_info(ktx2::Format::BC7_SRGB_BLOCK).map(|info| (info.block_bytes, info.color_model, info.srgb)),
+            Some((16, 134, true))
+        );
+        assert_eq!(
+            format_info(ktx2::Format::A2B10G10R10_UNORM_PACK32).map(|info| (info.block_bytes, info.type_size)),
+            Some((4, 4))
+        );
+    }
+
+    #[test]
+    fn test_reports_offsets() {
+        let mut bytes = sample_file();
+        // typeSize 改为 4，DFD 传输函数改为线性
+        bytes[TYPE_SIZE] = 4;
+        let dfd_offset = read_u32(&bytes, DFD_BYTE_OFFSET).unwrap() as usize;
+        bytes[dfd_offset + 4 + 8 + 2] = TRANSFER_LINEAR;
+
+        let issues = validate(&bytes);
+        let offsets: Vec<(usize, Severity)> = issues.iter().map(|issue| (issue.offset, issue.severity)).collect();
+        assert_eq!(
+            offsets,
+            vec![(TYPE_SIZE, Severity::Error), (dfd_offset + 4 + 8 + 2, Severity::Error)]
+        );
+
+        assert_eq!(validate(b"KTX")[0].offset, 0);
+    }
+
+    fn has_issue(issues: &[Issue], offset: usize, text: &str) -> bool {
+        issues.iter().any(|issue| issue.offset == offset && issue.message.contains(text))
+    }
+
+    #[test]
+    fn test_huge_dimensions() {
+        let mut bytes = sample_file();
+        bytes[PIXEL_WIDTH..PIXEL_WIDTH + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        bytes[PIXEL_HEIGHT..PIXEL_HEIGHT + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let issues = validate(&bytes);
+        assert!(has_issue(&issues, HEADER_LENGTH + 16, "expected more than 2^64 bytes"));
+    }
+
+    #[test]
+    fn test_level_range_overflow() {
+        let mut bytes = sample_file();
+        let level = |i: usize| HEADER_LENGTH + i * LEVEL_INDEX_LENGTH;
+        bytes[level(0)..level(0) + 8].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+        bytes[level(1)..level(1) + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        let issues = validate(&bytes);
+        assert!(has_issue(&issues, level(0), "extends past the end of the file"));
+        assert!(has_issue(&issues, level(1), "Level 1 data overlaps level 0"));
+    }
+
+    #[test]
+    fn test_sgd_range_overflow() {
+        let mut bytes = sample_file();
+        bytes[SGD_BYTE_OFFSET..SGD_BYTE_OFFSET + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+        bytes[SGD_BYTE_LENGTH..SGD_BYTE_LENGTH + 8].copy_from_slice(&16u64.to_le_bytes());
+
+        let issues = validate(&bytes);
+        assert!(has_issue(&issues, SGD_BYTE_LENGTH, "must be empty"));
+        assert!(has_issue(&issues, HEADER_LENGTH, "overlaps the header, index or metadata"));
+    }
+}
@@ -0,0 +1,186 @@
+//! 色彩空间模块
+//!
+//! 区分 sRGB 编码和线性数据：
+//! - 从 PNG 的 sRGB / iCCP / gAMA 块识别输入的色彩空间
+//! - 从 KTX2 的 vkFormat 或 DFD 传输函数识别纹理的色彩空间
+//! - 显示线性数据前转换为 sRGB 编码（iced 把 RGBA8 像素当作 sRGB 显示）
+//!
+//! 该模块只依赖 std 和 ktx2
+
+use std::borrow::Cow;
+use std::sync::OnceLock;
+
+/// gAMA 块中 sRGB（1/2.2）与线性（1.0）之间的分界值（× 100000）
+const GAMMA_THRESHOLD: u32 = 72_727;
+
+/// 像素数据的色彩空间
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorSpace {
+    /// sRGB 传输函数编码（对应 `_SRGB` vkFormat）
+    #[default]
+    Srgb,
+    /// 线性数据（对应 `_UNORM` vkFormat），例如法线贴图、粗糙度
+    Linear,
+}
+
+impl ColorSpace {
+    pub fn label(self) -> &'static str {
+        match self {
+            ColorSpace::Srgb => "sRGB",
+            ColorSpace::Linear => "Linear",
+        }
+    }
+
+    pub fn is_srgb(self) -> bool {
+        self == ColorSpace::Srgb
+    }
+}
+
+/// 从 PNG 辅助块识别色彩空间，没有相关块时返回 `None`
+///
+/// 优先级与 PNG 规范一致：sRGB > iCCP > gAMA。
+/// iCCP 只检查配置文件名称，名称含 "linear" 时视为线性，否则视为 sRGB
+pub fn detect_png(bytes: &[u8]) -> Option<ColorSpace> {
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+    if !bytes.starts_with(&SIGNATURE) {
+        return None;
+    }
+
+    let mut iccp = None;
+    let mut gama = None;
+    let mut offset = SIGNATURE.len();
+    while offset + 8 <= bytes.len() {
+        let length = u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+        let chunk_type = &bytes[offset + 4..offset + 8];
+        let data = bytes.get(offset + 8..offset + 8 + length)?;
+
+        match chunk_type {
+            b"sRGB" => return Some(ColorSpace::Srgb),
+            b"iCCP" => {
+                let name_end = data.iter().position(|&b| b == 0).unwrap_or(data.len());
+                let name = String::from_utf8_lossy(&data[..name_end]).to_ascii_lowercase();
+                iccp = Some(if name.contains("linear") {
+                    ColorSpace::Linear
+                } else {
+                    ColorSpace::Srgb
+                });
+            }
+            b"gAMA" if length == 4 => {
+                let gamma = u32::from_be_bytes(data.try_into().unwrap());
+                gama = Some(if gamma >= GAMMA_THRESHOLD {
+                    ColorSpace::Linear
+                } else {
+                    ColorSpace::Srgb
+                });
+            }
+            // 辅助块必须出现在图像数据之前
+            b"IDAT" | b"IEND" => break,
+            _ => {}
+        }
+
+        // 长度 + 类型 + 数据 + CRC
+        offset += 12 + length;
+    }
+
+    iccp.or(gama)
+}
+
+/// 从 KTX2 识别色彩空间：优先看 vkFormat，格式未定义时看 DFD 的传输函数
+pub fn detect_ktx2(bytes: &[u8]) -> Option<ColorSpace> {
+    let reader = ktx2::Reader::new(bytes).ok()?;
+    if let Some(format) = reader.header().format {
+        return Some(format_color_space(format));
+    }
+
+    let basic = reader
+        .dfd_blocks()
+        .find(|block| block.header.vendor_id == 0 && block.header.descriptor_type == 0)
+        .and_then(|block| ktx2::DfdBlockBasic::parse(block.data).ok())?;
+    match basic.header.transfer_function? {
+        ktx2::TransferFunction::SRGB => Some(ColorSpace::Srgb),
+        ktx2::TransferFunction::Linear => Some(ColorSpace::Linear),
+        _ => None,
+    }
+}
+
+/// vkFormat 对应的色彩空间：名称以 `_SRGB` 结尾（或含 `_SRGB_`）的是 sRGB
+pub fn format_color_space(format: ktx2::Format) -> ColorSpace {
+    if format!("{:?}", format).contains("SRGB") {
+        ColorSpace::Srgb
+    } else {
+        ColorSpace::Linear
+    }
+}
+
+/// sRGB 编码值转线性值
+pub fn srgb_to_linear(value: u8) -> f32 {
+    let c = value as f32 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// 线性值转 sRGB 编码值
+pub fn linear_to_srgb(value: f32) -> u8 {
+    let c = value.clamp(0.0, 1.0);
+    let encoded = if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
+/// 转为可直接显示的 sRGB 编码 RGBA8，Alpha 保持不变
+pub fn to_display(rgba: &[u8], color_space: ColorSpace) -> Cow<'_, [u8]> {
+    if color_space.is_srgb() {
+        return Cow::Borrowed(rgba);
+    }
+
+    let lut = linear_to_srgb_lut();
+    Cow::Owned(
+        rgba.chunks_exact(4)
+            .flat_map(|pixel| [lut[pixel[0] as usize], lut[pixel[1] as usize], lut[pixel[2] as usize], pixel[3]])
+            .collect(),
+    )
+}
+
+/// 8 位线性值到 sRGB 编码值的查找表
+fn linear_to_srgb_lut() -> &'static [u8; 256] {
+    static LUT: OnceLock<[u8; 256]> = OnceLock::new();
+    LUT.get_or_init(|| std::array::from_fn(|i| linear_to_srgb(i as f32 / 255.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 构造只含 IHDR 之前辅助块的 PNG 片段（CRC 不参与识别，填 0）
+    fn png_with_chunk(chunk_type: &[u8; 4], data: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+        bytes.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        bytes.extend_from_slice(chunk_type);
+        bytes.extend_from_slice(data);
+        bytes.extend_from_slice(&[0; 4]);
+        bytes
+    }
+
+    #[test]
+    fn test_detect_png_chunks() {
+        assert_eq!(detect_png(&png_with_chunk(b"sRGB", &[0])), Some(ColorSpace::Srgb));
+        assert_eq!(detect_png(&png_with_chunk(b"gAMA", &100_000u32.to_be_bytes())), Some(ColorSpace::Linear));
+        assert_eq!(detect_png(&png_with_chunk(b"gAMA", &45_455u32.to_be_bytes())), Some(ColorSpace::Srgb));
+        assert_eq!(detect_png(&png_with_chunk(b"iCCP", b"Linear Rec709\0\0")), Some(ColorSpace::Linear));
+        assert_eq!(detect_png(&png_with_chunk(b"tEXt", b"a\0b")), None);
+    }
+
+    #[test]
+    fn test_srgb_round_trip() {
+        for value in 0..=255u8 {
+            assert_eq!(linear_to_srgb(srgb_to_linear(value)), value);
+        }
+        assert_eq!(to_display(&[0, 255, 128, 7], ColorSpace::Linear).as_ref(), &[0, 255, 188, 7]);
+    }
+}
@@ -0,0 +1,335 @@
+//! 纹理对比模块
+//!
+//! 对比两张纹理（例如 PNG 源图与 KTX2 输出），检查压缩质量
+//! 支持并排显示、拖动分割线对比、放大的差异图以及 PSNR/SSIM 指标
+
+use std::borrow::Cow;
+
+use iced::widget::image::Handle;
+use serde::{Deserialize, Serialize};
+
+use crate::texture::TextureLoader;
+
+/// SSIM 计算使用的窗口大小
+const SSIM_WINDOW: usize = 8;
+
+/// 尺寸一致的一对 sRGB 编码 RGBA8 像素数据及其尺寸
+type ImagePair<'a> = (Cow<'a, [u8]>, Cow<'a, [u8]>, (u32, u32));
+
+/// 对比显示模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CompareMode {
+    /// 关闭对比，只显示当前纹理
+    #[default]
+    Off,
+    /// 左右并排显示
+    SideBySide,
+    /// 拖动分割线对比
+    Wipe,
+    /// 放大的绝对差异图
+    Difference,
+}
+
+impl CompareMode {
+    pub const ALL: [CompareMode; 4] = [
+        CompareMode::Off,
+        CompareMode::SideBySide,
+        CompareMode::Wipe,
+        CompareMode::Difference,
+    ];
+
+    /// 按钮上显示的名称
+    pub fn label(self) -> &'static str {
+        match self {
+            CompareMode::Off => "Off",
+            CompareMode::SideBySide => "Side by side",
+            CompareMode::Wipe => "Wipe",
+            CompareMode::Difference => "Difference",
+        }
+    }
+}
+
+/// 对比指标
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompareMetrics {
+    /// 峰值信噪比（dB），两图完全相同时为无穷大
+    pub psnr: f64,
+    /// 结构相似度，范围 [-1, 1]，1 表示完全相同
+    pub ssim: f64,
+    /// 单通道最大绝对差值
+    pub max_diff: u8,
+}
+
+/// 纹理对比状态
+///
+/// 左侧通常是 PNG 源图，右侧是 KTX2 输出
+pub struct TextureComparison {
+    /// 显示模式
+    pub mode: CompareMode,
+    /// 左侧（参考）纹理
+    pub left: TextureLoader,
+    /// 右侧（待测）纹理
+    pub right: TextureLoader,
+    /// 分割线位置，0.0 为最左，1.0 为最右
+    wipe: f32,
+    /// 差异图放大倍数
+    amplify: f32,
+    /// 对比指标（两张纹理都加载后计算）
+    metrics: Option<Result<CompareMetrics, String>>,
+    /// 缓存的分割线合成图
+    wipe_handle: Option<Handle>,
+    /// 缓存的差异图
+    difference_handle: Option<Handle>,
+}
+
+impl TextureComparison {
+    /// 创建新的对比状态
+    pub fn new() -> Self {
+        Self {
+            mode: CompareMode::Off,
+            left: TextureLoader::new(),
+            right: TextureLoader::new(),
+            wipe: 0.5,
+            amplify: 10.0,
+            metrics: None,
+            wipe_handle: None,
+            difference_handle: None,
+        }
+    }
+
+    /// 分割线位置
+    pub fn wipe(&self) -> f32 {
+        self.wipe
+    }
+
+    /// 差异图放大倍数
+    pub fn amplify(&self) -> f32 {
+        self.amplify
+    }
+
+    /// 对比指标
+    pub fn metrics(&self) -> Option<&Result<CompareMetrics, String>> {
+        self.metrics.as_ref()
+    }
+
+    /// 设置分割线位置并重新合成
+    pub fn set_wipe(&mut self, wipe: f32) {
+        self.wipe = wipe.clamp(0.0, 1.0);
+        self.wipe_handle = self.build_wipe();
+    }
+
+    /// 设置差异图放大倍数并重新生成差异图
+    pub fn set_amplify(&mut self, amplify: f32) {
+        self.amplify = amplify.max(1.0);
+        self.difference_handle = self.build_difference();
+    }
+
+    /// 在左侧或右侧纹理变化后重新计算指标和缓存图像
+    pub fn refresh(&mut self) {
+        self.metrics = match (self.left.data(), self.right.data()) {
+            (Some(_), Some(_)) => Some(compare(&self.left, &self.right)),
+            _ => None,
+        };
+        self.wipe_handle = self.build_wipe();
+        self.difference_handle = self.build_difference();
+    }
+
+    /// 获取分割线合成图句柄
+    pub fn wipe_handle(&self) -> Option<Handle> {
+        self.wipe_handle.clone()
+    }
+
+    /// 获取差异图句柄
+    pub fn difference_handle(&self) -> Option<Handle> {
+        self.difference_handle.clone()
+    }
+
+    fn comparable(&self) -> Option<ImagePair<'_>> {
+        let dims = self.left.dimensions()?;
+        if self.right.dimensions()? != dims {
+            return None;
+        }
+        Some((self.left.display_data()?, self.right.display_data()?, dims))
+    }
+
+    fn build_wipe(&self) -> Option<Handle> {
+        let (left, right, (width, height)) = self.comparable()?;
+        let pixels = wipe_image(&left, &right, width, height, self.wipe);
+        Some(Handle::from_rgba(width, height, pixels))
+    }
+
+    fn build_difference(&self) -> Option<Handle> {
+        let (left, right, (width, height)) = self.comparable()?;
+        let pixels = difference_image(&left, &right, self.amplify);
+        Some(Handle::from_rgba(width, height, pixels))
+    }
+}
+
+impl Default for TextureComparison {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 对比两张已加载的纹理，计算 PSNR 和 SSIM
+///
+/// 两边都先转换为 sRGB 编码，色彩空间不同的纹理也能直接比较
+pub fn compare(left: &TextureLoader, right: &TextureLoader) -> Result<CompareMetrics, String> {
+    let (a, b) = match (left.display_data(), right.display_data()) {
+        (Some(a), Some(b)) => (a, b),
+        _ => return Err("Both textures must be loaded".to_string()),
+    };
+    let dims = left.dimensions().unwrap_or_default();
+    if right.dimensions() != Some(dims) {
+        return Err(format!(
+            "Dimension mismatch: {:?} vs {:?}",
+            left.dimensions(),
+            right.dimensions()
+        ));
+    }
+
+    let max_diff = a
+        .iter()
+        .zip(b.iter())
+        .map(|(x, y)| x.abs_diff(*y))
+        .max()
+        .unwrap_or(0);
+
+    Ok(CompareMetrics {
+        psnr: psnr(&a, &b),
+        ssim: ssim(&a, &b, dims.0, dims.1),
+        max_diff,
+    })
+}
+
+/// 计算 RGB 通道的峰值信噪比（dB）
+///
+/// Alpha 通道不参与计算
+pub fn psnr(a: &[u8], b: &[u8]) -> f64 {
+    let mut sum = 0.0f64;
+    let mut count = 0usize;
+    for (pa, pb) in a.chunks_exact(4).zip(b.chunks_exact(4)) {
+        for c in 0..3 {
+            let d = pa[c] as f64 - pb[c] as f64;
+            sum += d * d;
+        }
+        count += 3;
+    }
+
+    if count == 0 || sum == 0.0 {
+        return f64::INFINITY;
+    }
+
+    let mse = sum / count as f64;
+    10.0 * (255.0 * 255.0 / mse).log10()
+}
+
+/// 计算亮度通道的平均结构相似度
+///
+/// 使用 8x8 不重叠窗口，边缘不足一个窗口的部分按实际大小计算
+pub fn ssim(a: &[u8], b: &[u8], width: u32, height: u32) -> f64 {
+    const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+    const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+    let (width, height) = (width as usize, height as usize);
+    let la = luminance(a);
+    let lb = luminance(b);
+
+    let mut total = 0.0;
+    let mut windows = 0usize;
+
+    for wy in (0..height).step_by(SSIM_WINDOW) {
+        for wx in (0..width).step_by(SSIM_WINDOW) {
+            let y_end = (wy + SSIM_WINDOW).min(height);
+            let x_end = (wx + SSIM_WINDOW).min(width);
+            let n = ((y_end - wy) * (x_end - wx)) as f64;
+
+            let (mut sum_a, mut sum_b) = (0.0, 0.0);
+            for y in wy..y_end {
+                for x in wx..x_end {
+                    sum_a += la[y * width + x];
+                    sum_b += lb[y * width + x];
+                }
+            }
+            let (mean_a, mean_b) = (sum_a / n, sum_b / n);
+
+            let (mut var_a, mut var_b, mut cov) = (0.0, 0.0, 0.0);
+            for y in wy..y_end {
+                for x in wx..x_end {
+                    let da = la[y * width + x] - mean_a;
+                    let db = lb[y * width + x] - mean_b;
+                    var_a += da * da;
+                    var_b += db * db;
+                    cov += da * db;
+                }
+            }
+            var_a /= n;
+            var_b /= n;
+            cov /= n;
+
+            total += ((2.0 * mean_a * mean_b + C1) * (2.0 * cov + C2))
+                / ((mean_a * mean_a + mean_b * mean_b + C1) * (var_a + var_b + C2));
+            windows += 1;
+        }
+    }
+
+    if windows == 0 { 1.0 } else { total / windows as f64 }
+}
+
+/// 生成放大后的绝对差异图（RGBA8，Alpha 固定为 255）
+pub fn difference_image(a: &[u8], b: &[u8], amplify: f32) -> Vec<u8> {
+    let mut out = Vec::with_capacity(a.len());
+    for (pa, pb) in a.chunks_exact(4).zip(b.chunks_exact(4)) {
+        for c in 0..3 {
+            let d = pa[c].abs_diff(pb[c]) as f32 * amplify;
+            out.push(d.min(255.0) as u8);
+        }
+        out.push(255);
+    }
+    out
+}
+
+/// 合成分割线对比图：分割线左侧取 `a`，右侧取 `b`
+pub fn wipe_image(a: &[u8], b: &[u8], width: u32, height: u32, position: f32) -> Vec<u8> {
+    let row_bytes = width as usize * 4;
+    let split = ((width as f32 * position.clamp(0.0, 1.0)).round() as usize * 4).min(row_bytes);
+
+    let mut out = Vec::with_capacity(a.len());
+    for y in 0..height as usize {
+        let row = y * row_bytes;
+        out.extend_from_slice(&a[row..row + split]);
+        out.extend_from_slice(&b[row + split..row + row_bytes]);
+    }
+    out
+}
+
+/// 将 RGBA8 转为 BT.709 亮度
+fn luminance(rgba: &[u8]) -> Vec<f64> {
+    rgba.chunks_exact(4)
+        .map(|p| 0.2126 * p[0] as f64 + 0.7152 * p[1] as f64 + 0.0722 * p[2] as f64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identical_images() {
+        let pixels: Vec<u8> = (0..16 * 16 * 4).map(|i| (i % 251) as u8).collect();
+        assert!(psnr(&pixels, &pixels).is_infinite());
+        assert!((ssim(&pixels, &pixels, 16, 16) - 1.0).abs() < 1e-9);
+        assert!(difference_image(&pixels, &pixels, 10.0)
+            .chunks_exact(4)
+            .all(|p| p == [0, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_wipe_split() {
+        let a = vec![0u8; 4 * 4 * 4];
+        let b = vec![255u8; 4 * 4 * 4];
+        let out = wipe_image(&a, &b, 4, 4, 0.5);
+        assert_eq!(&out[0..8], &[0; 8]);
+        assert_eq!(&out[8..16], &[255; 8]);
+    }
+}
@@ -0,0 +1,355 @@
+//! KTX2 层级数据解码
+//!
+//! 先按 supercompressionScheme 解压层级数据，再按 vkFormat 把单张图像解码为像素：
+//! - 8 位 UNORM / SRGB 格式和 BC1–BC5、BC7、ETC2 / EAC、ASTC（LDR）块压缩格式 → RGBA8
+//! - 16 位 UNORM 格式和 EAC R11 / RG11 → RGBA16
+//! - 16 / 32 位浮点格式 → RGBA32F
+//!
+//! 缺少的通道按 Vulkan 采样规则补齐（G、B 为 0，A 为 1）。
+//! BC6H、ASTC HDR 以及 Basis Universal（UASTC / ETC1S）暂不支持，返回错误
+//!
+//! 查看器（CPU 预览、缩略图、GPU 后备数据）和 `ktx2tool` 共用这里的解码器。
+//! 头部中的尺寸和长度都不可信，大小一律用 checked 运算推算
+
+mod astc;
+pub mod bc;
+mod etc;
+
+use std::borrow::Cow;
+use std::io::Read;
+
+use ktx2::{Format, SupercompressionScheme};
+
+/// 解码后的像素，按行排列的 RGBA
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pixels {
+    Rgba8(Vec<u8>),
+    Rgba16(Vec<u16>),
+    Rgba32F(Vec<f32>),
+}
+
+impl Pixels {
+    /// 转换为 RGBA8：16 位数据取高位，浮点数据截断到 [0, 1]（不做色调映射）
+    pub fn into_rgba8(self) -> Vec<u8> {
+        match self {
+            Pixels::Rgba8(rgba) => rgba,
+            Pixels::Rgba16(rgba) => rgba.into_iter().map(|v| ((v as u32 + 128) / 257) as u8).collect(),
+            Pixels::Rgba32F(rgba) => rgba
+                .into_iter()
+                .map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+                .collect(),
+        }
+    }
+}
+
+/// 格式的块尺寸（宽、高）和每块字节数；未压缩格式的块为 1x1
+pub fn block_layout(format: Format) -> Option<(u32, u32, usize)> {
+    if let Some(bytes) = bc::block_bytes(format).or_else(|| etc::block_bytes(format)) {
+        return Some((4, 4, bytes));
+    }
+    if let Some((block_width, block_height)) = astc::block_size(format) {
+        return Some((block_width, block_height, 16));
+    }
+    let (channels, kind) = uncompressed(format)?;
+    Some((1, 1, channels.len() * kind.size()))
+}
+
+/// 单张图像（一个层级中的一个层 / 面 / 深度切片）占用的字节数，溢出时返回 `None`
+pub fn image_size(format: Format, width: u32, height: u32) -> Option<usize> {
+    let (block_width, block_height, block_bytes) = block_layout(format)?;
+    (width.div_ceil(block_width) as usize)
+        .checked_mul(height.div_ceil(block_height) as usize)?
+        .checked_mul(block_bytes)
+}
+
+/// 第 `level` 层级的边长（至少为 1），层级号超过 31 时不会移位溢出
+pub fn level_extent(base: u32, level: u32) -> u32 {
+    base.checked_shr(level).unwrap_or(0).max(1)
+}
+
+/// 由头部推算一个层级的字节数（所有层 / 面 / 深度切片），格式不支持或溢出时返回 `None`
+pub fn level_size(header: &ktx2::Header, level: u32) -> Option<u64> {
+    let width = level_extent(header.pixel_width, level);
+    let height = level_extent(header.pixel_height, level);
+    let depth = level_extent(header.pixel_depth, level);
+    let slices = (header.layer_count.max(1) as u64)
+        .checked_mul(header.face_count.max(1) as u64)?
+        .checked_mul(depth as u64)?;
+    (image_size(header.format?, width, height)? as u64).checked_mul(slices)
+}
+
+/// 按超压缩方案解压一个层级的数据
+///
+/// `expected_length` 是由头部推算的层级大小（见 `level_size`）。层级索引中的
+/// uncompressedByteLength 与之不符时直接报错；解压按实际输出逐步分配，最多 `expected_length` 字节
+pub fn inflate(
+    scheme: Option<SupercompressionScheme>,
+    data: &[u8],
+    uncompressed_length: u64,
+    expected_length: u64,
+) -> Result<Cow<'_, [u8]>, String> {
+    let Some(scheme) = scheme else {
+        return Ok(Cow::Borrowed(data));
+    };
+    if uncompressed_length != expected_length {
+        return Err(format!(
+            "uncompressedByteLength {} does not match the {} bytes implied by the header",
+            uncompressed_length, expected_length
+        ));
+    }
+    let limit =
+        usize::try_from(expected_length).map_err(|_| format!("Level of {} bytes is too large", expected_length))?;
+
+    let inflated = match scheme {
+        SupercompressionScheme::Zstandard => {
+            let failed = |e: &dyn std::fmt::Display| format!("Zstandard decompression failed: {}", e);
+            let decoder = ruzstd::decoding::StreamingDecoder::new(data).map_err(|e| failed(&e))?;
+            // 多读一个字节，输出超过预期时由下面的长度检查报错
+            let mut inflated = Vec::new();
+            decoder
+                .take(expected_length.saturating_add(1))
+                .read_to_end(&mut inflated)
+                .map_err(|e| failed(&e))?;
+            inflated
+        }
+        SupercompressionScheme::ZLIB => miniz_oxide::inflate::decompress_to_vec_zlib_with_limit(data, limit)
+            .map_err(|e| format!("ZLIB decompression failed: {:?}", e.status))?,
+        scheme => return Err(format!("Supercompression scheme {:?} is not supported", scheme)),
+    };
+
+    if inflated.len() as u64 != uncompressed_length {
+        return Err(format!(
+            "Inflated level is {} bytes, expected uncompressedByteLength {}",
+            inflated.len(),
+            uncompressed_length
+        ));
+    }
+    Ok(Cow::Owned(inflated))
+}
+
+/// 把一张图像解码为像素
+pub fn decode(format: Format, data: &[u8], width: u32, height: u32) -> Result<Pixels, String> {
+    let expected = image_size(format, width, height).ok_or_else(|| format!("Unsupported format {:?}", format))?;
+    if data.len() < expected {
+        return Err(format!(
+            "{:?} image of {}x{} needs {} bytes, got {}",
+            format,
+            width,
+            height,
+            expected,
+            data.len()
+        ));
+    }
+    let data = &data[..expected];
+
+    if let Some(pixels) = bc::decode(format, data, width, height) {
+        return Ok(pixels);
+    }
+    if let Some(pixels) = etc::decode(format, data, width, height) {
+        return Ok(pixels);
+    }
+    if let Some(pixels) = astc::decode(format, data, width, height) {
+        return Ok(pixels);
+    }
+
+    let (channels, kind) = uncompressed(format).ok_or_else(|| format!("Unsupported format {:?}", format))?;
+    let texel_bytes = channels.len() * kind.size();
+    let texels = data.chunks_exact(texel_bytes);
+    Ok(match kind {
+        Component::Unorm8 => Pixels::Rgba8(
+            texels
+                .flat_map(|texel| swizzle(channels, texel.iter().copied(), 255))
+                .collect(),
+        ),
+        Component::Unorm16 => Pixels::Rgba16(
+            texels
+                .flat_map(|texel| swizzle(channels, u16_values(texel), u16::MAX))
+                .collect(),
+        ),
+        Component::Float16 => Pixels::Rgba32F(
+            texels
+                .flat_map(|texel| swizzle(channels, u16_values(texel).map(f16_to_f32), 1.0))
+                .collect(),
+        ),
+        Component::Float32 => Pixels::Rgba32F(
+            texels
+                .flat_map(|texel| {
+                    let values = texel
+                        .chunks_exact(4)
+                        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+                    swizzle(channels, values, 1.0)
+                })
+                .collect(),
+        ),
+    })
+}
+
+/// 未压缩格式的分量类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Component {
+    Unorm8,
+    Unorm16,
+    Float16,
+    Float32,
+}
+
+impl Component {
+    fn size(self) -> usize {
+        match self {
+            Component::Unorm8 => 1,
+            Component::Unorm16 | Component::Float16 => 2,
+            Component::Float32 => 4,
+        }
+    }
+}
+
+/// 未压缩格式的通道顺序（0..=3 对应 RGBA）和分量类型
+fn uncompressed(format: Format) -> Option<(&'static [usize], Component)> {
+    const R: &[usize] = &[0];
+    const RG: &[usize] = &[0, 1];
+    const RGB: &[usize] = &[0, 1, 2];
+    const BGR: &[usize] = &[2, 1, 0];
+    const RGBA: &[usize] = &[0, 1, 2, 3];
+    const BGRA: &[usize] = &[2, 1, 0, 3];
+
+    Some(match format {
+        Format::R8_UNORM | Format::R8_SRGB => (R, Component::Unorm8),
+        Format::R8G8_UNORM | Format::R8G8_SRGB => (RG, Component::Unorm8),
+        Format::R8G8B8_UNORM | Format::R8G8B8_SRGB => (RGB, Component::Unorm8),
+        Format::B8G8R8_UNORM | Format::B8G8R8_SRGB => (BGR, Component::Unorm8),
+        Format::R8G8B8A8_UNORM | Format::R8G8B8A8_SRGB => (RGBA, Component::Unorm8),
+        Format::B8G8R8A8_UNORM | Format::B8G8R8A8_SRGB => (BGRA, Component::Unorm8),
+        Format::R16_UNORM => (R, Component::Unorm16),
+        Format::R16G16_UNORM => (RG, Component::Unorm16),
+        Format::R16G16B16_UNORM => (RGB, Component::Unorm16),
+        Format::R16G16B16A16_UNORM => (RGBA, Component::Unorm16),
+        Format::R16_SFLOAT => (R, Component::Float16),
+        Format::R16G16_SFLOAT => (RG, Component::Float16),
+        Format::R16G16B16_SFLOAT => (RGB, Component::Float16),
+        Format::R16G16B16A16_SFLOAT => (RGBA, Component::Float16),
+        Format::R32_SFLOAT => (R, Component::Float32),
+        Format::R32G32_SFLOAT => (RG, Component::Float32),
+        Format::R32G32B32_SFLOAT => (RGB, Component::Float32),
+        Format::R32G32B32A32_SFLOAT => (RGBA, Component::Float32),
+        _ => return None,
+    })
+}
+
+/// 把一个纹素的分量按通道顺序放入 RGBA，缺少的 G、B 为 0，A 为 `one`
+fn swizzle<T: Copy + Default>(channels: &[usize], values: impl Iterator<Item = T>, one: T) -> [T; 4] {
+    let mut rgba = [T::default(), T::default(), T::default(), one];
+    for (&channel, value) in channels.iter().zip(values) {
+        rgba[channel] = value;
+    }
+    rgba
+}
+
+fn u16_values(bytes: &[u8]) -> impl Iterator<Item = u16> + '_ {
+    bytes.chunks_exact(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+/// IEEE 754 半精度转单精度
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+
+    let value = match (exponent, mantissa) {
+        (0, 0) => sign,
+        // 非规格化数
+        (0, _) => {
+            let magnitude = mantissa as f32 / (1 << 24) as f32;
+            return if sign != 0 { -magnitude } else { magnitude };
+        }
+        (0x1f, _) => sign | 0x7f80_0000 | (mantissa << 13),
+        _ => sign | ((exponent + 112) << 23) | (mantissa << 13),
+    };
+    f32::from_bits(value)
+}
+
+/// 逐块解码的公共循环：`decode_block` 把一个块写成 4x4 纹素，超出图像的部分丢弃
+fn decode_blocks<T: Copy + Default>(
+    data: &[u8],
+    block_bytes: usize,
+    width: u32,
+    height: u32,
+    mut decode_block: impl FnMut(&[u8]) -> [[T; 4]; 16],
+) -> Vec<T> {
+    let (width, height) = (width as usize, height as usize);
+    let blocks_x = width.div_ceil(4);
+    let mut out = vec![T::default(); width * height * 4];
+
+    for (index, block) in data.chunks_exact(block_bytes).enumerate() {
+        let (bx, by) = (index % blocks_x * 4, index / blocks_x * 4);
+        let texels = decode_block(block);
+        for (i, texel) in texels.iter().enumerate() {
+            let (x, y) = (bx + i % 4, by + i / 4);
+            if x < width && y < height {
+                let offset = (y * width + x) * 4;
+                out[offset..offset + 4].copy_from_slice(texel);
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 逐个解码参考向量并比较。块和期望像素都按内存顺序写成十六进制（16 位通道为小端），空白只用于对齐
+    pub(super) fn assert_vectors(format: Format, width: u32, height: u32, vectors: &[(&str, &str)]) {
+        for (block, expected) in vectors {
+            let bytes = match decode(format, &from_hex(block), width, height) {
+                Ok(Pixels::Rgba8(rgba)) => rgba,
+                Ok(Pixels::Rgba16(rgba)) => rgba.iter().flat_map(|value| value.to_le_bytes()).collect(),
+                other => panic!("unexpected result for block {block}: {other:?}"),
+            };
+            assert_eq!(bytes, from_hex(expected), "block {block}");
+        }
+    }
+
+    fn from_hex(hex: &str) -> Vec<u8> {
+        let digits: Vec<u8> = hex.bytes().filter(|byte| !byte.is_ascii_whitespace()).collect();
+        digits.chunks(2).map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_uncompressed_swizzle() {
+        let bgra = [1, 2, 3, 4, 5, 6, 7, 8];
+        assert_eq!(
+            decode(Format::B8G8R8A8_UNORM, &bgra, 2, 1),
+            Ok(Pixels::Rgba8(vec![3, 2, 1, 4, 7, 6, 5, 8]))
+        );
+
+        // 半精度 1.0、-2.0，缺少的 B 为 0、A 为 1
+        let rg = [0x00, 0x3c, 0x00, 0xc0];
+        assert_eq!(
+            decode(Format::R16G16_SFLOAT, &rg, 1, 1),
+            Ok(Pixels::Rgba32F(vec![1.0, -2.0, 0.0, 1.0]))
+        );
+        assert_eq!(image_size(Format::BC7_UNORM_BLOCK, 5, 3), Some(32));
+        assert!(decode(Format::R8G8B8A8_UNORM, &[0; 3], 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_inflate_limits() {
+        assert_eq!(image_size(Format::R8G8B8A8_UNORM, u32::MAX, u32::MAX), None);
+        assert_eq!(level_extent(1024, 40), 1);
+
+        // 纯 Rust 解码器解开原生 zstd 库压缩的数据
+        let scheme = Some(SupercompressionScheme::Zstandard);
+        let texels: Vec<u8> = (0..4096u32).map(|i| ((i * 7) ^ (i >> 3)) as u8).collect();
+        for level in [1, 19] {
+            let compressed = zstd::bulk::compress(&texels, level).unwrap();
+            assert_eq!(inflate(scheme, &compressed, 4096, 4096).unwrap().as_ref(), &texels[..]);
+        }
+
+        let level = vec![7u8; 64];
+        let compressed = zstd::bulk::compress(&level, 3).unwrap();
+        assert_eq!(inflate(scheme, &compressed, 64, 64).unwrap().as_ref(), &level[..]);
+        // 伪造的 uncompressedByteLength 不会按其分配内存
+        assert!(inflate(scheme, &compressed, 1 << 46, 64).is_err());
+        assert!(inflate(scheme, &compressed, 32, 32).is_err());
+    }
+}
@@ -0,0 +1,901 @@
+//! ASTC 块解码（每块 16 字节，块尺寸 4x4 到 12x12）
+//!
+//! 按 Khronos Data Format 规范的 LDR 配置解码：块模式、分区哈希、整数序列编码（ISE）、
+//! 端点反量化和权重网格插值。HDR 端点模式、HDR 空块和非法编码都解码为错误色（品红）。
+//! SFLOAT（HDR）格式不在支持范围内
+
+use ktx2::Format;
+
+use super::Pixels;
+
+/// 非法块的颜色
+const ERROR_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+/// 端点颜色可用的量化级数，由小到大
+const COLOR_LEVELS: [u32; 21] = [
+    2, 3, 4, 5, 6, 8, 10, 12, 16, 20, 24, 32, 40, 48, 64, 80, 96, 128, 160, 192, 256,
+];
+
+/// 权重的量化级数，按块模式中的 (H, R) 查表
+const WEIGHT_LEVELS: [[u32; 6]; 2] = [[2, 3, 4, 5, 6, 8], [10, 12, 16, 20, 24, 32]];
+
+/// ASTC 格式的块尺寸（宽、高）
+pub fn block_size(format: Format) -> Option<(u32, u32)> {
+    Some(match format {
+        Format::ASTC_4x4_UNORM_BLOCK | Format::ASTC_4x4_SRGB_BLOCK => (4, 4),
+        Format::ASTC_5x4_UNORM_BLOCK | Format::ASTC_5x4_SRGB_BLOCK => (5, 4),
+        Format::ASTC_5x5_UNORM_BLOCK | Format::ASTC_5x5_SRGB_BLOCK => (5, 5),
+        Format::ASTC_6x5_UNORM_BLOCK | Format::ASTC_6x5_SRGB_BLOCK => (6, 5),
+        Format::ASTC_6x6_UNORM_BLOCK | Format::ASTC_6x6_SRGB_BLOCK => (6, 6),
+        Format::ASTC_8x5_UNORM_BLOCK | Format::ASTC_8x5_SRGB_BLOCK => (8, 5),
+        Format::ASTC_8x6_UNORM_BLOCK | Format::ASTC_8x6_SRGB_BLOCK => (8, 6),
+        Format::ASTC_8x8_UNORM_BLOCK | Format::ASTC_8x8_SRGB_BLOCK => (8, 8),
+        Format::ASTC_10x5_UNORM_BLOCK | Format::ASTC_10x5_SRGB_BLOCK => (10, 5),
+        Format::ASTC_10x6_UNORM_BLOCK | Format::ASTC_10x6_SRGB_BLOCK => (10, 6),
+        Format::ASTC_10x8_UNORM_BLOCK | Format::ASTC_10x8_SRGB_BLOCK => (10, 8),
+        Format::ASTC_10x10_UNORM_BLOCK | Format::ASTC_10x10_SRGB_BLOCK => (10, 10),
+        Format::ASTC_12x10_UNORM_BLOCK | Format::ASTC_12x10_SRGB_BLOCK => (12, 10),
+        Format::ASTC_12x12_UNORM_BLOCK | Format::ASTC_12x12_SRGB_BLOCK => (12, 12),
+        _ => return None,
+    })
+}
+
+/// 解码 ASTC 格式图像，不是 ASTC LDR 格式时返回 `None`
+pub fn decode(format: Format, data: &[u8], width: u32, height: u32) -> Option<Pixels> {
+    let (block_width, block_height) = block_size(format)?;
+    let srgb = matches!(
+        format,
+        Format::ASTC_4x4_SRGB_BLOCK
+            | Format::ASTC_5x4_SRGB_BLOCK
+            | Format::ASTC_5x5_SRGB_BLOCK
+            | Format::ASTC_6x5_SRGB_BLOCK
+            | Format::ASTC_6x6_SRGB_BLOCK
+            | Format::ASTC_8x5_SRGB_BLOCK
+            | Format::ASTC_8x6_SRGB_BLOCK
+            | Format::ASTC_8x8_SRGB_BLOCK
+            | Format::ASTC_10x5_SRGB_BLOCK
+            | Format::ASTC_10x6_SRGB_BLOCK
+            | Format::ASTC_10x8_SRGB_BLOCK
+            | Format::ASTC_10x10_SRGB_BLOCK
+            | Format::ASTC_12x10_SRGB_BLOCK
+            | Format::ASTC_12x12_SRGB_BLOCK
+    );
+
+    let (width, height) = (width as usize, height as usize);
+    let (block_width, block_height) = (block_width as usize, block_height as usize);
+    let blocks_x = width.div_ceil(block_width);
+    let mut out = vec![0; width * height * 4];
+    let mut texels = vec![[0; 4]; block_width * block_height];
+
+    for (index, block) in data.chunks_exact(16).enumerate() {
+        let bits = u128::from_le_bytes(block.try_into().unwrap_or_default());
+        let footprint = Footprint {
+            width: block_width,
+            height: block_height,
+        };
+        if decode_block(bits, footprint, srgb, &mut texels).is_none() {
+            texels.fill(ERROR_COLOR);
+        }
+        let (bx, by) = (index % blocks_x * block_width, index / blocks_x * block_height);
+        for (i, texel) in texels.iter().enumerate() {
+            let (x, y) = (bx + i % block_width, by + i / block_width);
+            if x < width && y < height {
+                let offset = (y * width + x) * 4;
+                out[offset..offset + 4].copy_from_slice(texel);
+            }
+        }
+    }
+    Some(Pixels::Rgba8(out))
+}
+
+/// 块覆盖的纹素范围
+#[derive(Debug, Clone, Copy)]
+struct Footprint {
+    width: usize,
+    height: usize,
+}
+
+/// 块模式字段描述的权重网格
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BlockMode {
+    grid_width: usize,
+    grid_height: usize,
+    dual_plane: bool,
+    weight_levels: u32,
+}
+
+/// 解码一个块，非法编码返回 `None`
+fn decode_block(bits: u128, footprint: Footprint, srgb: bool, texels: &mut [[u8; 4]]) -> Option<()> {
+    let mode = read(bits, 0, 11);
+    if mode & 0x1ff == 0x1fc {
+        texels.fill(void_extent(bits)?);
+        return Some(());
+    }
+
+    let mode = block_mode(mode)?;
+    let grid_size = mode.grid_width * mode.grid_height;
+    let weight_count = grid_size * if mode.dual_plane { 2 } else { 1 };
+    if mode.grid_width > footprint.width || mode.grid_height > footprint.height || weight_count > 64 {
+        return None;
+    }
+    let weight_bits = ise_bit_count(weight_count, mode.weight_levels);
+    if !(24..=96).contains(&weight_bits) {
+        return None;
+    }
+
+    let partitions = read(bits, 11, 2) as usize + 1;
+    if mode.dual_plane && partitions == 4 {
+        return None;
+    }
+
+    // 端点模式：单分区时 4 位；多分区时 6 位，不同模式的额外位放在权重数据下方
+    let mut below_weights = 128 - weight_bits;
+    let (seed, color_start, modes) = if partitions == 1 {
+        (0, 17, vec![read(bits, 13, 4)])
+    } else {
+        let field = read(bits, 23, 6);
+        let modes = if field & 3 == 0 {
+            vec![field >> 2; partitions]
+        } else {
+            let extra = 3 * partitions as u32 - 4;
+            below_weights = below_weights.checked_sub(extra)?;
+            let field = field | (read(bits, below_weights, extra) << 6);
+            let base = (field & 3) - 1;
+            (0..partitions)
+                .map(|i| {
+                    let class = base + ((field >> (2 + i)) & 1);
+                    let low = (field >> (2 + partitions + 2 * i)) & 3;
+                    (class << 2) | low
+                })
+                .collect()
+        };
+        (read(bits, 13, 10), 29, modes)
+    };
+    if modes.iter().any(|&mode| is_hdr(mode)) {
+        return None;
+    }
+
+    let plane_channel = if mode.dual_plane {
+        below_weights = below_weights.checked_sub(2)?;
+        Some(read(bits, below_weights, 2) as usize)
+    } else {
+        None
+    };
+
+    // 端点数据取能放下的最高量化级数
+    let value_count: usize = modes.iter().map(|&mode| ((mode >> 2) as usize + 1) * 2).sum();
+    let color_bits = below_weights.checked_sub(color_start)?;
+    if value_count > 18 {
+        return None;
+    }
+    let color_levels = COLOR_LEVELS
+        .iter()
+        .copied()
+        .rfind(|&levels| ise_bit_count(value_count, levels) <= color_bits)
+        .filter(|&levels| levels >= 6)?;
+    let values: Vec<u32> = decode_ise(bits, color_start, value_count, color_levels)
+        .into_iter()
+        .map(|value| unquantize_color(value, color_levels))
+        .collect();
+
+    let mut endpoints = Vec::with_capacity(partitions);
+    let mut offset = 0;
+    for &mode in &modes {
+        let count = ((mode >> 2) as usize + 1) * 2;
+        endpoints.push(decode_endpoints(mode, &values[offset..offset + count]));
+        offset += count;
+    }
+
+    // 权重从块的最高位开始倒序存储
+    let weights: Vec<u32> = decode_ise(bits.reverse_bits(), 0, weight_count, mode.weight_levels)
+        .into_iter()
+        .map(|value| unquantize_weight(value, mode.weight_levels))
+        .collect();
+    let planes = if mode.dual_plane { 2 } else { 1 };
+    let plane_weights: Vec<Vec<u32>> = (0..planes)
+        .map(|plane| {
+            let grid: Vec<u32> = weights.iter().skip(plane).step_by(planes).copied().collect();
+            infill_weights(&grid, mode, footprint)
+        })
+        .collect();
+
+    let small_block = footprint.width * footprint.height < 31;
+    for (i, texel) in texels.iter_mut().enumerate() {
+        let (x, y) = (i % footprint.width, i / footprint.width);
+        let partition = if partitions > 1 {
+            select_partition(seed, x as u32, y as u32, partitions as u32, small_block)
+        } else {
+            0
+        };
+        let (low, high) = endpoints[partition];
+        *texel = std::array::from_fn(|channel| {
+            let plane = if plane_channel == Some(channel) { 1 } else { 0 };
+            let weight = plane_weights[plane][i];
+            // 8 位端点扩展为 16 位后插值，sRGB 端点在低位补 0x80
+            let expand = |value: u8| {
+                if srgb {
+                    ((value as u32) << 8) | 0x80
+                } else {
+                    value as u32 * 257
+                }
+            };
+            let value = (expand(low[channel]) * (64 - weight) + expand(high[channel]) * weight + 32) >> 6;
+            (value >> 8) as u8
+        });
+    }
+    Some(())
+}
+
+/// 空块（整块单色）的颜色；HDR 空块和坐标范围非法的块返回 `None`
+fn void_extent(bits: u128) -> Option<[u8; 4]> {
+    if read(bits, 9, 1) != 0 {
+        return None;
+    }
+    let coords: [u32; 4] = std::array::from_fn(|i| read(bits, 12 + 13 * i as u32, 13));
+    if coords != [0x1fff; 4] && (coords[0] >= coords[1] || coords[2] >= coords[3]) {
+        return None;
+    }
+    Some(std::array::from_fn(|i| (read(bits, 64 + 16 * i as u32, 16) >> 8) as u8))
+}
+
+/// 解析 11 位块模式，保留的编码返回 `None`
+fn block_mode(mode: u32) -> Option<BlockMode> {
+    let bit = |index: u32| (mode >> index) & 1;
+    let a = (mode >> 5) & 3;
+    let (range, grid_width, grid_height, high, dual_plane);
+
+    if mode & 3 != 0 {
+        range = ((mode & 3) << 1) | bit(4);
+        let b = (mode >> 7) & 3;
+        (grid_width, grid_height) = match (mode >> 2) & 3 {
+            0 => (b + 4, a + 2),
+            1 => (b + 8, a + 2),
+            2 => (a + 2, b + 8),
+            _ if bit(8) == 0 => (a + 2, (b & 1) + 6),
+            _ => ((b & 1) + 2, a + 2),
+        };
+        high = bit(9);
+        dual_plane = bit(10) == 1;
+    } else {
+        range = (((mode >> 2) & 3) << 1) | bit(4);
+        if mode & 0xf == 0 {
+            return None;
+        }
+        (grid_width, grid_height) = match (mode >> 7) & 3 {
+            0 => (12, a + 2),
+            1 => (a + 2, 12),
+            2 => (a + 6, ((mode >> 9) & 3) + 6),
+            _ => match a {
+                0 => (6, 10),
+                1 => (10, 6),
+                _ => return None,
+            },
+        };
+        // 宽高都由 A、B 决定的模式没有 D、H 位
+        if (mode >> 7) & 3 == 2 {
+            (high, dual_plane) = (0, false);
+        } else {
+            (high, dual_plane) = (bit(9), bit(10) == 1);
+        }
+    }
+
+    Some(BlockMode {
+        grid_width: grid_width as usize,
+        grid_height: grid_height as usize,
+        dual_plane,
+        weight_levels: WEIGHT_LEVELS[high as usize][range.checked_sub(2)? as usize],
+    })
+}
+
+/// HDR 端点模式（LDR 配置下非法）
+fn is_hdr(mode: u32) -> bool {
+    matches!(mode, 2 | 3 | 7 | 11 | 14 | 15)
+}
+
+/// 从 `start` 开始读取 `count` 位，超出 128 位的部分为 0
+fn read(bits: u128, start: u32, count: u32) -> u32 {
+    if start >= 128 {
+        return 0;
+    }
+    ((bits >> start) & ((1u128 << count) - 1)) as u32
+}
+
+/// 量化级数对应的 ISE 编码：(每个值的低位位数, 是否有三进制位, 是否有五进制位)
+fn ise_encoding(levels: u32) -> (u32, bool, bool) {
+    if levels.is_power_of_two() {
+        (levels.trailing_zeros(), false, false)
+    } else if levels.is_multiple_of(3) {
+        ((levels / 3).trailing_zeros(), true, false)
+    } else {
+        ((levels / 5).trailing_zeros(), false, true)
+    }
+}
+
+/// `count` 个值按 ISE 编码占用的位数
+fn ise_bit_count(count: usize, levels: u32) -> u32 {
+    let count = count as u32;
+    match ise_encoding(levels) {
+        (bits, true, _) => bits * count + (8 * count).div_ceil(5),
+        (bits, _, true) => bits * count + (7 * count).div_ceil(3),
+        (bits, ..) => bits * count,
+    }
+}
+
+/// ISE 解码出的一个值：三进制 / 五进制部分和低位
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IseValue {
+    high: u32,
+    low: u32,
+}
+
+/// 从 `start` 开始解码 `count` 个 ISE 值，序列之后的位按 0 处理
+fn decode_ise(bits: u128, start: u32, count: usize, levels: u32) -> Vec<IseValue> {
+    let end = start + ise_bit_count(count, levels);
+    let mut position = start;
+    let mut take = |width: u32| {
+        let value = read(bits, position, width.min(end.saturating_sub(position)));
+        position += width;
+        value
+    };
+
+    let (low_bits, trits, quints) = ise_encoding(levels);
+    let mut values = Vec::with_capacity(count + 4);
+    while values.len() < count {
+        if trits {
+            // 5 个值共享 8 位：低位和三进制位交错存放
+            let mut lows = [0; 5];
+            let mut packed = 0;
+            for (i, shift, width) in [(0, 0, 2), (1, 2, 2), (2, 4, 1), (3, 5, 2), (4, 7, 1)] {
+                lows[i] = take(low_bits);
+                packed |= take(width) << shift;
+            }
+            let highs = unpack_trits(packed);
+            values.extend((0..5).map(|i| IseValue {
+                high: highs[i],
+                low: lows[i],
+            }));
+        } else if quints {
+            // 3 个值共享 7 位
+            let mut lows = [0; 3];
+            let mut packed = 0;
+            for (i, shift, width) in [(0, 0, 3), (1, 3, 2), (2, 5, 2)] {
+                lows[i] = take(low_bits);
+                packed |= take(width) << shift;
+            }
+            let highs = unpack_quints(packed);
+            values.extend((0..3).map(|i| IseValue {
+                high: highs[i],
+                low: lows[i],
+            }));
+        } else {
+            values.push(IseValue {
+                high: 0,
+                low: take(low_bits),
+            });
+        }
+    }
+    values.truncate(count);
+    values
+}
+
+/// 8 位打包值展开为 5 个三进制位
+fn unpack_trits(t: u32) -> [u32; 5] {
+    let bit = |value: u32, index: u32| (value >> index) & 1;
+    let (c, t3, t4);
+    if (t >> 2) & 7 == 7 {
+        c = (((t >> 5) & 7) << 2) | (t & 3);
+        (t4, t3) = (2, 2);
+    } else {
+        c = t & 0x1f;
+        if (t >> 5) & 3 == 3 {
+            (t4, t3) = (2, bit(t, 7));
+        } else {
+            (t4, t3) = (bit(t, 7), (t >> 5) & 3);
+        }
+    }
+    let (t0, t1, t2);
+    if c & 3 == 3 {
+        t2 = 2;
+        t1 = bit(c, 4);
+        t0 = (bit(c, 3) << 1) | (bit(c, 2) & !bit(c, 3) & 1);
+    } else if (c >> 2) & 3 == 3 {
+        (t2, t1, t0) = (2, 2, c & 3);
+    } else {
+        t2 = bit(c, 4);
+        t1 = (c >> 2) & 3;
+        t0 = (bit(c, 1) << 1) | (bit(c, 0) & !bit(c, 1) & 1);
+    }
+    [t0, t1, t2, t3, t4]
+}
+
+/// 7 位打包值展开为 3 个五进制位
+fn unpack_quints(q: u32) -> [u32; 3] {
+    let bit = |value: u32, index: u32| (value >> index) & 1;
+    if (q >> 1) & 3 == 3 && (q >> 5) & 3 == 0 {
+        let q2 = (bit(q, 0) << 2) | ((bit(q, 4) & !bit(q, 0) & 1) << 1) | (bit(q, 3) & !bit(q, 0) & 1);
+        return [4, 4, q2];
+    }
+    let (q2, c);
+    if (q >> 1) & 3 == 3 {
+        q2 = 4;
+        c = (((q >> 3) & 3) << 3) | ((!(q >> 5) & 3) << 1) | bit(q, 0);
+    } else {
+        q2 = (q >> 5) & 3;
+        c = q & 0x1f;
+    }
+    let (q0, q1) = if c & 7 == 5 {
+        ((c >> 3) & 3, 4)
+    } else {
+        (c & 7, (c >> 3) & 3)
+    };
+    [q0, q1, q2]
+}
+
+/// 把 `value` 的 `bits` 位重复填满 `width` 位
+fn replicate(value: u32, bits: u32, width: u32) -> u32 {
+    if bits == 0 {
+        return 0;
+    }
+    let mut result = 0;
+    let mut filled = 0;
+    while filled < width {
+        result = (result << bits) | value;
+        filled += bits;
+    }
+    result >> (filled - width)
+}
+
+/// 端点值反量化到 0–255
+fn unquantize_color(value: IseValue, levels: u32) -> u32 {
+    let (bits, trits, _) = ise_encoding(levels);
+    if levels.is_power_of_two() {
+        return replicate(value.low, bits, 8);
+    }
+    let a = if value.low & 1 != 0 { 0x1ff } else { 0 };
+    let x = value.low >> 1;
+    let (b, c) = match (trits, bits) {
+        (true, 1) => (0, 204),
+        (true, 2) => (x * 0x116, 93),
+        (true, 3) => (x * 0x85, 44),
+        (true, 4) => ((x << 6) | x, 22),
+        (true, 5) => ((x << 5) | (x >> 2), 11),
+        (true, _) => ((x << 4) | (x >> 4), 5),
+        (false, 1) => (0, 113),
+        (false, 2) => (x * 0x10c, 54),
+        (false, 3) => ((x << 7) | (x << 1) | (x >> 1), 26),
+        (false, 4) => ((x << 6) | (x >> 1), 13),
+        (false, _) => ((x << 5) | (x >> 3), 6),
+    };
+    let t = (value.high * c + b) ^ a;
+    (a & 0x80) | (t >> 2)
+}
+
+/// 权重反量化到 0–64
+fn unquantize_weight(value: IseValue, levels: u32) -> u32 {
+    let (bits, trits, _) = ise_encoding(levels);
+    let weight = if levels.is_power_of_two() {
+        replicate(value.low, bits, 6)
+    } else if bits == 0 {
+        let table: &[u32] = if trits { &[0, 32, 63] } else { &[0, 16, 32, 47, 63] };
+        table[value.high as usize]
+    } else {
+        let a = if value.low & 1 != 0 { 0x7f } else { 0 };
+        let x = value.low >> 1;
+        let (b, c) = match (trits, bits) {
+            (true, 1) => (0, 50),
+            (true, 2) => (x * 0x45, 23),
+            (true, _) => (x * 0x21, 11),
+            (false, 1) => (0, 28),
+            (false, _) => (x * 0x42, 13),
+        };
+        let t = (value.high * c + b) ^ a;
+        (a & 0x20) | (t >> 2)
+    };
+    if weight > 32 { weight + 1 } else { weight }
+}
+
+/// 端点值解码为一对 RGBA8 端点
+fn decode_endpoints(mode: u32, v: &[u32]) -> ([u8; 4], [u8; 4]) {
+    let v: Vec<i32> = v.iter().map(|&value| value as i32).collect();
+    let rgba = |r: i32, g: i32, b: i32, a: i32| [r, g, b, a].map(|c| c.clamp(0, 255) as u8);
+    // 蓝色收缩：R、G 向 B 靠拢，换取更高精度
+    let blue_contract = |r: i32, g: i32, b: i32, a: i32| rgba((r + b) >> 1, (g + b) >> 1, b, a);
+    // 偏移值的最高位移到基准值上，偏移值变为 6 位有符号数
+    let transfer = |offset: i32, base: i32| {
+        let base = (base >> 1) | (offset & 0x80);
+        let offset = (offset >> 1) & 0x3f;
+        let offset = if offset & 0x20 != 0 { offset - 0x40 } else { offset };
+        (offset, base)
+    };
+
+    match mode {
+        0 => (rgba(v[0], v[0], v[0], 255), rgba(v[1], v[1], v[1], 255)),
+        1 => {
+            let low = (v[0] >> 2) | (v[1] & 0xc0);
+            let high = (low + (v[1] & 0x3f)).min(255);
+            (rgba(low, low, low, 255), rgba(high, high, high, 255))
+        }
+        4 => (rgba(v[0], v[0], v[0], v[2]), rgba(v[1], v[1], v[1], v[3])),
+        5 => {
+            let (d0, l0) = transfer(v[1], v[0]);
+            let (d1, a0) = transfer(v[3], v[2]);
+            (rgba(l0, l0, l0, a0), rgba(l0 + d0, l0 + d0, l0 + d0, a0 + d1))
+        }
+        6 | 10 => {
+            let (a0, a1) = if mode == 10 { (v[4], v[5]) } else { (255, 255) };
+            let scale = |c: i32| (c * v[3]) >> 8;
+            (
+                rgba(scale(v[0]), scale(v[1]), scale(v[2]), a0),
+                rgba(v[0], v[1], v[2], a1),
+            )
+        }
+        8 | 12 => {
+            let (a0, a1) = if mode == 12 { (v[6], v[7]) } else { (255, 255) };
+            if v[1] + v[3] + v[5] >= v[0] + v[2] + v[4] {
+                (rgba(v[0], v[2], v[4], a0), rgba(v[1], v[3], v[5], a1))
+            } else {
+                (blue_contract(v[1], v[3], v[5], a1), blue_contract(v[0], v[2], v[4], a0))
+            }
+        }
+        _ => {
+            // 9 和 13：基准值加偏移
+            let (d0, r) = transfer(v[1], v[0]);
+            let (d1, g) = transfer(v[3], v[2]);
+            let (d2, b) = transfer(v[5], v[4]);
+            let (d3, a) = if mode == 13 { transfer(v[7], v[6]) } else { (0, 255) };
+            if d0 + d1 + d2 >= 0 {
+                (rgba(r, g, b, a), rgba(r + d0, g + d1, b + d2, a + d3))
+            } else {
+                (blue_contract(r + d0, g + d1, b + d2, a + d3), blue_contract(r, g, b, a))
+            }
+        }
+    }
+}
+
+/// 把权重网格双线性插值到块中的每个纹素
+fn infill_weights(grid: &[u32], mode: BlockMode, footprint: Footprint) -> Vec<u32> {
+    let (grid_width, grid_height) = (mode.grid_width, mode.grid_height);
+    let scale = |size: usize| (1024 + size / 2) / (size - 1);
+    let (ds, dt) = (scale(footprint.width), scale(footprint.height));
+    let at = |index: usize| grid.get(index).copied().unwrap_or(0);
+
+    (0..footprint.width * footprint.height)
+        .map(|i| {
+            let (s, t) = (i % footprint.width, i / footprint.width);
+            let gs = (ds * s * (grid_width - 1) + 32) >> 6;
+            let gt = (dt * t * (grid_height - 1) + 32) >> 6;
+            let (js, fs) = (gs >> 4, (gs & 0xf) as u32);
+            let (jt, ft) = (gt >> 4, (gt & 0xf) as u32);
+            let v0 = js + jt * grid_width;
+
+            let w11 = (fs * ft + 8) >> 4;
+            let w10 = ft - w11;
+            let w01 = fs - w11;
+            let w00 = 16 + w11 - fs - ft;
+            (at(v0) * w00 + at(v0 + 1) * w01 + at(v0 + grid_width) * w10 + at(v0 + grid_width + 1) * w11 + 8) >> 4
+        })
+        .collect()
+}
+
+/// 按分区哈希计算纹素所属的分区
+fn select_partition(seed: u32, x: u32, y: u32, partitions: u32, small_block: bool) -> usize {
+    let (x, y) = if small_block { (x << 1, y << 1) } else { (x, y) };
+    let seed = seed + (partitions - 1) * 1024;
+    let rnum = hash52(seed);
+
+    let mut seeds: [u32; 12] = std::array::from_fn(|i| match i {
+        0..8 => (rnum >> (4 * i)) & 0xf,
+        8..11 => (rnum >> (18 + 4 * (i - 8))) & 0xf,
+        _ => rnum.rotate_left(2) & 0xf,
+    });
+    for value in &mut seeds {
+        *value *= *value;
+    }
+
+    let (sh1, sh2) = if seed & 1 != 0 {
+        (if seed & 2 != 0 { 4 } else { 5 }, if partitions == 3 { 6 } else { 5 })
+    } else {
+        (if partitions == 3 { 6 } else { 5 }, if seed & 2 != 0 { 4 } else { 5 })
+    };
+    let sh3 = if seed & 0x10 != 0 { sh1 } else { sh2 };
+    for (i, value) in seeds.iter_mut().enumerate() {
+        *value >>= match i {
+            0..8 if i % 2 == 0 => sh1,
+            0..8 => sh2,
+            _ => sh3,
+        };
+    }
+
+    // 2D 块的 z 为 0，seeds[8..12] 不参与计算
+    let a = (seeds[0] * x + seeds[1] * y + (rnum >> 14)) & 0x3f;
+    let b = (seeds[2] * x + seeds[3] * y + (rnum >> 10)) & 0x3f;
+    let c = if partitions >= 3 {
+        (seeds[4] * x + seeds[5] * y + (rnum >> 6)) & 0x3f
+    } else {
+        0
+    };
+    let d = if partitions >= 4 {
+        (seeds[6] * x + seeds[7] * y + (rnum >> 2)) & 0x3f
+    } else {
+        0
+    };
+
+    if a >= b && a >= c && a >= d {
+        0
+    } else if b >= c && b >= d {
+        1
+    } else if c >= d {
+        2
+    } else {
+        3
+    }
+}
+
+/// 分区选择使用的整数哈希
+fn hash52(mut p: u32) -> u32 {
+    p ^= p >> 15;
+    p = p.wrapping_sub(p << 17);
+    p = p.wrapping_add(p << 7);
+    p = p.wrapping_add(p << 4);
+    p ^= p >> 5;
+    p = p.wrapping_add(p << 16);
+    p ^= p >> 7;
+    p ^= p >> 3;
+    p ^= p << 6;
+    p ^= p >> 17;
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::assert_vectors;
+
+    #[test]
+    fn test_decode_blocks() {
+        // 三分区块，期望值由 Mesa 22.3.6（llvmpipe）解码得到
+        let block = 0xb4a5059c930910d6b5d4c0c56c765203u128.to_le_bytes();
+        let Some(Pixels::Rgba8(rgba)) = decode(Format::ASTC_4x4_UNORM_BLOCK, &block, 4, 4) else {
+            panic!("not an ASTC format");
+        };
+        let expected: [[u8; 3]; 16] = [
+            [74, 173, 165],
+            [81, 187, 180],
+            [88, 137, 7],
+            [41, 65, 3],
+            [65, 102, 5],
+            [85, 133, 7],
+            [85, 133, 7],
+            [43, 69, 3],
+            [67, 155, 148],
+            [78, 180, 173],
+            [78, 180, 173],
+            [62, 145, 139],
+            [63, 147, 141],
+            [77, 178, 170],
+            [77, 178, 170],
+            [63, 147, 141],
+        ];
+        for (texel, rgb) in rgba.chunks_exact(4).zip(expected) {
+            assert_eq!(texel, [rgb[0], rgb[1], rgb[2], 255]);
+        }
+
+        // 空块：坐标全 1，颜色为 16 位 RGBA
+        let void = (0xffff_0000_8000_ff00u128 << 64) | (u64::MAX as u128 & !0xfff) | 0xdfc;
+        let Some(Pixels::Rgba8(rgba)) = decode(Format::ASTC_6x6_UNORM_BLOCK, &void.to_le_bytes(), 5, 3) else {
+            panic!("not an ASTC format");
+        };
+        assert_eq!(rgba.len(), 5 * 3 * 4);
+        assert!(rgba.chunks_exact(4).all(|texel| texel == [255, 128, 0, 255]));
+
+        // 保留的块模式解码为错误色
+        let Some(Pixels::Rgba8(rgba)) = decode(Format::ASTC_4x4_SRGB_BLOCK, &[0; 16], 4, 4) else {
+            panic!("not an ASTC format");
+        };
+        assert_eq!(&rgba[..4], ERROR_COLOR);
+    }
+
+    #[test]
+    fn test_reference_vectors() {
+        // 期望值由 Mesa 22.3.6（llvmpipe）解码得到：glCompressedTexImage2D 上传单个块，glGetTexImage 读回
+        assert_vectors(
+            Format::ASTC_4x4_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // 端点模式 0
+                (
+                    "4f03bc9c0c96ec0a77b0be971be19ac8",
+                    "515151ff 555555ff 5a5a5aff 5d5d5dff \
+                     585858ff 565656ff 545454ff 525252ff \
+                     5d5d5dff 585858ff 535353ff 4f4f4fff \
+                     535353ff 555555ff 575757ff 585858ff",
+                ),
+                // 端点模式 1
+                (
+                    "11229ab547963ba0c7c5b3d825b5a298",
+                    "fdfdfdff ffffffff ffffffff f6f6f6ff \
+                     fbfbfbff fefefeff fcfcfcff f9f9f9ff \
+                     f9f9f9ff fcfcfcff f9f9f9ff fcfcfcff \
+                     f7f7f7ff fafafaff f7f7f7ff ffffffff",
+                ),
+                // 端点模式 4
+                (
+                    "cf83a6bf5c3a0916c90e12efec54c537",
+                    "b1b1b14f 9b9b9b64 a6a6a659 d3d3d32e \
+                     bcbcbc44 97979767 9090906e a2a2a25d \
+                     77777786 75757588 7a7a7a83 8b8b8b73 \
+                     c5c5c53c 7e7e7e80 75757588 a2a2a25d",
+                ),
+                // 端点模式 5
+                (
+                    "03a2fe926e077818bf2ea0704de69a37",
+                    "3636365b 2e2e2e5b 3434345b 2b2b2b5c \
+                     3636365b 3232325b 3030305b 2c2c2c5c \
+                     3636365b 3535355b 2b2b2b5c 2c2c2c5c \
+                     3535355b 3838385b 2727275c 2d2d2d5b",
+                ),
+                // 端点模式 6
+                (
+                    "42c0221fca34e385e41cac17a49f285b",
+                    "7e0d57ff 7e0d57ff 6a0b49ff 910f65ff \
+                     57093cff 6a0b49ff 6a0b49ff 57093cff \
+                     6a0b49ff 7e0d57ff 910f65ff 910f65ff \
+                     6a0b49ff 6a0b49ff 7e0d57ff 57093cff",
+                ),
+                // 端点模式 8
+                (
+                    "cf01bf26d85980dd042d37a6c80a44fb",
+                    "80bdb1ff 80bdb1ff 7bb4acff 70a1a0ff \
+                     4f677dff 455573ff 597888ff 8fd6c0ff \
+                     404d6eff 4a5e78ff 4f677dff 4f677dff \
+                     80bdb1ff 699498ff 557184ff 404d6eff",
+                ),
+                // 端点模式 9
+                (
+                    "51228de10b7eb2114e0b23ee644fe85a",
+                    "e00f6eff dc1e70ff de156eff e10a6dff \
+                     e3046cff e2076dff dd186fff dd1b6fff \
+                     dd1b6fff dd186fff de156eff e3046cff \
+                     e3046cff db2170ff e10a6dff df136eff",
+                ),
+                // 端点模式 10
+                (
+                    "334223c5bfc3d08e4dd962f0bbf04b0f",
+                    "66a09e9b 7abfbcaf 72b2afa6 91e2dfc7 \
+                     66a09e9b 66a09e9b 87d3d0bd 54838287 \
+                     5c918f90 69a4a29e 7abfbcaf 3d605f6f \
+                     476f6e7a 83cccab8 476f6e7a 446b6a77",
+                ),
+                // 端点模式 12
+                (
+                    "3282e9853c0bb0336e5ff6401019a962",
+                    "26452a9f 264427a2 264427a2 24483895 \
+                     26452a9f 26452b9e 26452b9e 24483895 \
+                     2546309b 26452a9f 26462c9e 2547319a \
+                     24493b93 264424a3 26452a9f 274322a5",
+                ),
+                // 端点模式 13
+                (
+                    "cea365ac804c1f0dc9da465e2e0935ed",
+                    "3c056d98 4007729a 4309759c 4409769c \
+                     4108729a 36026695 36026695 3f07719a \
+                     38036896 3a046b97 3d066e99 4108729a \
+                     3c056d98 37026796 36026695 3a046b97",
+                ),
+                // 权重用 trit 编码
+                (
+                    "af018eab12ace258aad73b4aa1168d63",
+                    "c7c7c7ff b9b9b9ff a4a4a4ff 828282ff \
+                     797979ff a4a4a4ff a4a4a4ff 828282ff \
+                     797979ff 929292ff 8e8e8eff 7a7a7aff \
+                     c7c7c7ff 797979ff 5c5c5cff 6a6a6aff",
+                ),
+                // 权重用 quint 编码
+                (
+                    "4182ba9c518c87a00b43599f77a9f971",
+                    "69696939 5d5d5d28 9d9d9d81 a9a9a992 \
+                     8282825c 69696939 9d9d9d81 8282825c \
+                     b5b5b5a4 a9a9a992 8282825c a9a9a992 \
+                     5d5d5d28 9d9d9d81 b5b5b5a4 5d5d5d28",
+                ),
+                // 双权重平面
+                (
+                    "1144159de3f7948c4afcb66e0babe377",
+                    "8acef146 66cef146 66cef146 8a62734a \
+                     7fcef146 66bdde46 71bdde46 73738749 \
+                     71cef146 66a9c647 7fa9c647 58879e48 \
+                     66cef146 6698b248 8a98b248 4298b248",
+                ),
+                // 2 个分区
+                (
+                    "de8b792a87eb3286d4a979a3be0cd7f1",
+                    "ededed54 f0f0f057 efefef56 eaeaea51 \
+                     ececec53 f0f0f056 f1f1f158 f2f2f258 \
+                     ededed54 eeeeee55 f0f0f056 f1f1f157 \
+                     eaeaea52 ebebeb52 ebebeb52 ebebeb52",
+                ),
+                // 3 个分区
+                (
+                    "4350f29c347516265afe14a36c709f84",
+                    "45454590 343434a2 292929ad 7b7b7bc9 \
+                     7f7f7fc9 838383ca 909090ca 838383ca \
+                     292929ad 2e2e2ea8 3a3a3a9b 45454590 \
+                     45454590 45454590 2e2e2ea8 45454590",
+                ),
+                // 4 个分区
+                (
+                    "df3b02c0ea48b8fdee78296022da7dde",
+                    "f3f3f3ff f1f1f1ff 9e9e9eff 5a5a5aff \
+                     f2f2f2ff edededff ebebebff eaeaeaff \
+                     b5b5b5ff 7c7c7cff 6c6c6cff 969696ff \
+                     a5a5a5ff 878787ff 464646ff 676767ff",
+                ),
+                // 保留的块模式：错误色
+                (
+                    "0098adb556104f5ad14e7bb2502f7b78",
+                    "ff00ffff ff00ffff ff00ffff ff00ffff \
+                     ff00ffff ff00ffff ff00ffff ff00ffff \
+                     ff00ffff ff00ffff ff00ffff ff00ffff \
+                     ff00ffff ff00ffff ff00ffff ff00ffff",
+                ),
+                // 空块：常量颜色
+                (
+                    "fcfdffffffffffff457c769f39d86441",
+                    "7c9fd841 7c9fd841 7c9fd841 7c9fd841 \
+                     7c9fd841 7c9fd841 7c9fd841 7c9fd841 \
+                     7c9fd841 7c9fd841 7c9fd841 7c9fd841 \
+                     7c9fd841 7c9fd841 7c9fd841 7c9fd841",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::ASTC_6x6_UNORM_BLOCK,
+            6,
+            6,
+            &[
+                // 权重网格小于块尺寸，权重双线性插值
+                (
+                    "61244ae77fa4b2c4c916931c4b6856f1",
+                    "ffffffff ffffffff fbfbfbff eeeeeeff e9e9f2ff e9e9ffff \
+                     eeeeffff eeeeffff ececfeff ebebfdff f0f0f6ff fbfbeeff \
+                     e9e9ffff f2f2f7ff f7f7f2ff f7f7f2ff f4f4efff f2f2e9ff \
+                     e9e9ffff f2f2f2ff f9f9e9ff fefee9ff fafaecff f2f2f2ff \
+                     e9e9fbff e9e9f3ff eeeeeeff fbfbeeff fefef3ff fbfbfbff \
+                     e9e9e9ff e9e9f7ff eeeeffff fbfbffff f7f7f7ff e9e9e9ff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::ASTC_8x5_UNORM_BLOCK,
+            8,
+            5,
+            &[
+                // 权重网格小于块尺寸，权重双线性插值
+                (
+                    "d1216b68d090a4895e7724289861907a",
+                    "414e69ff 414e69ff 3f3a54ff 40415cff 414e69ff 414a65ff 40415cff 414e69ff \
+                     3f3852ff 3f3852ff 3f334dff 40415cff 404964ff 403f5aff 3f344fff 3f3852ff \
+                     3f3852ff 3f3852ff 403d58ff 40425dff 40405bff 3f3650ff 3f314bff 3f314bff \
+                     403f5aff 403f5aff 40445fff 40405bff 3f3751ff 3f324cff 3f334eff 3f314bff \
+                     403f5aff 403f5aff 3f3550ff 3f314bff 3f314bff 3f344fff 403d58ff 3f314bff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::ASTC_4x4_SRGB_BLOCK,
+            4,
+            4,
+            &[
+                // 端点模式 12，sRGB 格式输出编码值，不转成线性
+                (
+                    "3282e9853c0bb0336e5ff6401019a962",
+                    "26452b9f 274527a1 274527a1 24483895 \
+                     26452b9f 26462c9e 26462c9e 24483895 \
+                     2547309b 26452b9f 26462c9e 2547329a \
+                     24493b93 274425a3 26452b9f 274422a5",
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,639 @@
+//! BC1–BC5、BC7 块解码（每块 4x4 纹素）
+
+use ktx2::Format;
+
+use super::{Pixels, decode_blocks};
+
+/// BC 格式每块的字节数，BC6H 不在支持范围内
+pub fn block_bytes(format: Format) -> Option<usize> {
+    match format {
+        Format::BC1_RGB_UNORM_BLOCK
+        | Format::BC1_RGB_SRGB_BLOCK
+        | Format::BC1_RGBA_UNORM_BLOCK
+        | Format::BC1_RGBA_SRGB_BLOCK
+        | Format::BC4_UNORM_BLOCK => Some(8),
+        Format::BC2_UNORM_BLOCK
+        | Format::BC2_SRGB_BLOCK
+        | Format::BC3_UNORM_BLOCK
+        | Format::BC3_SRGB_BLOCK
+        | Format::BC5_UNORM_BLOCK
+        | Format::BC7_UNORM_BLOCK
+        | Format::BC7_SRGB_BLOCK => Some(16),
+        _ => None,
+    }
+}
+
+/// 解码 BC 格式图像，不是 BC 格式时返回 `None`
+pub fn decode(format: Format, data: &[u8], width: u32, height: u32) -> Option<Pixels> {
+    let bytes = block_bytes(format)?;
+    let pixels = match format {
+        Format::BC1_RGB_UNORM_BLOCK | Format::BC1_RGB_SRGB_BLOCK => {
+            decode_blocks(data, bytes, width, height, |block| bc1(block, Bc1Mode::Opaque))
+        }
+        Format::BC1_RGBA_UNORM_BLOCK | Format::BC1_RGBA_SRGB_BLOCK => {
+            decode_blocks(data, bytes, width, height, |block| bc1(block, Bc1Mode::PunchThrough))
+        }
+        Format::BC2_UNORM_BLOCK | Format::BC2_SRGB_BLOCK => decode_blocks(data, bytes, width, height, bc2),
+        Format::BC3_UNORM_BLOCK | Format::BC3_SRGB_BLOCK => decode_blocks(data, bytes, width, height, bc3),
+        Format::BC4_UNORM_BLOCK => decode_blocks(data, bytes, width, height, |block| {
+            bc4(block).map(|r| [r, 0, 0, 255])
+        }),
+        Format::BC5_UNORM_BLOCK => decode_blocks(data, bytes, width, height, |block| {
+            let (red, green) = (bc4(&block[..8]), bc4(&block[8..]));
+            std::array::from_fn(|i| [red[i], green[i], 0, 255])
+        }),
+        _ => decode_blocks(data, bytes, width, height, bc7),
+    };
+    Some(Pixels::Rgba8(pixels))
+}
+
+/// BC1 颜色块的解释方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bc1Mode {
+    /// BC1 RGB：三色模式的第 4 个颜色为不透明黑色
+    Opaque,
+    /// BC1 RGBA：三色模式的第 4 个颜色完全透明
+    PunchThrough,
+    /// BC2 / BC3 的颜色部分：始终是四色模式
+    FourColor,
+}
+
+/// RGB565 扩展为 RGB888
+pub fn rgb565(color: u16) -> [u8; 3] {
+    let (r, g, b) = ((color >> 11) & 0x1f, (color >> 5) & 0x3f, color & 0x1f);
+    [
+        ((r << 3) | (r >> 2)) as u8,
+        ((g << 2) | (g >> 4)) as u8,
+        ((b << 3) | (b >> 2)) as u8,
+    ]
+}
+
+/// BC1 四色模式的调色板（`c0 > c1` 时，以及 BC2 / BC3 的颜色部分）
+pub fn bc1_palette(c0: u16, c1: u16) -> [[u8; 4]; 4] {
+    bc1_palette_for(c0, c1, Bc1Mode::FourColor)
+}
+
+fn bc1_palette_for(c0: u16, c1: u16, mode: Bc1Mode) -> [[u8; 4]; 4] {
+    let (e0, e1) = (rgb565(c0), rgb565(c1));
+    let mix = |a: u32, b: u32, d: u32| -> [u8; 4] {
+        let channel = |i: usize| ((a * e0[i] as u32 + b * e1[i] as u32 + d / 2) / d) as u8;
+        [channel(0), channel(1), channel(2), 255]
+    };
+    if c0 > c1 || mode == Bc1Mode::FourColor {
+        [mix(1, 0, 1), mix(0, 1, 1), mix(2, 1, 3), mix(1, 2, 3)]
+    } else {
+        let last = match mode {
+            Bc1Mode::PunchThrough => [0, 0, 0, 0],
+            _ => [0, 0, 0, 255],
+        };
+        [mix(1, 0, 1), mix(0, 1, 1), mix(1, 1, 2), last]
+    }
+}
+
+fn bc1(block: &[u8], mode: Bc1Mode) -> [[u8; 4]; 16] {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let indices = u32::from_le_bytes([block[4], block[5], block[6], block[7]]);
+    let palette = bc1_palette_for(c0, c1, mode);
+
+    std::array::from_fn(|i| palette[(indices >> (2 * i)) as usize & 3])
+}
+
+fn bc2(block: &[u8]) -> [[u8; 4]; 16] {
+    let alpha = u64::from_le_bytes(block[..8].try_into().unwrap());
+    let mut texels = bc1(&block[8..], Bc1Mode::FourColor);
+    for (i, texel) in texels.iter_mut().enumerate() {
+        texel[3] = ((alpha >> (4 * i)) & 0xf) as u8 * 17;
+    }
+    texels
+}
+
+fn bc3(block: &[u8]) -> [[u8; 4]; 16] {
+    let alpha = bc4(&block[..8]);
+    let mut texels = bc1(&block[8..], Bc1Mode::FourColor);
+    for (texel, a) in texels.iter_mut().zip(alpha) {
+        texel[3] = a;
+    }
+    texels
+}
+
+/// BC4 单通道块（也是 BC3 的 Alpha 和 BC5 的每个通道）
+fn bc4(block: &[u8]) -> [u8; 16] {
+    let mut bits = [0u8; 8];
+    bits[..6].copy_from_slice(&block[2..8]);
+    let indices = u64::from_le_bytes(bits);
+    let palette = bc4_palette(block[0], block[1]);
+
+    std::array::from_fn(|i| palette[(indices >> (3 * i)) as usize & 7])
+}
+
+/// BC4 调色板：`e0 > e1` 时 8 个插值，否则 6 个插值加上 0 和 255
+pub fn bc4_palette(e0: u8, e1: u8) -> [u8; 8] {
+    let (e0, e1) = (e0 as u32, e1 as u32);
+    let mut palette = [0u8; 8];
+    palette[0] = e0 as u8;
+    palette[1] = e1 as u8;
+    if e0 > e1 {
+        for i in 1..7 {
+            palette[i + 1] = (((7 - i) as u32 * e0 + i as u32 * e1 + 3) / 7) as u8;
+        }
+    } else {
+        for i in 1..5 {
+            palette[i + 1] = (((5 - i) as u32 * e0 + i as u32 * e1 + 2) / 5) as u8;
+        }
+        palette[6] = 0;
+        palette[7] = 255;
+    }
+    palette
+}
+
+/// BC7 各模式参数
+pub struct Bc7Mode {
+    pub subsets: usize,
+    pub partition_bits: u32,
+    pub rotation_bits: u32,
+    pub index_selection_bits: u32,
+    pub color_bits: u32,
+    pub alpha_bits: u32,
+    /// 每个端点一个 P 位
+    pub endpoint_pbits: bool,
+    /// 每个子集共享一个 P 位
+    pub shared_pbits: bool,
+    pub index_bits: u32,
+    pub secondary_index_bits: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn bc7_mode(
+    subsets: usize,
+    partition_bits: u32,
+    rotation_bits: u32,
+    index_selection_bits: u32,
+    color_bits: u32,
+    alpha_bits: u32,
+    endpoint_pbits: bool,
+    shared_pbits: bool,
+    index_bits: u32,
+    secondary_index_bits: u32,
+) -> Bc7Mode {
+    Bc7Mode {
+        subsets,
+        partition_bits,
+        rotation_bits,
+        index_selection_bits,
+        color_bits,
+        alpha_bits,
+        endpoint_pbits,
+        shared_pbits,
+        index_bits,
+        secondary_index_bits,
+    }
+}
+
+pub const BC7_MODES: [Bc7Mode; 8] = [
+    bc7_mode(3, 4, 0, 0, 4, 0, true, false, 3, 0),
+    bc7_mode(2, 6, 0, 0, 6, 0, false, true, 3, 0),
+    bc7_mode(3, 6, 0, 0, 5, 0, false, false, 2, 0),
+    bc7_mode(2, 6, 0, 0, 7, 0, true, false, 2, 0),
+    bc7_mode(1, 0, 2, 1, 5, 6, false, false, 2, 3),
+    bc7_mode(1, 0, 2, 0, 7, 8, false, false, 2, 2),
+    bc7_mode(1, 0, 0, 0, 7, 7, true, false, 4, 0),
+    bc7_mode(2, 6, 0, 0, 5, 5, true, false, 2, 0),
+];
+
+/// 两个子集的分区表，第 i 位是纹素 i 所属的子集
+pub const BC7_PARTITIONS_2: [u16; 64] = [
+    0xcccc, 0x8888, 0xeeee, 0xecc8, 0xc880, 0xfeec, 0xfec8, 0xec80, 0xc800, 0xffec, 0xfe80, 0xe800, 0xffe8, 0xff00,
+    0xfff0, 0xf000, 0xf710, 0x008e, 0x7100, 0x08ce, 0x008c, 0x7310, 0x3100, 0x8cce, 0x088c, 0x3110, 0x6666, 0x366c,
+    0x17e8, 0x0ff0, 0x718e, 0x399c, 0xaaaa, 0xf0f0, 0x5a5a, 0x33cc, 0x3c3c, 0x55aa, 0x9696, 0xa55a, 0x73ce, 0x13c8,
+    0x324c, 0x3bdc, 0x6996, 0xc33c, 0x9966, 0x0660, 0x0272, 0x04e4, 0x4e40, 0x2720, 0xc936, 0x936c, 0x39c6, 0x639c,
+    0x9336, 0x9cc6, 0x817e, 0xe718, 0xccf0, 0x0fcc, 0x7744, 0xee22,
+];
+
+/// 三个子集的分区表，纹素 i 的子集在第 2i、2i+1 位
+pub const BC7_PARTITIONS_3: [u32; 64] = [
+    0xaa685050, 0x6a5a5040, 0x5a5a4200, 0x5450a0a8, 0xa5a50000, 0xa0a05050, 0x5555a0a0, 0x5a5a5050, 0xaa550000,
+    0xaa555500, 0xaaaa5500, 0x90909090, 0x94949494, 0xa4a4a4a4, 0xa9a59450, 0x2a0a4250, 0xa5945040, 0x0a425054,
+    0xa5a5a500, 0x55a0a0a0, 0xa8a85454, 0x6a6a4040, 0xa4a45000, 0x1a1a0500, 0x0050a4a4, 0xaaa59090, 0x14696914,
+    0x69691400, 0xa08585a0, 0xaa821414, 0x50a4a450, 0x6a5a0200, 0xa9a58000, 0x5090a0a8, 0xa8a09050, 0x24242424,
+    0x00aa5500, 0x24924924, 0x24499224, 0x50a50a50, 0x500aa550, 0xaaaa4444, 0x66660000, 0xa5a0a5a0, 0x50a050a0,
+    0x69286928, 0x44aaaa44, 0x66666600, 0xaa444444, 0x54a854a8, 0x95809580, 0x96969600, 0xa85454a8, 0x80959580,
+    0xaa141414, 0x96960000, 0xaaaa1414, 0xa05050a0, 0xa0a5a5a0, 0x96000000, 0x40804080, 0xa9a8a9a8, 0xaaaaaa44,
+    0x2a4a5254,
+];
+
+/// 两个子集时第二个子集的锚点纹素
+pub const BC7_ANCHORS_2: [u8; 64] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 2, 8, 2, 2, 8, 8, 15, 2, 8, 2, 2, 8, 8, 2, 2,
+    15, 15, 6, 8, 2, 8, 15, 15, 2, 8, 2, 2, 2, 15, 15, 6, 6, 2, 6, 8, 15, 15, 2, 2, 15, 15, 15, 15, 15, 2, 2, 15,
+];
+
+/// 三个子集时第二个子集的锚点纹素
+pub const BC7_ANCHORS_3_SECOND: [u8; 64] = [
+    3, 3, 15, 15, 8, 3, 15, 15, 8, 8, 6, 6, 6, 5, 3, 3, 3, 3, 8, 15, 3, 3, 6, 10, 5, 8, 8, 6, 8, 5, 15, 15, 8, 15, 3,
+    5, 6, 10, 8, 15, 15, 3, 15, 5, 15, 15, 15, 15, 3, 15, 5, 5, 5, 8, 5, 10, 5, 10, 8, 13, 15, 12, 3, 3,
+];
+
+/// 三个子集时第三个子集的锚点纹素
+pub const BC7_ANCHORS_3_THIRD: [u8; 64] = [
+    15, 8, 8, 3, 15, 15, 3, 8, 15, 15, 15, 15, 15, 15, 15, 8, 15, 8, 15, 3, 15, 8, 15, 8, 3, 15, 6, 10, 15, 15, 10, 8,
+    15, 3, 15, 10, 10, 8, 9, 10, 6, 15, 8, 15, 3, 6, 6, 8, 15, 3, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 3, 15, 15, 8,
+];
+
+/// 插值权重，按索引位数（2、3、4）选取
+pub const BC7_WEIGHTS_2: [u32; 4] = [0, 21, 43, 64];
+pub const BC7_WEIGHTS_3: [u32; 8] = [0, 9, 18, 27, 37, 46, 55, 64];
+pub const BC7_WEIGHTS_4: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+/// 纹素 `texel` 在分区 `partition` 中所属的子集
+pub fn bc7_subset(subsets: usize, partition: usize, texel: usize) -> usize {
+    match subsets {
+        2 => (BC7_PARTITIONS_2[partition] >> texel) as usize & 1,
+        3 => (BC7_PARTITIONS_3[partition] >> (2 * texel)) as usize & 3,
+        _ => 0,
+    }
+}
+
+/// 子集 `subset` 的锚点纹素（锚点的索引少存一位，最高位隐含为 0）
+pub fn bc7_anchor(subsets: usize, partition: usize, subset: usize) -> usize {
+    match (subsets, subset) {
+        (_, 0) => 0,
+        (2, _) => BC7_ANCHORS_2[partition] as usize,
+        (_, 1) => BC7_ANCHORS_3_SECOND[partition] as usize,
+        _ => BC7_ANCHORS_3_THIRD[partition] as usize,
+    }
+}
+
+/// 纹素是否是某个子集的锚点
+pub fn bc7_is_anchor(subsets: usize, partition: usize, texel: usize) -> bool {
+    (0..subsets).any(|subset| bc7_anchor(subsets, partition, subset) == texel)
+}
+
+/// 按权重插值两个端点分量
+pub fn bc7_interpolate(e0: u8, e1: u8, weight: u32) -> u8 {
+    (((64 - weight) * e0 as u32 + weight * e1 as u32 + 32) >> 6) as u8
+}
+
+pub fn bc7_weights(bits: u32) -> &'static [u32] {
+    match bits {
+        2 => &BC7_WEIGHTS_2,
+        3 => &BC7_WEIGHTS_3,
+        _ => &BC7_WEIGHTS_4,
+    }
+}
+
+/// 从低位开始顺序读取的位流
+struct BitReader {
+    bits: u128,
+    position: u32,
+}
+
+impl BitReader {
+    fn read(&mut self, count: u32) -> u32 {
+        let value = (self.bits >> self.position) as u32 & ((1u64 << count) - 1) as u32;
+        self.position += count;
+        value
+    }
+}
+
+/// `bits` 位的分量扩展到 8 位
+pub fn expand(value: u32, bits: u32) -> u8 {
+    let value = value << (8 - bits);
+    (value | (value >> bits)) as u8
+}
+
+fn bc7(block: &[u8]) -> [[u8; 4]; 16] {
+    let mut reader = BitReader {
+        bits: u128::from_le_bytes(block.try_into().unwrap()),
+        position: 0,
+    };
+
+    // 模式号是最低位的连续 0 的个数，第一个字节为 0 是保留编码，解码为全 0
+    let mode_index = block[0].trailing_zeros() as usize;
+    let Some(mode) = BC7_MODES.get(mode_index) else {
+        return [[0; 4]; 16];
+    };
+    reader.read(mode_index as u32 + 1);
+
+    let partition = reader.read(mode.partition_bits) as usize;
+    let rotation = reader.read(mode.rotation_bits);
+    let index_selection = reader.read(mode.index_selection_bits);
+
+    // 端点顺序：每个通道依次给出所有子集的两个端点
+    let endpoints = mode.subsets * 2;
+    let mut raw = [[0u32; 4]; 6];
+    for channel in 0..3 {
+        for endpoint in raw.iter_mut().take(endpoints) {
+            endpoint[channel] = reader.read(mode.color_bits);
+        }
+    }
+    for endpoint in raw.iter_mut().take(endpoints) {
+        endpoint[3] = reader.read(mode.alpha_bits);
+    }
+
+    let mut pbits = [0u32; 6];
+    if mode.endpoint_pbits {
+        for pbit in pbits.iter_mut().take(endpoints) {
+            *pbit = reader.read(1);
+        }
+    } else if mode.shared_pbits {
+        for subset in 0..mode.subsets {
+            let pbit = reader.read(1);
+            pbits[subset * 2] = pbit;
+            pbits[subset * 2 + 1] = pbit;
+        }
+    }
+    let has_pbits = mode.endpoint_pbits || mode.shared_pbits;
+
+    let mut colors = [[0u8; 4]; 6];
+    for (e, endpoint) in raw.iter().enumerate().take(endpoints) {
+        for channel in 0..4 {
+            let bits = if channel < 3 { mode.color_bits } else { mode.alpha_bits };
+            colors[e][channel] = match bits {
+                0 => 255,
+                _ if has_pbits => expand((endpoint[channel] << 1) | pbits[e], bits + 1),
+                _ => expand(endpoint[channel], bits),
+            };
+        }
+    }
+
+    let mut primary = [0u32; 16];
+    for (texel, index) in primary.iter_mut().enumerate() {
+        let anchor = bc7_is_anchor(mode.subsets, partition, texel);
+        *index = reader.read(mode.index_bits - anchor as u32);
+    }
+    let mut secondary = [0u32; 16];
+    if mode.secondary_index_bits > 0 {
+        for (texel, index) in secondary.iter_mut().enumerate() {
+            *index = reader.read(mode.secondary_index_bits - (texel == 0) as u32);
+        }
+    }
+
+    std::array::from_fn(|texel| {
+        let subset = bc7_subset(mode.subsets, partition, texel);
+        let (e0, e1) = (colors[subset * 2], colors[subset * 2 + 1]);
+
+        let (color_weight, alpha_weight) = if mode.secondary_index_bits == 0 {
+            let weight = bc7_weights(mode.index_bits)[primary[texel] as usize];
+            (weight, weight)
+        } else if index_selection == 0 {
+            (
+                bc7_weights(mode.index_bits)[primary[texel] as usize],
+                bc7_weights(mode.secondary_index_bits)[secondary[texel] as usize],
+            )
+        } else {
+            (
+                bc7_weights(mode.secondary_index_bits)[secondary[texel] as usize],
+                bc7_weights(mode.index_bits)[primary[texel] as usize],
+            )
+        };
+
+        let mut texel = [0u8; 4];
+        for channel in 0..3 {
+            texel[channel] = bc7_interpolate(e0[channel], e1[channel], color_weight);
+        }
+        texel[3] = bc7_interpolate(e0[3], e1[3], alpha_weight);
+        if rotation > 0 {
+            texel.swap(3, rotation as usize - 1);
+        }
+        texel
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::assert_vectors;
+
+    #[test]
+    fn test_bc7_anchors_belong_to_their_subset() {
+        for partition in 0..64 {
+            assert_eq!(bc7_subset(2, partition, BC7_ANCHORS_2[partition] as usize), 1);
+            assert_eq!(bc7_subset(3, partition, BC7_ANCHORS_3_SECOND[partition] as usize), 1);
+            assert_eq!(bc7_subset(3, partition, BC7_ANCHORS_3_THIRD[partition] as usize), 2);
+            assert_eq!(bc7_subset(3, partition, 0), 0);
+        }
+    }
+
+    #[test]
+    fn test_bc1_and_bc7_solid_blocks() {
+        // BC1：c0 = 纯红，所有索引为 0
+        let bc1_block = [0x00, 0xf8, 0x00, 0x00, 0, 0, 0, 0];
+        assert!(bc1(&bc1_block, Bc1Mode::Opaque).iter().all(|t| *t == [255, 0, 0, 255]));
+
+        // BC7 模式 6：两个端点都是 (127,127,127,127) 加 P 位 1，即 255
+        let mut bits: u128 = 1 << 6;
+        let mut position = 7;
+        for _ in 0..8 {
+            bits |= 0x7f << position;
+            position += 7;
+        }
+        bits |= 0b11 << position;
+        let block = bits.to_le_bytes();
+        assert!(bc7(&block).iter().all(|t| *t == [255; 4]));
+    }
+
+    #[test]
+    fn test_reference_vectors() {
+        // 期望值由 Mesa 22.3.6（llvmpipe）解码得到：glCompressedTexImage2D 上传单个块，glGetTexImage 读回。
+        // 各实现对 BC1–BC5 插值的舍入不同（Mesa 最多偏差 2），这里的端点都选成插值恰好整除、
+        // Mesa 与精确值一致的组合；6 级 Alpha 的插值项 Mesa 总是偏低，块里只用索引 0、1、6、7
+        assert_vectors(
+            Format::BC1_RGB_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // BC1 四色
+                (
+                    "3aef6e2de41b00ff",
+                    "efe7d6ff 29ae73ff add4b5ff 6bc194ff \
+                     6bc194ff add4b5ff 29ae73ff efe7d6ff \
+                     efe7d6ff efe7d6ff efe7d6ff efe7d6ff \
+                     6bc194ff 6bc194ff 6bc194ff 6bc194ff",
+                ),
+                // BC1 三色，RGB 格式不透明
+                (
+                    "e6729deee41b00ff",
+                    "735d31ff efd3efff b19890ff 000000ff \
+                     000000ff b19890ff efd3efff 735d31ff \
+                     735d31ff 735d31ff 735d31ff 735d31ff \
+                     000000ff 000000ff 000000ff 000000ff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::BC1_RGBA_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // BC1 三色 + 透明
+                (
+                    "e6729deee41b00ff",
+                    "735d31ff efd3efff b19890ff 00000000 \
+                     00000000 b19890ff efd3efff 735d31ff \
+                     735d31ff 735d31ff 735d31ff 735d31ff \
+                     00000000 00000000 00000000 00000000",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::BC2_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // BC2
+                (
+                    "1032547698badcfe3aef6e2de41b00ff",
+                    "efe7d600 29ae7311 add4b522 6bc19433 \
+                     6bc19444 add4b555 29ae7366 efe7d677 \
+                     efe7d688 efe7d699 efe7d6aa efe7d6bb \
+                     6bc194cc 6bc194dd 6bc194ee 6bc194ff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::BC3_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // BC3 Alpha 8 级
+                (
+                    "ff5088c6fa88c6fa3aef6e2de41b00ff",
+                    "efe7d6ff 29ae7350 add4b5e6 6bc194cd \
+                     6bc194b4 add4b59b 29ae7382 efe7d669 \
+                     efe7d6ff efe7d650 efe7d6e6 efe7d6cd \
+                     6bc194b4 6bc1949b 6bc19482 6bc19469",
+                ),
+                // BC3 Alpha 6 级
+                (
+                    "14f5888ff87770073aef6e2de41b00ff",
+                    "efe7d614 29ae73f5 add4b500 6bc194ff \
+                     6bc19414 add4b5f5 29ae7300 efe7d6ff \
+                     efe7d6ff efe7d600 efe7d6f5 efe7d614 \
+                     6bc194ff 6bc19400 6bc194f5 6bc19414",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::BC4_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // BC4 8 级
+                (
+                    "ff5088c6fa88c6fa",
+                    "ff0000ff 500000ff e60000ff cd0000ff \
+                     b40000ff 9b0000ff 820000ff 690000ff \
+                     ff0000ff 500000ff e60000ff cd0000ff \
+                     b40000ff 9b0000ff 820000ff 690000ff",
+                ),
+                // BC4 6 级
+                (
+                    "14f5888ff8777007",
+                    "140000ff f50000ff 000000ff ff0000ff \
+                     140000ff f50000ff 000000ff ff0000ff \
+                     ff0000ff 000000ff f50000ff 140000ff \
+                     ff0000ff 000000ff f50000ff 140000ff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::BC5_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // BC5
+                (
+                    "ff5088c6fa88c6fa14f5888ff8777007",
+                    "ff1400ff 50f500ff e60000ff cdff00ff \
+                     b41400ff 9bf500ff 820000ff 69ff00ff \
+                     ffff00ff 500000ff e6f500ff cd1400ff \
+                     b4ff00ff 9b0000ff 82f500ff 691400ff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::BC7_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // 模式 0
+                (
+                    "9f41bd5bcbb0f1d7bda6ec8707d777c6",
+                    "b1a0f5ff 0052b5ff e784e7ff ad6befff \
+                     dbab44ff 0052b5ff ceadffff cf79eaff \
+                     d9b747ff d8c14bff 0052b5ff 7787e0ff \
+                     de8c39ff dbab44ff d7cc4eff 1d5fbfff",
+                ),
+                // 模式 1
+                (
+                    "9e71202100f8df0135c637f5fb2adf2a",
+                    "ac1411ff 39f2a7ff 91261bff 2de8b7ff \
+                     22dfc7ff 763826ff 22dfc7ff 763826ff \
+                     2de8b7ff 91261bff 28e4bfff 068352ff \
+                     217147ff 3ff69fff 3c5f3dff 4aff8fff",
+                ),
+                // 模式 2
+                (
+                    "e4624c055c6a2aef254310544dd19a96",
+                    "8c7fb6ff 8ca5ceff 3eae70ff 3eae70ff \
+                     4aad00ff 3fc003ff 8c3184ff 7f6a8fff \
+                     4aad00ff 29e708ff 8ca5ceff 3eae70ff \
+                     8c3184ff 8c579cff 00ef52ff 3eae70ff",
+                ),
+                // 模式 3
+                (
+                    "9858d34074a66b250efaeaa308cd7e55",
+                    "ac3206ff b95f56ff 80acd4ff b694a5ff \
+                     c68eabff 9aa0bdff b694a5ff 9aa0bdff \
+                     d0888eff d0888eff d0888eff b694a5ff \
+                     b694a5ff b694a5ff b694a5ff 80acd4ff",
+                ),
+                // 模式 4
+                (
+                    "b04fff7b29b80c14fc9988ec67083cbd",
+                    "66ffbd7b 66f6ba88 82ecb695 82c6a8c9 \
+                     48c6a8c9 48bda5d6 82f6ba88 82e3b3a1 \
+                     48ffbd7b 2cf6ba88 2cffbd7b 2cc6a8c9 \
+                     82e3b3a1 2cecb695 82bda5d6 66d0acbc",
+                ),
+                // 模式 5
+                (
+                    "e09f22026e1750af997d7d5af0a75fea",
+                    "3e10d4ed 8be1d404 3e106bed 8be16b04 \
+                     729c6b50 8be1b204 8be18d04 729c8d50 \
+                     729c6b50 8be16b04 8be1b204 3e10b2ed \
+                     57558da1 8be18d04 729c8d50 3e106bed",
+                ),
+                // 模式 6
+                (
+                    "40c6204224f8be1431142a5f9e1221eb",
+                    "182208be 153720a0 133d2796 17280fb5 \
+                     0d67575a 163018a9 07897d29 12442e8d \
+                     08837632 0e5f4d66 163018a9 17280fb5 \
+                     17280fb5 163018a9 0c6e5e51 08837632",
+                ),
+                // 模式 7
+                (
+                    "8006e61dc78d4e1956569c5829d8ef03",
+                    "c78e2cae d0a725b5 d0a725b5 ef45b64d \
+                     c78e2cae e3db18c3 d13c9e5a c3389261 \
+                     e3db18c3 e141aa54 c3389261 c3389261 \
+                     e141aa54 ef45b64d ef45b64d ef45b64d",
+                ),
+                // 保留的模式 8：全 0
+                (
+                    "0098adb556104f5ad14e7bb2502f7b78",
+                    "00000000 00000000 00000000 00000000 \
+                     00000000 00000000 00000000 00000000 \
+                     00000000 00000000 00000000 00000000 \
+                     00000000 00000000 00000000 00000000",
+                ),
+            ],
+        );
+
+        // 6 级 Alpha 插值项按规范公式 ((5 - i) * e0 + i * e1) / 5 检查
+        assert_eq!(bc4_palette(0, 5), [0, 5, 1, 2, 3, 4, 0, 255]);
+        assert_eq!(bc4_palette(20, 245), [20, 245, 65, 110, 155, 200, 0, 255]);
+    }
+}
@@ -0,0 +1,416 @@
+//! ETC2 / EAC 块解码（每块 4x4 纹素，块内数据按大端序存储，纹素索引按列排列）
+
+use ktx2::Format;
+
+use super::{Pixels, decode_blocks};
+
+/// ETC1 / ETC2 亮度修正表
+const MODIFIERS: [[i32; 2]; 8] = [[2, 8], [5, 17], [9, 29], [13, 42], [18, 60], [24, 80], [33, 106], [47, 183]];
+
+/// T / H 模式的距离表
+const DISTANCES: [i32; 8] = [3, 6, 11, 16, 23, 32, 41, 64];
+
+/// EAC 修正表
+const EAC_MODIFIERS: [[i32; 8]; 16] = [
+    [-3, -6, -9, -15, 2, 5, 8, 14],
+    [-3, -7, -10, -13, 2, 6, 9, 12],
+    [-2, -5, -8, -13, 1, 4, 7, 12],
+    [-2, -4, -6, -13, 1, 3, 5, 12],
+    [-3, -6, -8, -12, 2, 5, 7, 11],
+    [-3, -7, -9, -11, 2, 6, 8, 10],
+    [-4, -7, -8, -11, 3, 6, 7, 10],
+    [-3, -5, -8, -11, 2, 4, 7, 10],
+    [-2, -6, -8, -10, 1, 5, 7, 9],
+    [-2, -5, -8, -10, 1, 4, 7, 9],
+    [-2, -4, -8, -10, 1, 3, 7, 9],
+    [-2, -5, -7, -10, 1, 4, 6, 9],
+    [-3, -4, -7, -10, 2, 3, 6, 9],
+    [-1, -2, -3, -10, 0, 1, 2, 9],
+    [-4, -6, -8, -9, 3, 5, 7, 8],
+    [-3, -5, -7, -9, 2, 4, 6, 8],
+];
+
+/// ETC2 / EAC 格式每块的字节数，SNORM 变体不在支持范围内
+pub fn block_bytes(format: Format) -> Option<usize> {
+    match format {
+        Format::ETC2_R8G8B8_UNORM_BLOCK
+        | Format::ETC2_R8G8B8_SRGB_BLOCK
+        | Format::ETC2_R8G8B8A1_UNORM_BLOCK
+        | Format::ETC2_R8G8B8A1_SRGB_BLOCK
+        | Format::EAC_R11_UNORM_BLOCK => Some(8),
+        Format::ETC2_R8G8B8A8_UNORM_BLOCK | Format::ETC2_R8G8B8A8_SRGB_BLOCK | Format::EAC_R11G11_UNORM_BLOCK => {
+            Some(16)
+        }
+        _ => None,
+    }
+}
+
+/// 解码 ETC2 / EAC 格式图像，不是这些格式时返回 `None`
+pub fn decode(format: Format, data: &[u8], width: u32, height: u32) -> Option<Pixels> {
+    let bytes = block_bytes(format)?;
+    Some(match format {
+        Format::ETC2_R8G8B8_UNORM_BLOCK | Format::ETC2_R8G8B8_SRGB_BLOCK => {
+            Pixels::Rgba8(decode_blocks(data, bytes, width, height, |block| etc2(block, false)))
+        }
+        Format::ETC2_R8G8B8A1_UNORM_BLOCK | Format::ETC2_R8G8B8A1_SRGB_BLOCK => {
+            Pixels::Rgba8(decode_blocks(data, bytes, width, height, |block| etc2(block, true)))
+        }
+        Format::ETC2_R8G8B8A8_UNORM_BLOCK | Format::ETC2_R8G8B8A8_SRGB_BLOCK => {
+            Pixels::Rgba8(decode_blocks(data, bytes, width, height, |block| {
+                let alpha = eac(&block[..8], |base, modifier, multiplier| {
+                    (base + modifier * multiplier).clamp(0, 255) as u16
+                });
+                let mut texels = etc2(&block[8..], false);
+                for (texel, a) in texels.iter_mut().zip(alpha) {
+                    texel[3] = a as u8;
+                }
+                texels
+            }))
+        }
+        Format::EAC_R11_UNORM_BLOCK => Pixels::Rgba16(decode_blocks(data, bytes, width, height, |block| {
+            eac(block, r11).map(|r| [r, 0, 0, u16::MAX])
+        })),
+        _ => Pixels::Rgba16(decode_blocks(data, bytes, width, height, |block| {
+            let (red, green) = (eac(&block[..8], r11), eac(&block[8..], r11));
+            std::array::from_fn(|i| [red[i], green[i], 0, u16::MAX])
+        })),
+    })
+}
+
+fn bits(block: u64, high: u32, count: u32) -> i32 {
+    ((block >> (high + 1 - count)) & ((1 << count) - 1)) as i32
+}
+
+fn extend4(value: i32) -> i32 {
+    value * 17
+}
+
+fn extend5(value: i32) -> i32 {
+    (value << 3) | (value >> 2)
+}
+
+fn clamp(value: i32) -> u8 {
+    value.clamp(0, 255) as u8
+}
+
+/// 纹素 i（按行）在块内的 2 位索引
+fn pixel_index(block: u64, i: usize) -> usize {
+    let bit = (i % 4) * 4 + i / 4;
+    let msb = (block >> (16 + bit)) & 1;
+    let lsb = (block >> bit) & 1;
+    (msb << 1 | lsb) as usize
+}
+
+/// ETC2 RGB 块；`punch_through` 时差分位表示块是否不透明，索引 2 的纹素透明
+fn etc2(block: &[u8], punch_through: bool) -> [[u8; 4]; 16] {
+    let block = u64::from_be_bytes(block.try_into().unwrap());
+    let differential = bits(block, 33, 1) == 1;
+    let opaque = !punch_through || differential;
+
+    if !differential && !punch_through {
+        let base = [
+            [extend4(bits(block, 63, 4)), extend4(bits(block, 55, 4)), extend4(bits(block, 47, 4))],
+            [extend4(bits(block, 59, 4)), extend4(bits(block, 51, 4)), extend4(bits(block, 43, 4))],
+        ];
+        return etc1_subblocks(block, base, true);
+    }
+
+    let (r, g, b) = (bits(block, 63, 5), bits(block, 55, 5), bits(block, 47, 5));
+    let signed = |value: i32| if value >= 4 { value - 8 } else { value };
+    let (r2, g2, b2) = (
+        r + signed(bits(block, 58, 3)),
+        g + signed(bits(block, 50, 3)),
+        b + signed(bits(block, 42, 3)),
+    );
+
+    if !(0..32).contains(&r2) {
+        t_mode(block, opaque)
+    } else if !(0..32).contains(&g2) {
+        h_mode(block, opaque)
+    } else if !(0..32).contains(&b2) {
+        planar(block)
+    } else {
+        let base = [
+            [extend5(r), extend5(g), extend5(b)],
+            [extend5(r2), extend5(g2), extend5(b2)],
+        ];
+        etc1_subblocks(block, base, opaque)
+    }
+}
+
+/// 个别 / 差分模式：两个子块各有基色和修正表
+fn etc1_subblocks(block: u64, base: [[i32; 3]; 2], opaque: bool) -> [[u8; 4]; 16] {
+    let flip = bits(block, 32, 1) == 1;
+    let tables = [bits(block, 39, 3) as usize, bits(block, 36, 3) as usize];
+
+    std::array::from_fn(|i| {
+        let (x, y) = (i % 4, i / 4);
+        let subblock = if flip { (y >= 2) as usize } else { (x >= 2) as usize };
+        let [small, large] = MODIFIERS[tables[subblock]];
+        let modifier = match pixel_index(block, i) {
+            0 if opaque => small,
+            0 => 0,
+            1 => large,
+            2 if opaque => -small,
+            2 => return [0, 0, 0, 0],
+            _ => -large,
+        };
+        let [r, g, b] = base[subblock];
+        [clamp(r + modifier), clamp(g + modifier), clamp(b + modifier), 255]
+    })
+}
+
+fn paint(block: u64, colors: [[i32; 3]; 4], opaque: bool) -> [[u8; 4]; 16] {
+    std::array::from_fn(|i| match pixel_index(block, i) {
+        2 if !opaque => [0, 0, 0, 0],
+        index => {
+            let [r, g, b] = colors[index];
+            [clamp(r), clamp(g), clamp(b), 255]
+        }
+    })
+}
+
+fn offset(color: [i32; 3], distance: i32) -> [i32; 3] {
+    color.map(|c| c + distance)
+}
+
+fn t_mode(block: u64, opaque: bool) -> [[u8; 4]; 16] {
+    let r1 = (bits(block, 60, 2) << 2) | bits(block, 57, 2);
+    let c1 = [extend4(r1), extend4(bits(block, 55, 4)), extend4(bits(block, 51, 4))];
+    let c2 = [
+        extend4(bits(block, 47, 4)),
+        extend4(bits(block, 43, 4)),
+        extend4(bits(block, 39, 4)),
+    ];
+    let distance = DISTANCES[((bits(block, 35, 2) << 1) | bits(block, 32, 1)) as usize];
+    paint(block, [c1, offset(c2, distance), c2, offset(c2, -distance)], opaque)
+}
+
+fn h_mode(block: u64, opaque: bool) -> [[u8; 4]; 16] {
+    let r1 = bits(block, 62, 4);
+    let g1 = (bits(block, 58, 3) << 1) | bits(block, 52, 1);
+    let b1 = (bits(block, 51, 1) << 3) | bits(block, 49, 3);
+    let (r2, g2, b2) = (bits(block, 46, 4), bits(block, 42, 4), bits(block, 38, 4));
+
+    // 距离索引的最低位由两个基色的大小关系决定
+    let ordering = ((r1 << 8) | (g1 << 4) | b1) >= ((r2 << 8) | (g2 << 4) | b2);
+    let index = (bits(block, 34, 1) << 2) | (bits(block, 32, 1) << 1) | ordering as i32;
+    let distance = DISTANCES[index as usize];
+
+    let c1 = [extend4(r1), extend4(g1), extend4(b1)];
+    let c2 = [extend4(r2), extend4(g2), extend4(b2)];
+    paint(
+        block,
+        [offset(c1, distance), offset(c1, -distance), offset(c2, distance), offset(c2, -distance)],
+        opaque,
+    )
+}
+
+fn planar(block: u64) -> [[u8; 4]; 16] {
+    let extend6 = |v: i32| (v << 2) | (v >> 4);
+    let extend7 = |v: i32| (v << 1) | (v >> 6);
+
+    let origin = [
+        extend6(bits(block, 62, 6)),
+        extend7((bits(block, 56, 1) << 6) | bits(block, 54, 6)),
+        extend6((bits(block, 48, 1) << 5) | (bits(block, 44, 2) << 3) | bits(block, 41, 3)),
+    ];
+    let horizontal = [
+        extend6((bits(block, 38, 5) << 1) | bits(block, 32, 1)),
+        extend7(bits(block, 31, 7)),
+        extend6(bits(block, 24, 6)),
+    ];
+    let vertical = [
+        extend6(bits(block, 18, 6)),
+        extend7(bits(block, 12, 7)),
+        extend6(bits(block, 5, 6)),
+    ];
+
+    std::array::from_fn(|i| {
+        let (x, y) = ((i % 4) as i32, (i / 4) as i32);
+        let channel = |c: usize| {
+            clamp((x * (horizontal[c] - origin[c]) + y * (vertical[c] - origin[c]) + 4 * origin[c] + 2) >> 2)
+        };
+        [channel(0), channel(1), channel(2), 255]
+    })
+}
+
+/// EAC 块：`value(base, modifier, multiplier)` 计算每个纹素的值
+fn eac(block: &[u8], value: impl Fn(i32, i32, i32) -> u16) -> [u16; 16] {
+    let block = u64::from_be_bytes(block.try_into().unwrap());
+    let base = bits(block, 63, 8);
+    let multiplier = bits(block, 55, 4);
+    let table = EAC_MODIFIERS[bits(block, 51, 4) as usize];
+
+    std::array::from_fn(|i| {
+        let bit = (i % 4) * 4 + i / 4;
+        let index = bits(block, 47 - 3 * bit as u32, 3) as usize;
+        value(base, table[index], multiplier)
+    })
+}
+
+/// EAC R11 UNORM：11 位结果扩展到 16 位
+fn r11(base: i32, modifier: i32, multiplier: i32) -> u16 {
+    let delta = match multiplier {
+        0 => modifier,
+        _ => modifier * multiplier * 8,
+    };
+    let value = (base * 8 + 4 + delta).clamp(0, 2047) as u16;
+    (value << 5) | (value >> 6)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode::tests::assert_vectors;
+
+    #[test]
+    fn test_etc2_individual_and_eac_blocks() {
+        // 个别模式：左子块基色 (0x8, 0x4, 0x2)，修正表 0，所有索引为 0（+2）
+        let block = 0x8844_2200_0000_0000u64.to_be_bytes();
+        let texels = etc2(&block, false);
+        assert_eq!(texels[0], [138, 70, 36, 255]);
+        assert_eq!(texels[3], [138, 70, 36, 255]);
+
+        // EAC Alpha：基值 100，乘数 2，表 0，所有索引为 4（+2）
+        let alpha = 0x6420_9249_2492_4924u64.to_be_bytes();
+        let values = eac(&alpha, |base, modifier, multiplier| (base + modifier * multiplier) as u16);
+        assert!(values.iter().all(|&v| v == 104));
+    }
+
+    #[test]
+    fn test_reference_vectors() {
+        // 期望值由 Mesa 22.3.6（llvmpipe）解码得到：glCompressedTexImage2D 上传单个块，glGetTexImage 读回，EAC 按 16 位读回
+        assert_vectors(
+            Format::ETC2_R8G8B8_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // 个别模式
+                (
+                    "f13fa60de6281c5f",
+                    "ff3bb2ff ff3bb2ff ff35acff ff3bb2ff \
+                     ff3bb2ff fd31a8ff fd31a8ff fd31a8ff \
+                     3bff90ff 3bff90ff 00d53cff 04f259ff \
+                     00d53cff 1eff73ff 3bff90ff 04f259ff",
+                ),
+                // 差分模式
+                (
+                    "9f41bd5bcbb0f1d7",
+                    "b95fdaff 7f25a0ff 7f25a0ff b95fdaff \
+                     b95fdaff 9339b4ff 9339b4ff b95fdaff \
+                     feb4ffff feb4ffff b56bc6ff 2a003bff \
+                     b56bc6ff 2a003bff 732984ff 2a003bff",
+                ),
+                // T 模式
+                (
+                    "0efaeaa308cd7e55",
+                    "e8a4a4ff f4b0b0ff 66ffaaff f4b0b0ff \
+                     66ffaaff 66ffaaff f4b0b0ff f4b0b0ff \
+                     e8a4a4ff e8a4a4ff f4b0b0ff f4b0b0ff \
+                     eeaaaaff eeaaaaff e8a4a4ff 66ffaaff",
+                ),
+                // H 模式
+                (
+                    "b114b8abba6a2b9d",
+                    "5b2806ff 5b2806ff 5b2806ff 821c60ff \
+                     821c60ff 821c60ff 6c064aff 6c064aff \
+                     5b2806ff 821c60ff 713e1cff 713e1cff \
+                     6c064aff 5b2806ff 6c064aff 821c60ff",
+                ),
+                // 平面模式
+                (
+                    "4bf4f257a62d94da",
+                    "96f551ff 9ce242ff a2ce33ff a8bb23ff \
+                     9de257ff a3ce48ff a9bb39ff afa729ff \
+                     a4ce5dff aabb4eff b0a73fff b6942fff \
+                     abbb63ff b1a754ff b79445ff bd8035ff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::ETC2_R8G8B8A1_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // 差分模式，穿孔 Alpha
+                (
+                    "f13fa60de6281c5f",
+                    "ff41adff ff41adff f739a5ff ff41adff \
+                     ff41adff 00000000 00000000 00000000 \
+                     ff5bbeff ff5bbeff d5076aff 00000000 \
+                     d5076aff ff3194ff ff5bbeff 00000000",
+                ),
+                // T 模式，穿孔 Alpha
+                (
+                    "f95df455842487fa",
+                    "dd55ddff ff5465ff ff5465ff dd55ddff \
+                     ff5465ff ef3445ff ff5465ff dd55ddff \
+                     00000000 ff5465ff ef3445ff dd55ddff \
+                     ff5465ff ff5465ff dd55ddff ef3445ff",
+                ),
+                // H 模式，穿孔 Alpha
+                (
+                    "6a0551f1fb4e7ded",
+                    "cd3412ff ed5432ff 9a23deff 9a23deff \
+                     00000000 cd3412ff 00000000 9a23deff \
+                     9a23deff 9a23deff cd3412ff 9a23deff \
+                     9a23deff cd3412ff 9a23deff 00000000",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::ETC2_R8G8B8A8_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // 差分模式，EAC Alpha
+                (
+                    "9f41bd5bcbb0f1d7bda6ec8707d777c6",
+                    "ab93ddb7 ab93ddb7 8169b3b7 f9e1ff93 \
+                     8169b3cf cfb7ffcf 8169b3a7 f9e1ffcf \
+                     9483bd77 9483bd83 9483bd83 b6a5df77 \
+                     aa99d3b7 9483bd6b aa99d3cf aa99d3cf",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::EAC_R11_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // 乘数不为 0
+                (
+                    "9f41bd5bcbb0f1d7",
+                    "96b700000000ffff 96b700000000ffff 96b700000000ffff 929300000000ffff \
+                     99cf00000000ffff 99cf00000000ffff 94a700000000ffff 99cf00000000ffff \
+                     8e7700000000ffff 908300000000ffff 908300000000ffff 8e7700000000ffff \
+                     96b700000000ffff 8d6b00000000ffff 99cf00000000ffff 99cf00000000ffff",
+                ),
+                // 乘数为 0：修正值乘 1/8
+                (
+                    "260fd86b4b2474ec",
+                    "442700000000ffff 042700000000ffff e42500000000ffff a42500000000ffff \
+                     442700000000ffff 042700000000ffff e42500000000ffff 642500000000ffff \
+                     242600000000ffff e42500000000ffff 242600000000ffff 042700000000ffff \
+                     442700000000ffff 642500000000ffff 842700000000ffff c42600000000ffff",
+                ),
+            ],
+        );
+        assert_vectors(
+            Format::EAC_R11G11_UNORM_BLOCK,
+            4,
+            4,
+            &[
+                // 乘数不为 0
+                (
+                    "9f41bd5bcbb0f1d7bda6ec8707d777c6",
+                    "96b7ffff0000ffff 96b7894f0000ffff 96b7ffff0000ffff 9293894f0000ffff \
+                     99cf894f0000ffff 99cf9bdb0000ffff 94a79ff90000ffff 99cfffff0000ffff \
+                     8e778e770000ffff 908392950000ffff 9083ffff0000ffff 8e7792950000ffff \
+                     96b792950000ffff 8d6bffff0000ffff 99cfffff0000ffff 99cfffff0000ffff",
+                ),
+            ],
+        );
+    }
+}
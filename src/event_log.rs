@@ -0,0 +1,229 @@
+//! 事件日志模块
+//!
+//! 用固定容量的环形缓冲区保存结构化事件（时间戳、类别、详情），
+//! 支持按类别过滤、暂停、清空，并合并高频的光标移动事件
+
+use std::collections::VecDeque;
+
+/// 默认最多保留的事件数
+const DEFAULT_CAPACITY: usize = 500;
+
+/// 事件类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventCategory {
+    Mouse,
+    Touch,
+    Window,
+    Keyboard,
+    Texture,
+}
+
+impl EventCategory {
+    /// 所有类别，按过滤器显示顺序排列
+    pub const ALL: [EventCategory; 5] = [
+        EventCategory::Mouse,
+        EventCategory::Touch,
+        EventCategory::Window,
+        EventCategory::Keyboard,
+        EventCategory::Texture,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            EventCategory::Mouse => "Mouse",
+            EventCategory::Touch => "Touch",
+            EventCategory::Window => "Window",
+            EventCategory::Keyboard => "Keyboard",
+            EventCategory::Texture => "Texture",
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 单条事件记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogEntry {
+    /// 记录时间（Unix 毫秒）
+    pub timestamp: f64,
+    pub category: EventCategory,
+    pub details: String,
+    /// 合并的事件数（未合并时为 1）
+    pub count: u32,
+    /// 是否允许与后续同类高频事件合并
+    coalescing: bool,
+}
+
+impl LogEntry {
+    /// 面板中显示的文本，例如 `12:34:56.789 [Mouse] Mouse moved: (1, 2) ×15`
+    pub fn line(&self) -> String {
+        let millis = self.timestamp as u64;
+        let seconds = millis / 1000;
+        let mut line = format!(
+            "{:02}:{:02}:{:02}.{:03} [{}] {}",
+            (seconds / 3600) % 24,
+            (seconds / 60) % 60,
+            seconds % 60,
+            millis % 1000,
+            self.category.label(),
+            self.details
+        );
+        if self.count > 1 {
+            line.push_str(&format!(" ×{}", self.count));
+        }
+        line
+    }
+}
+
+/// 事件日志
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventLog {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    paused: bool,
+    /// 每个类别是否显示，下标与 [`EventCategory::ALL`] 一致
+    filters: [bool; 5],
+}
+
+impl EventLog {
+    /// 创建指定容量的事件日志
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            paused: false,
+            filters: [true; 5],
+        }
+    }
+
+    /// 记录一条事件
+    pub fn record(&mut self, category: EventCategory, details: impl Into<String>) {
+        self.push(category, details.into(), false);
+    }
+
+    /// 记录高频事件（如 CursorMoved）
+    ///
+    /// 与上一条同类高频事件连续出现时只更新详情并累加次数
+    pub fn record_coalesced(&mut self, category: EventCategory, details: impl Into<String>) {
+        self.push(category, details.into(), true);
+    }
+
+    /// 最近一条事件
+    pub fn last(&self) -> Option<&LogEntry> {
+        self.entries.back()
+    }
+
+    /// 通过过滤器的事件，最新的在前
+    pub fn visible(&self) -> impl Iterator<Item = &LogEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|entry| self.filters[entry.category.index()])
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub fn is_shown(&self, category: EventCategory) -> bool {
+        self.filters[category.index()]
+    }
+
+    pub fn set_shown(&mut self, category: EventCategory, shown: bool) {
+        self.filters[category.index()] = shown;
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    fn push(&mut self, category: EventCategory, details: String, coalescing: bool) {
+        if self.paused {
+            return;
+        }
+
+        let timestamp = now_millis();
+        if coalescing
+            && let Some(last) = self.entries.back_mut()
+            && last.coalescing
+            && last.category == category
+        {
+            last.timestamp = timestamp;
+            last.details = details;
+            last.count += 1;
+            return;
+        }
+
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            timestamp,
+            category,
+            details,
+            count: 1,
+            coalescing,
+        });
+    }
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+/// 当前时间（Unix 毫秒）
+///
+/// WASM 上 `std::time::SystemTime` 不可用，改用 `Date.now()`
+fn now_millis() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        js_sys::Date::now()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs_f64() * 1000.0)
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_and_coalescing() {
+        let mut log = EventLog::new(3);
+        log.record_coalesced(EventCategory::Mouse, "Mouse moved: (0, 0)");
+        log.record_coalesced(EventCategory::Mouse, "Mouse moved: (1, 1)");
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.last().map(|entry| entry.count), Some(2));
+
+        log.record(EventCategory::Window, "Window focused");
+        log.record(EventCategory::Texture, "Texture loaded");
+        log.record(EventCategory::Keyboard, "Zoom in");
+        assert_eq!(log.len(), 3);
+
+        log.set_shown(EventCategory::Texture, false);
+        let details: Vec<&str> = log.visible().map(|entry| entry.details.as_str()).collect();
+        assert_eq!(details, vec!["Zoom in", "Window focused"]);
+
+        log.set_paused(true);
+        log.record(EventCategory::Mouse, "ignored");
+        assert_eq!(log.len(), 3);
+    }
+}
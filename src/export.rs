@@ -0,0 +1,195 @@
+//! 纹理导出模块
+//!
+//! 把预览中当前显示的图像（已应用 mip 层级和通道开关）编码为 PNG 或 KTX2，
+//! 原生环境通过保存对话框写入磁盘，WASM 环境触发浏览器下载
+//!
+//! 两种格式都保留原始像素值：KTX2 按色彩空间选择 `_SRGB` / `_UNORM`，
+//! PNG 写入 sRGB 块或 gAMA 1.0（`color_space::detect_png` 读取时据此还原色彩空间）
+
+use crate::color_space::ColorSpace;
+use crate::ktx2_writer::{self, Ktx2Writer};
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Png,
+    Ktx2,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 2] = [ExportFormat::Png, ExportFormat::Ktx2];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ExportFormat::Png => "PNG",
+            ExportFormat::Ktx2 => "KTX2",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Png => "png",
+            ExportFormat::Ktx2 => "ktx2",
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn mime_type(self) -> &'static str {
+        match self {
+            ExportFormat::Png => "image/png",
+            ExportFormat::Ktx2 => "image/ktx2",
+        }
+    }
+}
+
+/// 编码好的导出文件
+#[derive(Debug, Clone)]
+pub struct ExportedFile {
+    pub name: String,
+    pub format: ExportFormat,
+    pub data: Vec<u8>,
+}
+
+/// 把 `color_space` 编码的 RGBA8 像素编码为指定格式
+pub fn encode(
+    rgba: &[u8],
+    width: u32,
+    height: u32,
+    format: ExportFormat,
+    color_space: ColorSpace,
+) -> Result<Vec<u8>, String> {
+    match format {
+        ExportFormat::Png => write_png(rgba, width, height, png::BitDepth::Eight, color_space),
+        ExportFormat::Ktx2 => Ktx2Writer::rgba8(width, height, color_space.is_srgb())
+            .level(rgba.to_vec())
+            .key_value("KTXorientation", ktx2_writer::nul_terminated("rd"))
+            .write(),
+    }
+}
+
+/// 把 16 位 RGBA 像素编码为 PNG，色彩空间的标记方式与 `encode` 相同
+pub fn encode_png16(rgba: &[u16], width: u32, height: u32, color_space: ColorSpace) -> Result<Vec<u8>, String> {
+    // PNG 的 16 位采样为大端序
+    let bytes: Vec<u8> = rgba.iter().flat_map(|value| value.to_be_bytes()).collect();
+    write_png(&bytes, width, height, png::BitDepth::Sixteen, color_space)
+}
+
+/// 写入 RGBA PNG：sRGB 数据带 sRGB 块，线性数据带 gAMA 1.0
+fn write_png(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    depth: png::BitDepth,
+    color_space: ColorSpace,
+) -> Result<Vec<u8>, String> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(depth);
+    match color_space {
+        ColorSpace::Srgb => encoder.set_source_srgb(png::SrgbRenderingIntent::Perceptual),
+        ColorSpace::Linear => encoder.set_source_gamma(png::ScaledFloat::from_scaled(100_000)),
+    }
+
+    let map_err = |e: png::EncodingError| format!("Failed to encode PNG: {}", e);
+    let mut writer = encoder.write_header().map_err(map_err)?;
+    writer.write_image_data(data).map_err(map_err)?;
+    writer.finish().map_err(map_err)?;
+    Ok(out)
+}
+
+/// 根据源文件名生成导出文件名，例如 `1_mip2.png`
+pub fn file_name(source: Option<&str>, mip_level: u32, format: ExportFormat) -> String {
+    let stem = source
+        .map(|name| match name.rsplit_once('.') {
+            Some((stem, _)) if !stem.is_empty() => stem,
+            _ => name,
+        })
+        .unwrap_or("texture");
+
+    if mip_level == 0 {
+        format!("{}.{}", stem, format.extension())
+    } else {
+        format!("{}_mip{}.{}", stem, mip_level, format.extension())
+    }
+}
+
+/// 弹出保存对话框并写入文件（仅原生）
+///
+/// 返回保存的路径，用户取消时返回 `None`
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn save(file: ExportedFile) -> Result<Option<String>, String> {
+    let Some(handle) = rfd::AsyncFileDialog::new()
+        .set_file_name(&file.name)
+        .add_filter(file.format.label(), &[file.format.extension()])
+        .save_file()
+        .await
+    else {
+        return Ok(None);
+    };
+
+    let path = handle.path().to_path_buf();
+    std::fs::write(&path, &file.data)
+        .map_err(|e| format!("无法写入文件 {}: {}", path.display(), e))?;
+    Ok(Some(path.display().to_string()))
+}
+
+/// 触发浏览器下载（仅 WASM）
+#[cfg(target_arch = "wasm32")]
+pub fn download(file: &ExportedFile) -> Result<(), String> {
+    use wasm_bindgen::JsCast;
+
+    let js_error = |e: wasm_bindgen::JsValue| format!("{:?}", e);
+
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(file.data.as_slice()));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(file.format.mime_type());
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options).map_err(js_error)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob).map_err(js_error)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or("no global `document` exists")?;
+    let anchor: web_sys::HtmlAnchorElement = document
+        .create_element("a")
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(|_| "anchor element has wrong type".to_string())?;
+    anchor.set_href(&url);
+    anchor.set_download(&file.name);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url).map_err(js_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_name() {
+        assert_eq!(file_name(Some("1.png"), 0, ExportFormat::Ktx2), "1.ktx2");
+        assert_eq!(file_name(Some("a.b.ktx2"), 3, ExportFormat::Png), "a.b_mip3.png");
+        assert_eq!(file_name(None, 0, ExportFormat::Png), "texture.png");
+    }
+
+    #[test]
+    fn test_png_keeps_color_space() {
+        use crate::color_space;
+
+        // 线性数据原样写入并标记 gAMA 1.0，读回后像素值和色彩空间都不变
+        let rgba = [0, 64, 128, 255, 10, 20, 30, 40];
+        for space in [ColorSpace::Linear, ColorSpace::Srgb] {
+            let png = encode(&rgba, 2, 1, ExportFormat::Png, space).unwrap();
+            assert_eq!(color_space::detect_png(&png), Some(space));
+            let decoded = image::load_from_memory(&png).unwrap().into_rgba8();
+            assert_eq!(decoded.as_raw().as_slice(), &rgba);
+        }
+
+        let png16 = encode_png16(&[0, 1000, 40000, 65535], 1, 1, ColorSpace::Linear).unwrap();
+        assert_eq!(color_space::detect_png(&png16), Some(ColorSpace::Linear));
+        let decoded = image::load_from_memory(&png16).unwrap().into_rgba16();
+        assert_eq!(decoded.as_raw().as_slice(), &[0, 1000, 40000, 65535]);
+        assert!(encode(&rgba, 3, 1, ExportFormat::Png, ColorSpace::Srgb).is_err());
+    }
+}
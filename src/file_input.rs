@@ -0,0 +1,230 @@
+//! 本地文件输入模块
+//!
+//! 原生环境：通过 iced 的 `window::Event::FileDropped` 接收拖放文件，
+//! 通过系统文件对话框选择文件
+//!
+//! WASM 环境：监听页面的 HTML 拖放事件，并使用隐藏的 `<input type=file>` 选择文件
+
+use iced::Subscription;
+
+/// 文件选择框接受的扩展名
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub const ACCEPTED_EXTENSIONS: [&str; 7] = ["ktx2", "png", "jpg", "jpeg", "bmp", "tga", "webp"];
+
+/// 隐藏文件输入框的元素 id
+#[cfg(target_arch = "wasm32")]
+const FILE_INPUT_ID: &str = "iced-file-input";
+
+/// 从本地读取到的文件
+#[derive(Debug, Clone)]
+pub struct LocalFile {
+    /// 文件名（不含目录）
+    pub name: String,
+    /// 文件内容
+    pub data: Vec<u8>,
+    /// 可重新打开的来源：原生环境为文件路径，WASM 环境为 URL
+    ///
+    /// 浏览器中拖放或选择的文件无法重新打开，为 `None`
+    pub source: Option<String>,
+}
+
+/// 读取拖放到窗口上的文件（仅原生）
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn read_path(path: std::path::PathBuf) -> Result<LocalFile, String> {
+    let data = std::fs::read(&path)
+        .map_err(|e| format!("无法读取文件 {}: {}", path.display(), e))?;
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    Ok(LocalFile {
+        name,
+        data,
+        source: Some(path.display().to_string()),
+    })
+}
+
+/// 打开系统文件对话框选择纹理（仅原生）
+///
+/// 用户取消时返回 `None`
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn pick_file() -> Option<LocalFile> {
+    let handle = rfd::AsyncFileDialog::new()
+        .add_filter("Textures", &ACCEPTED_EXTENSIONS)
+        .pick_file()
+        .await?;
+
+    Some(LocalFile {
+        name: handle.file_name(),
+        data: handle.read().await,
+        source: Some(handle.path().display().to_string()),
+    })
+}
+
+/// 重新打开保存的来源（文件路径）
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn open_source(source: String) -> Result<LocalFile, String> {
+    read_path(std::path::PathBuf::from(source)).await
+}
+
+/// 重新打开保存的来源（URL）
+#[cfg(target_arch = "wasm32")]
+pub async fn open_source(source: String) -> Result<LocalFile, String> {
+    fetch_url(source).await
+}
+
+/// 通过 fetch 下载文件（仅 WASM）
+#[cfg(target_arch = "wasm32")]
+pub async fn fetch_url(url: String) -> Result<LocalFile, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no global `window` exists")?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(&url))
+        .await
+        .map_err(|e| format!("Failed to fetch {}: {:?}", url, e))?
+        .dyn_into()
+        .map_err(|_| "response not valid".to_string())?;
+    if !response.ok() {
+        return Err(format!("Failed to fetch {}: HTTP {}", url, response.status()));
+    }
+
+    let buffer = JsFuture::from(response.array_buffer().map_err(|e| format!("{:?}", e))?)
+        .await
+        .map_err(|e| format!("Failed to read {}: {:?}", url, e))?;
+    let name = url.rsplit('/').next().unwrap_or(&url).to_string();
+
+    Ok(LocalFile {
+        name,
+        data: js_sys::Uint8Array::new(&buffer).to_vec(),
+        source: Some(url),
+    })
+}
+
+/// 打开浏览器文件选择框（仅 WASM）
+///
+/// 选中的文件通过 [`subscription`] 返回
+#[cfg(target_arch = "wasm32")]
+pub fn open_picker() {
+    use wasm_bindgen::JsCast;
+
+    let input = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(FILE_INPUT_ID))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+
+    match input {
+        Some(input) => input.click(),
+        None => log::warn!(target: crate::logging::INPUT, "File input not installed"),
+    }
+}
+
+/// 浏览器拖放和文件选择框产生的文件
+#[cfg(target_arch = "wasm32")]
+pub fn subscription() -> Subscription<Result<LocalFile, String>> {
+    Subscription::run(browser_files)
+}
+
+/// 原生环境下拖放文件由 `window::Event::FileDropped` 处理
+#[cfg(not(target_arch = "wasm32"))]
+pub fn subscription() -> Subscription<Result<LocalFile, String>> {
+    Subscription::none()
+}
+
+/// 安装 DOM 监听器，并将读取到的文件转为消息流
+#[cfg(target_arch = "wasm32")]
+fn browser_files() -> impl iced::futures::Stream<Item = Result<LocalFile, String>> {
+    use iced::futures::channel::mpsc;
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen::closure::Closure;
+
+    let (sender, receiver) = mpsc::unbounded();
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .expect("no global `document` exists");
+
+    // 阻止浏览器在新标签页中打开拖入的文件
+    let on_dragover = Closure::<dyn FnMut(web_sys::DragEvent)>::new(|event: web_sys::DragEvent| {
+        event.prevent_default();
+    });
+    let _ = document.add_event_listener_with_callback("dragover", on_dragover.as_ref().unchecked_ref());
+    on_dragover.forget();
+
+    let drop_sender = sender.clone();
+    let on_drop = Closure::<dyn FnMut(web_sys::DragEvent)>::new(move |event: web_sys::DragEvent| {
+        event.prevent_default();
+        if let Some(files) = event.data_transfer().and_then(|transfer| transfer.files()) {
+            read_file_list(&files, &drop_sender);
+        }
+    });
+    let _ = document.add_event_listener_with_callback("drop", on_drop.as_ref().unchecked_ref());
+    on_drop.forget();
+
+    // 隐藏的文件选择框，由 open_picker() 触发
+    if let Some(old) = document.get_element_by_id(FILE_INPUT_ID) {
+        old.remove();
+    }
+    let input: web_sys::HtmlInputElement = document
+        .create_element("input")
+        .expect("failed to create input element")
+        .dyn_into()
+        .expect("input element has wrong type");
+    input.set_type("file");
+    input.set_id(FILE_INPUT_ID);
+    input.set_hidden(true);
+    input.set_accept(
+        &ACCEPTED_EXTENSIONS
+            .iter()
+            .map(|ext| format!(".{}", ext))
+            .collect::<Vec<_>>()
+            .join(","),
+    );
+
+    let change_input = input.clone();
+    let on_change = Closure::<dyn FnMut(web_sys::Event)>::new(move |_event: web_sys::Event| {
+        if let Some(files) = change_input.files() {
+            read_file_list(&files, &sender);
+        }
+        // 清空选择，再次选择同一个文件时也能触发 change
+        change_input.set_value("");
+    });
+    let _ = input.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+    on_change.forget();
+
+    if let Some(body) = document.body() {
+        let _ = body.append_child(&input);
+    }
+
+    receiver
+}
+
+/// 异步读取 FileList 中的每个文件
+#[cfg(target_arch = "wasm32")]
+fn read_file_list(
+    files: &web_sys::FileList,
+    sender: &iced::futures::channel::mpsc::UnboundedSender<Result<LocalFile, String>>,
+) {
+    use wasm_bindgen_futures::JsFuture;
+
+    for index in 0..files.length() {
+        let Some(file) = files.get(index) else {
+            continue;
+        };
+        let sender = sender.clone();
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let name = file.name();
+            let result = match JsFuture::from(file.array_buffer()).await {
+                Ok(buffer) => Ok(LocalFile {
+                    name,
+                    data: js_sys::Uint8Array::new(&buffer).to_vec(),
+                    source: None,
+                }),
+                Err(e) => Err(format!("Failed to read {}: {:?}", name, e)),
+            };
+            let _ = sender.unbounded_send(result);
+        });
+    }
+}
@@ -0,0 +1,357 @@
+//! 纹理画廊模块
+//!
+//! 原生环境扫描本地目录，WASM 环境从服务器获取 JSON 清单，
+//! 以缩略图网格列出所有纹理。缩略图只在条目滚动到可见区域时才生成
+//!
+//! 启动时读取的资源清单（见 `assets` 模块）也用画廊展示，每个纹理只列出选中的变体
+
+use std::ops::Range;
+
+use iced::widget::image::Handle;
+use serde::Deserialize;
+
+use crate::assets::{self, AssetManifest, Capabilities};
+use crate::file_input;
+use crate::logging;
+use crate::texture::TextureLoader;
+
+/// 缩略图最长边（像素）
+pub const THUMBNAIL_SIZE: u32 = 96;
+/// 每行显示的缩略图数
+pub const COLUMNS: usize = 5;
+/// 每个网格单元的高度（缩略图 + 名称 + 间距）
+pub const TILE_HEIGHT: f32 = 140.0;
+
+/// WASM 环境下的画廊清单地址
+#[cfg(target_arch = "wasm32")]
+pub const MANIFEST_URL: &str = "gallery.json";
+
+/// 资源清单的来源，变体路径相对清单所在目录
+#[cfg(target_arch = "wasm32")]
+pub const ASSET_MANIFEST: &str = "assets.json";
+#[cfg(not(target_arch = "wasm32"))]
+pub const ASSET_MANIFEST: &str = "public/assets.json";
+
+/// 清单中的一项
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ManifestEntry {
+    /// 显示名称，缺省时使用文件名
+    #[serde(default)]
+    pub name: Option<String>,
+    /// 相对于页面的 URL
+    pub path: String,
+}
+
+/// 缩略图生成结果：(宽, 高, RGBA8) 或错误信息
+pub type ThumbnailResult = Result<(u32, u32, Vec<u8>), String>;
+
+/// 缩略图状态
+#[derive(Debug, Clone)]
+pub enum Thumbnail {
+    /// 尚未请求
+    Pending,
+    /// 正在生成
+    Loading,
+    Ready(Handle),
+    /// 生成失败，保存错误信息用于提示
+    Failed(String),
+}
+
+/// 画廊中的一个纹理
+#[derive(Debug, Clone)]
+pub struct GalleryEntry {
+    pub name: String,
+    /// 可重新打开的来源，含义同 `LocalFile::source`
+    pub source: String,
+    pub thumbnail: Thumbnail,
+}
+
+/// 画廊内容
+#[derive(Debug, Clone)]
+pub struct Gallery {
+    /// 目录路径或清单地址
+    pub title: String,
+    entries: Vec<GalleryEntry>,
+}
+
+impl Gallery {
+    /// 由 (名称, 来源) 列表创建画廊
+    pub fn new(title: String, items: Vec<(String, String)>) -> Self {
+        let entries = items
+            .into_iter()
+            .map(|(name, source)| GalleryEntry {
+                name,
+                source,
+                thumbnail: Thumbnail::Pending,
+            })
+            .collect();
+        Self { title, entries }
+    }
+
+    pub fn entries(&self) -> &[GalleryEntry] {
+        &self.entries
+    }
+
+    /// 滚动位置对应的可见条目范围（多算一行作为预加载）
+    pub fn visible_range(&self, scroll_y: f32, viewport_height: f32) -> Range<usize> {
+        let first_row = (scroll_y.max(0.0) / TILE_HEIGHT).floor() as usize;
+        let last_row = ((scroll_y.max(0.0) + viewport_height) / TILE_HEIGHT).ceil() as usize + 1;
+        let start = (first_row * COLUMNS).min(self.entries.len());
+        let end = (last_row * COLUMNS).min(self.entries.len());
+        start..end
+    }
+
+    /// 取出范围内尚未请求的缩略图，并标记为正在生成
+    pub fn request_thumbnails(&mut self, range: Range<usize>) -> Vec<String> {
+        self.entries[range]
+            .iter_mut()
+            .filter(|entry| matches!(entry.thumbnail, Thumbnail::Pending))
+            .map(|entry| {
+                entry.thumbnail = Thumbnail::Loading;
+                entry.source.clone()
+            })
+            .collect()
+    }
+
+    /// 缩略图生成完成
+    pub fn set_thumbnail(&mut self, source: &str, result: ThumbnailResult) {
+        let thumbnail = match result {
+            Ok((width, height, pixels)) => Thumbnail::Ready(Handle::from_rgba(width, height, pixels)),
+            Err(e) => Thumbnail::Failed(e),
+        };
+        for entry in self.entries.iter_mut().filter(|entry| entry.source == source) {
+            entry.thumbnail = thumbnail.clone();
+        }
+    }
+
+    pub fn source(&self, index: usize) -> Option<&str> {
+        self.entries.get(index).map(|entry| entry.source.as_str())
+    }
+}
+
+/// 解码纹理并缩小为缩略图，返回 (宽, 高, RGBA8)
+pub fn make_thumbnail(bytes: &[u8]) -> ThumbnailResult {
+    let mut loader = TextureLoader::new();
+    loader.load_from_bytes(bytes)?;
+    let (width, height) = loader.dimensions().ok_or("Texture has no dimensions")?;
+    let rgba = loader.display_data().ok_or("Texture has no data")?;
+
+    let img = image::RgbaImage::from_raw(width, height, rgba.into_owned())
+        .ok_or("Pixel data does not match dimensions")?;
+    let scale = THUMBNAIL_SIZE as f32 / width.max(height) as f32;
+    if scale >= 1.0 {
+        return Ok((width, height, img.into_raw()));
+    }
+
+    let thumb_w = ((width as f32 * scale).round() as u32).max(1);
+    let thumb_h = ((height as f32 * scale).round() as u32).max(1);
+    let thumb = image::imageops::thumbnail(&img, thumb_w, thumb_h);
+    Ok((thumb_w, thumb_h, thumb.into_raw()))
+}
+
+/// 读取来源并生成缩略图
+pub async fn load_thumbnail(source: String) -> (String, ThumbnailResult) {
+    let result = match file_input::open_source(source.clone()).await {
+        Ok(file) => make_thumbnail(&file.data),
+        Err(e) => Err(e),
+    };
+    (source, result)
+}
+
+/// 选择目录并列出其中的纹理（仅原生）
+///
+/// 用户取消时返回 `None`
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn open() -> Option<Result<Gallery, String>> {
+    let folder = rfd::AsyncFileDialog::new().pick_folder().await?;
+    Some(scan_directory(folder.path()))
+}
+
+/// 获取服务器上的画廊清单（仅 WASM）
+#[cfg(target_arch = "wasm32")]
+pub async fn open() -> Option<Result<Gallery, String>> {
+    Some(fetch_manifest(MANIFEST_URL).await)
+}
+
+/// 读取资源清单，按 `capabilities` 为每个纹理选择变体
+///
+/// 清单不存在（或无法下载）时返回 `None`
+pub async fn open_assets(capabilities: Capabilities) -> Option<Result<Gallery, String>> {
+    let file = match file_input::open_source(ASSET_MANIFEST.to_string()).await {
+        Ok(file) => file,
+        Err(e) => {
+            log::debug!(target: logging::TEXTURE, "No asset manifest: {}", e);
+            return None;
+        }
+    };
+    Some(assets::parse(&file.data).map(|manifest| {
+        Gallery::new(
+            ASSET_MANIFEST.to_string(),
+            asset_items(&manifest, &capabilities, ASSET_MANIFEST),
+        )
+    }))
+}
+
+/// 每个纹理取第一个受支持的变体，返回 (名称, 来源)，没有受支持变体的纹理被跳过
+pub fn asset_items(manifest: &AssetManifest, capabilities: &Capabilities, manifest_source: &str) -> Vec<(String, String)> {
+    let base = manifest_source.rsplit_once('/').map_or("", |(dir, _)| dir);
+    manifest
+        .textures
+        .iter()
+        .filter_map(|asset| {
+            let Some(variant) = asset.best_variant(capabilities) else {
+                log::warn!(target: logging::TEXTURE, "No supported variant for {}", asset.name);
+                return None;
+            };
+            let source = match base {
+                "" => variant.path.clone(),
+                _ => format!("{}/{}", base, variant.path),
+            };
+            Some((format!("{} ({})", asset.name, variant.name), source))
+        })
+        .collect()
+}
+
+/// 列出目录中扩展名可识别的纹理，按文件名排序
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory(dir: &std::path::Path) -> Result<Gallery, String> {
+    let read_dir = std::fs::read_dir(dir).map_err(|e| format!("无法读取目录 {}: {}", dir.display(), e))?;
+
+    let mut items: Vec<(String, String)> = read_dir
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && has_accepted_extension(path))
+        .map(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            (name, path.display().to_string())
+        })
+        .collect();
+    items.sort();
+
+    Ok(Gallery::new(dir.display().to_string(), items))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn has_accepted_extension(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            file_input::ACCEPTED_EXTENSIONS
+                .iter()
+                .any(|accepted| accepted.eq_ignore_ascii_case(ext))
+        })
+}
+
+/// 下载并解析 JSON 清单
+#[cfg(target_arch = "wasm32")]
+async fn fetch_manifest(url: &str) -> Result<Gallery, String> {
+    let file = file_input::fetch_url(url.to_string()).await?;
+    let entries = parse_manifest(&file.data)?;
+    Ok(Gallery::new(url.to_string(), entries))
+}
+
+/// 解析清单：`[{"name": "...", "path": "..."}, ...]`
+#[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+pub fn parse_manifest(json: &[u8]) -> Result<Vec<(String, String)>, String> {
+    let entries: Vec<ManifestEntry> =
+        serde_json::from_slice(json).map_err(|e| format!("Invalid gallery manifest: {}", e))?;
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let name = entry
+                .name
+                .unwrap_or_else(|| entry.path.rsplit('/').next().unwrap_or(&entry.path).to_string());
+            (name, entry.path)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lazy_thumbnail_requests() {
+        let items = (0..23).map(|i| (format!("{}.png", i), format!("{}.png", i))).collect();
+        let mut gallery = Gallery::new("test".to_string(), items);
+
+        // 一屏两行 + 一行预加载
+        let range = gallery.visible_range(0.0, TILE_HEIGHT * 2.0);
+        assert_eq!(range, 0..15);
+        assert_eq!(gallery.request_thumbnails(range.clone()).len(), 15);
+        assert!(gallery.request_thumbnails(range).is_empty());
+
+        let range = gallery.visible_range(TILE_HEIGHT * 3.5, TILE_HEIGHT);
+        assert_eq!(range, 15..23);
+    }
+
+    #[test]
+    fn test_block_compressed_thumbnail() {
+        use iced_web_app::ktx2_writer::{Ktx2Writer, PixelFormat};
+
+        // 本机适配器支持 BC 时资源清单会选中 BC 变体，缩略图也要能在 CPU 上解码
+        let red_block = [0x00, 0xf8, 0x00, 0x00, 0, 0, 0, 0];
+        let ktx2 = Ktx2Writer::new(8, 8, PixelFormat::Bc1, false)
+            .level(red_block.repeat(4))
+            .write()
+            .unwrap();
+        let (width, height, rgba) = make_thumbnail(&ktx2).unwrap();
+        assert_eq!((width, height), (8, 8));
+        assert!(rgba.chunks_exact(4).all(|texel| texel == [255, 0, 0, 255]));
+    }
+
+    #[test]
+    fn test_asset_items() {
+        let manifest = assets::parse(
+            br#"{ "textures": [
+                { "name": "crate", "source": "crate.png", "variants": [
+                    { "name": "bc", "path": "textures/crate.bc.ktx2", "width": 4, "height": 4, "levels": 1,
+                      "format": "BC7_SRGB_BLOCK", "compression": "bc", "supercompression": "None",
+                      "platforms": ["native", "web"], "size": 100, "hash": "0" },
+                    { "name": "default", "path": "textures/crate.ktx2", "width": 4, "height": 4, "levels": 1,
+                      "format": "R8G8B8A8_SRGB", "compression": null, "supercompression": "None",
+                      "platforms": ["native", "web"], "size": 200, "hash": "1" }
+                ] },
+                { "name": "sky", "source": "sky.png", "variants": [
+                    { "name": "default", "path": "textures/sky.ktx2", "width": 4, "height": 4, "levels": 1,
+                      "format": "R8G8B8A8_SRGB", "compression": null, "supercompression": "Zstandard",
+                      "platforms": ["native"], "size": 50, "hash": "2" }
+                ] }
+            ] }"#,
+        )
+        .unwrap();
+        let web = Capabilities {
+            platform: assets::Platform::Web,
+            compression: Vec::new(),
+        };
+
+        assert_eq!(
+            asset_items(&manifest, &web, "assets.json"),
+            vec![("crate (default)".to_string(), "textures/crate.ktx2".to_string())]
+        );
+        let native = Capabilities {
+            platform: assets::Platform::Native,
+            compression: vec![assets::Compression::Bc],
+        };
+        assert_eq!(
+            asset_items(&manifest, &native, "public/assets.json")[0].1,
+            "public/textures/crate.bc.ktx2"
+        );
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let entries = parse_manifest(br#"[{"path": "textures/a.ktx2"}, {"name": "B", "path": "b.png"}]"#).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ("a.ktx2".to_string(), "textures/a.ktx2".to_string()),
+                ("B".to_string(), "b.png".to_string()),
+            ]
+        );
+    }
+}
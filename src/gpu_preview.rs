@@ -0,0 +1,645 @@
+//! GPU 纹理预览模块
+//!
+//! 通过 iced 的 shader 控件把纹理作为真正的 wgpu 纹理上传并采样：
+//! - 设备支持时直接上传 BC / ETC2 / ASTC 压缩数据
+//! - 否则退回 CPU 解码后的 RGBA8（BC、ETC2、ASTC 由 `decode` 模块解码），并在 CPU 上生成完整的 mip 链
+//!
+//! 采样走硬件过滤和 mip 选择，看到的就是 GPU 实际采样的结果
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use iced::wgpu;
+use iced::widget::shader::{self, Viewport};
+use iced::{Rectangle, mouse};
+
+use crate::assets::Compression;
+use crate::color_space::{self, ColorSpace};
+use crate::decode;
+use crate::logging;
+use crate::texture::TextureLoader;
+use crate::viewer::{self, ViewerState};
+
+/// 统一缓冲区大小：4 个 vec4<f32>
+const UNIFORMS_SIZE: u64 = 64;
+
+/// 纹理编号，用于判断是否需要重新上传
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// 一组 mip 层级数据，下标 0 为最大层级
+#[derive(Debug, Clone)]
+struct MipChain {
+    format: wgpu::TextureFormat,
+    levels: Vec<Vec<u8>>,
+}
+
+/// 等待上传到 GPU 的纹理
+#[derive(Debug)]
+pub struct GpuTexture {
+    id: u64,
+    width: u32,
+    height: u32,
+    /// KTX2 中原样存储的数据（压缩格式或 RGBA8）
+    native: Option<MipChain>,
+    /// 原生格式不可用时使用的 RGBA8 数据
+    fallback: Option<MipChain>,
+}
+
+impl GpuTexture {
+    /// 由 KTX2 文件创建，`decoded` 为 CPU 解码结果（用作后备）
+    ///
+    /// 没有传入解码结果时，压缩格式在这里用 CPU 解码第 0 层级，设备缺少对应特性时仍能显示
+    pub fn from_ktx2(bytes: &[u8], decoded: Option<&[u8]>) -> Result<Self, String> {
+        let reader = ktx2::Reader::new(bytes).map_err(|e| format!("Failed to create KTX2 reader: {:?}", e))?;
+        let header = reader.header();
+        let width = header.pixel_width;
+        let height = header.pixel_height.max(1);
+        let color_space = color_space::detect_ktx2(bytes).unwrap_or_default();
+
+        // 超级压缩的数据需要先解压，不能直接上传
+        let native = match (header.format.and_then(wgpu_format), header.supercompression_scheme) {
+            (Some(format), None) => {
+                let levels = reader
+                    .levels()
+                    .take(viewer::mip_count(width, height) as usize)
+                    .enumerate()
+                    .map(|(level, data)| first_slice(data.data, format, width, height, level as u32))
+                    .collect::<Option<Vec<_>>>();
+                levels
+                    .filter(|levels| !levels.is_empty())
+                    .map(|levels| MipChain { format, levels })
+            }
+            _ => None,
+        };
+        // 旧版 ktx2_generator 的层级索引位置不对，未压缩数据与 CPU 解码结果不一致时以后者为准
+        let native = native.filter(|chain| {
+            !chain.format.required_features().is_empty() || decoded.is_none_or(|rgba| chain.levels[0] == rgba)
+        });
+
+        // RGBA8 数据本身就能直接上传，只有压缩格式才需要后备
+        let needs_fallback = native
+            .as_ref()
+            .is_none_or(|chain| !chain.format.required_features().is_empty());
+        let decoded = match decoded {
+            Some(rgba) => Some(Cow::Borrowed(rgba)),
+            None if needs_fallback => decode_base_level(bytes).map(Cow::Owned),
+            None => None,
+        };
+        let fallback = match decoded {
+            Some(rgba) if needs_fallback => Some(rgba8_mip_chain(&rgba, width, height, color_space)),
+            _ => None,
+        };
+
+        if native.is_none() && fallback.is_none() {
+            return Err(format!("Unsupported KTX2 format for GPU preview: {:?}", header.format));
+        }
+
+        Ok(Self::new(width, height, native, fallback))
+    }
+
+    /// 由 CPU 解码后的 RGBA8 图像创建，在 CPU 上生成 mip 链
+    pub fn from_rgba(rgba: &[u8], width: u32, height: u32, color_space: ColorSpace) -> Self {
+        Self::new(width, height, None, Some(rgba8_mip_chain(rgba, width, height, color_space)))
+    }
+
+    fn new(width: u32, height: u32, native: Option<MipChain>, fallback: Option<MipChain>) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            width,
+            height,
+            native,
+            fallback,
+        }
+    }
+
+    /// 可用的 mip 层级数
+    pub fn level_count(&self) -> u32 {
+        self.native
+            .iter()
+            .chain(&self.fallback)
+            .map(|chain| chain.levels.len() as u32)
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// 状态栏文本
+    pub fn status(&self) -> String {
+        match (&self.native, &self.fallback) {
+            (Some(native), Some(_)) => format!(
+                "GPU: {:?} ({} levels), RGBA8 fallback if unsupported",
+                native.format,
+                native.levels.len()
+            ),
+            (Some(native), None) => format!("GPU: {:?} ({} levels)", native.format, native.levels.len()),
+            (None, Some(fallback)) => format!("GPU: {:?} (CPU decoded, {} levels)", fallback.format, fallback.levels.len()),
+            (None, None) => "GPU: no data".to_string(),
+        }
+    }
+
+    /// 按设备支持的特性选择要上传的数据
+    ///
+    /// 压缩纹理的基础尺寸必须是块尺寸的整数倍，否则也使用后备数据
+    fn select(&self, features: wgpu::Features) -> Option<&MipChain> {
+        self.native
+            .as_ref()
+            .filter(|chain| {
+                let (block_width, block_height) = chain.format.block_dimensions();
+                features.contains(chain.format.required_features())
+                    && self.width.is_multiple_of(block_width)
+                    && self.height.is_multiple_of(block_height)
+            })
+            .or(self.fallback.as_ref())
+    }
+}
+
+/// shader 控件使用的预览程序
+#[derive(Debug, Clone)]
+pub struct Preview {
+    texture: Arc<GpuTexture>,
+    /// 可见区域（归一化纹理坐标）：x, y, 宽, 高
+    uv_rect: [f32; 4],
+    channels: [bool; 4],
+    mip_level: u32,
+    /// 是否由 GPU 根据屏幕尺寸自动选择 mip 层级
+    auto_lod: bool,
+}
+
+impl Preview {
+    pub fn new(texture: Arc<GpuTexture>, viewer: &ViewerState, auto_lod: bool) -> Self {
+        let (width, height) = (texture.width.max(1), texture.height.max(1));
+        let crop = viewer.crop_region(width, height);
+        Self {
+            uv_rect: [
+                crop.x as f32 / width as f32,
+                crop.y as f32 / height as f32,
+                crop.width as f32 / width as f32,
+                crop.height as f32 / height as f32,
+            ],
+            channels: viewer.channels(),
+            mip_level: viewer.mip_level().min(texture.level_count() - 1),
+            auto_lod,
+            texture,
+        }
+    }
+
+    /// 可见区域的宽高比（宽 / 高）
+    pub fn aspect_ratio(&self) -> f32 {
+        let width = self.uv_rect[2] * self.texture.width as f32;
+        let height = self.uv_rect[3] * self.texture.height as f32;
+        width / height.max(1.0)
+    }
+}
+
+impl<Message> shader::Program<Message> for Preview {
+    type State = ();
+    type Primitive = Primitive;
+
+    fn draw(&self, _state: &Self::State, _cursor: mouse::Cursor, bounds: Rectangle) -> Self::Primitive {
+        // 保持宽高比，多余部分留空
+        let bounds_ratio = bounds.width / bounds.height.max(1.0);
+        let ratio = self.aspect_ratio();
+        let letterbox = if bounds_ratio > ratio {
+            [ratio / bounds_ratio, 1.0]
+        } else {
+            [1.0, bounds_ratio / ratio]
+        };
+
+        Primitive {
+            preview: self.clone(),
+            letterbox,
+        }
+    }
+}
+
+/// 一帧的绘制参数
+#[derive(Debug)]
+pub struct Primitive {
+    preview: Preview,
+    letterbox: [f32; 2],
+}
+
+impl Primitive {
+    fn uniforms(&self, target_srgb: bool) -> Vec<u8> {
+        let preview = &self.preview;
+        let flag = |value: bool| if value { 1.0 } else { 0.0 };
+        let channels = preview.channels.map(flag);
+        let values: [f32; 16] = [
+            preview.uv_rect[0],
+            preview.uv_rect[1],
+            preview.uv_rect[2],
+            preview.uv_rect[3],
+            channels[0],
+            channels[1],
+            channels[2],
+            channels[3],
+            preview.mip_level as f32,
+            flag(preview.auto_lod),
+            flag(target_srgb),
+            0.0,
+            self.letterbox[0],
+            self.letterbox[1],
+            0.0,
+            0.0,
+        ];
+        values.iter().flat_map(|value| value.to_le_bytes()).collect()
+    }
+}
+
+impl shader::Primitive for Primitive {
+    type Pipeline = Pipeline;
+
+    fn prepare(
+        &self,
+        pipeline: &mut Self::Pipeline,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        _bounds: &Rectangle,
+        _viewport: &Viewport,
+    ) {
+        let texture = &self.preview.texture;
+        if pipeline.uploaded.as_ref().is_none_or(|uploaded| uploaded.id != texture.id) {
+            pipeline.uploaded = pipeline.upload(device, queue, texture);
+        }
+
+        if pipeline.uploaded.is_some() {
+            queue.write_buffer(&pipeline.uniforms, 0, &self.uniforms(pipeline.target_srgb));
+        }
+    }
+
+    fn draw(&self, pipeline: &Self::Pipeline, render_pass: &mut wgpu::RenderPass<'_>) -> bool {
+        let Some(uploaded) = &pipeline.uploaded else {
+            // 没有可上传的数据时什么也不画
+            return true;
+        };
+
+        render_pass.set_pipeline(&pipeline.pipeline);
+        render_pass.set_bind_group(0, &uploaded.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+        true
+    }
+}
+
+/// 已上传到 GPU 的纹理
+struct Uploaded {
+    id: u64,
+    bind_group: wgpu::BindGroup,
+    _texture: wgpu::Texture,
+}
+
+/// 预览渲染管线，所有预览控件共享
+pub struct Pipeline {
+    pipeline: wgpu::RenderPipeline,
+    bind_group_layout: wgpu::BindGroupLayout,
+    sampler: wgpu::Sampler,
+    uniforms: wgpu::Buffer,
+    /// 渲染目标是否为 sRGB 格式（写入时由硬件编码）
+    target_srgb: bool,
+    uploaded: Option<Uploaded>,
+}
+
+impl shader::Pipeline for Pipeline {
+    fn new(device: &wgpu::Device, _queue: &wgpu::Queue, format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("gpu_preview.shader"),
+            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("gpu_preview.wgsl"))),
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("gpu_preview.bind_group_layout"),
+            entries: &[
+                wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Texture {
+                        sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                        view_dimension: wgpu::TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("gpu_preview.pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("gpu_preview.pipeline"),
+            layout: Some(&layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_main"),
+                buffers: &[],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_main"),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("gpu_preview.sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..wgpu::SamplerDescriptor::default()
+        });
+
+        let uniforms = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("gpu_preview.uniforms"),
+            size: UNIFORMS_SIZE,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            pipeline,
+            bind_group_layout,
+            sampler,
+            uniforms,
+            target_srgb: format.is_srgb(),
+            uploaded: None,
+        }
+    }
+}
+
+impl Pipeline {
+    /// 创建 wgpu 纹理并写入全部 mip 层级
+    fn upload(&self, device: &wgpu::Device, queue: &wgpu::Queue, texture: &GpuTexture) -> Option<Uploaded> {
+        let Some(chain) = texture.select(device.features()) else {
+            log::error!(target: logging::TEXTURE, "GPU preview: no uploadable data for this device");
+            return None;
+        };
+        let native = texture.native.as_ref().is_some_and(|native| std::ptr::eq(native, chain));
+        log::info!(target: logging::TEXTURE,
+            "GPU preview: uploading {:?} ({} levels, {})",
+            chain.format,
+            chain.levels.len(),
+            if native { "native" } else { "CPU decoded" });
+
+        let gpu_texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("gpu_preview.texture"),
+            size: wgpu::Extent3d {
+                width: texture.width,
+                height: texture.height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: chain.levels.len() as u32,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: chain.format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+
+        let (block_width, block_height) = chain.format.block_dimensions();
+        let block_size = chain.format.block_copy_size(None).unwrap_or(4);
+        for (level, data) in chain.levels.iter().enumerate() {
+            let width = decode::level_extent(texture.width, level as u32);
+            let height = decode::level_extent(texture.height, level as u32);
+            let blocks_x = width.div_ceil(block_width);
+            let blocks_y = height.div_ceil(block_height);
+
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &gpu_texture,
+                    mip_level: level as u32,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                data,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(blocks_x * block_size),
+                    rows_per_image: Some(blocks_y),
+                },
+                // 压缩格式按整块复制
+                wgpu::Extent3d {
+                    width: blocks_x * block_width,
+                    height: blocks_y * block_height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        let view = gpu_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("gpu_preview.bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.uniforms.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
+        Some(Uploaded {
+            id: texture.id,
+            bind_group,
+            _texture: gpu_texture,
+        })
+    }
+}
+
+/// KTX2 vkFormat 对应的 wgpu 格式，不支持时返回 `None`
+pub fn wgpu_format(format: ktx2::Format) -> Option<wgpu::TextureFormat> {
+    use ktx2::Format as K;
+    use wgpu::{AstcBlock, AstcChannel, TextureFormat as W};
+
+    let astc = |block, srgb| W::Astc {
+        block,
+        channel: if srgb { AstcChannel::UnormSrgb } else { AstcChannel::Unorm },
+    };
+
+    Some(match format {
+        K::R8G8B8A8_UNORM => W::Rgba8Unorm,
+        K::R8G8B8A8_SRGB => W::Rgba8UnormSrgb,
+        K::BC1_RGB_UNORM_BLOCK | K::BC1_RGBA_UNORM_BLOCK => W::Bc1RgbaUnorm,
+        K::BC1_RGB_SRGB_BLOCK | K::BC1_RGBA_SRGB_BLOCK => W::Bc1RgbaUnormSrgb,
+        K::BC3_UNORM_BLOCK => W::Bc3RgbaUnorm,
+        K::BC3_SRGB_BLOCK => W::Bc3RgbaUnormSrgb,
+        K::BC4_UNORM_BLOCK => W::Bc4RUnorm,
+        K::BC5_UNORM_BLOCK => W::Bc5RgUnorm,
+        K::BC7_UNORM_BLOCK => W::Bc7RgbaUnorm,
+        K::BC7_SRGB_BLOCK => W::Bc7RgbaUnormSrgb,
+        K::ETC2_R8G8B8_UNORM_BLOCK => W::Etc2Rgb8Unorm,
+        K::ETC2_R8G8B8_SRGB_BLOCK => W::Etc2Rgb8UnormSrgb,
+        K::ETC2_R8G8B8A1_UNORM_BLOCK => W::Etc2Rgb8A1Unorm,
+        K::ETC2_R8G8B8A1_SRGB_BLOCK => W::Etc2Rgb8A1UnormSrgb,
+        K::ETC2_R8G8B8A8_UNORM_BLOCK => W::Etc2Rgba8Unorm,
+        K::ETC2_R8G8B8A8_SRGB_BLOCK => W::Etc2Rgba8UnormSrgb,
+        K::ASTC_4x4_UNORM_BLOCK => astc(AstcBlock::B4x4, false),
+        K::ASTC_4x4_SRGB_BLOCK => astc(AstcBlock::B4x4, true),
+        K::ASTC_6x6_UNORM_BLOCK => astc(AstcBlock::B6x6, false),
+        K::ASTC_6x6_SRGB_BLOCK => astc(AstcBlock::B6x6, true),
+        K::ASTC_8x8_UNORM_BLOCK => astc(AstcBlock::B8x8, false),
+        K::ASTC_8x8_SRGB_BLOCK => astc(AstcBlock::B8x8, true),
+        _ => return None,
+    })
+}
+
+/// 设备支持的压缩格式族，用于选择资源清单中的变体
+///
+/// 原生环境探测默认适配器的功能
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn supported_compression() -> Vec<Compression> {
+    let instance = wgpu::Instance::default();
+    match instance.request_adapter(&wgpu::RequestAdapterOptions::default()).await {
+        Ok(adapter) => compression_families(adapter.features()),
+        Err(e) => {
+            log::warn!(target: logging::TEXTURE, "No GPU adapter to probe: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// 设备支持的压缩格式族，用于选择资源清单中的变体
+///
+/// WASM 环境在临时画布上查询 WebGL2 压缩纹理扩展，与 wgpu WebGL 后端的判断一致
+#[cfg(target_arch = "wasm32")]
+pub async fn supported_compression() -> Vec<Compression> {
+    use wasm_bindgen::JsCast;
+
+    let context = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.create_element("canvas").ok())
+        .and_then(|canvas| canvas.dyn_into::<web_sys::HtmlCanvasElement>().ok())
+        .and_then(|canvas| canvas.get_context("webgl2").ok().flatten())
+        .and_then(|context| context.dyn_into::<web_sys::WebGl2RenderingContext>().ok());
+    let Some(context) = context else {
+        log::warn!(target: logging::TEXTURE, "WebGL2 unavailable, assuming no texture compression");
+        return Vec::new();
+    };
+
+    let has = |extension: &str| context.get_extension(extension).ok().flatten().is_some();
+    let mut features = wgpu::Features::empty();
+    if has("WEBGL_compressed_texture_s3tc")
+        && has("EXT_texture_compression_rgtc")
+        && has("EXT_texture_compression_bptc")
+    {
+        features |= wgpu::Features::TEXTURE_COMPRESSION_BC;
+    }
+    if has("WEBGL_compressed_texture_etc") {
+        features |= wgpu::Features::TEXTURE_COMPRESSION_ETC2;
+    }
+    if has("WEBGL_compressed_texture_astc") {
+        features |= wgpu::Features::TEXTURE_COMPRESSION_ASTC;
+    }
+    compression_families(features)
+}
+
+/// wgpu 功能对应的压缩格式族
+fn compression_families(features: wgpu::Features) -> Vec<Compression> {
+    [
+        (wgpu::Features::TEXTURE_COMPRESSION_BC, Compression::Bc),
+        (wgpu::Features::TEXTURE_COMPRESSION_ETC2, Compression::Etc2),
+        (wgpu::Features::TEXTURE_COMPRESSION_ASTC, Compression::Astc),
+    ]
+    .into_iter()
+    .filter(|(feature, _)| features.contains(*feature))
+    .map(|(_, compression)| compression)
+    .collect()
+}
+
+/// 取出层级中第一个数组层/面的数据，长度不足时返回 `None`
+fn first_slice(data: &[u8], format: wgpu::TextureFormat, width: u32, height: u32, level: u32) -> Option<Vec<u8>> {
+    let (block_width, block_height) = format.block_dimensions();
+    let block_size = format.block_copy_size(None)? as usize;
+    let blocks_x = decode::level_extent(width, level).div_ceil(block_width) as usize;
+    let blocks_y = decode::level_extent(height, level).div_ceil(block_height) as usize;
+    data.get(..blocks_x * blocks_y * block_size).map(<[u8]>::to_vec)
+}
+
+/// 用 CPU 解码第 0 层级的第一张图像（与检查器使用同一个加载器），不支持的格式返回 `None`
+fn decode_base_level(bytes: &[u8]) -> Option<Vec<u8>> {
+    let mut loader = TextureLoader::new();
+    loader.load_from_ktx2_bytes(bytes).ok()?;
+    loader.data().map(<[u8]>::to_vec)
+}
+
+/// 在 CPU 上逐级缩小生成 RGBA8 mip 链
+fn rgba8_mip_chain(rgba: &[u8], width: u32, height: u32, color_space: ColorSpace) -> MipChain {
+    let mut levels = vec![rgba.to_vec()];
+    let (mut level_width, mut level_height) = (width, height);
+    for _ in 1..viewer::mip_count(width, height) {
+        let (next_width, next_height, pixels) =
+            viewer::downsample(levels.last().unwrap(), level_width, level_height, 1);
+        levels.push(pixels);
+        (level_width, level_height) = (next_width, next_height);
+    }
+
+    MipChain {
+        format: if color_space.is_srgb() {
+            wgpu::TextureFormat::Rgba8UnormSrgb
+        } else {
+            wgpu::TextureFormat::Rgba8Unorm
+        },
+        levels,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fallback_used_without_compression_features() {
+        let chain = rgba8_mip_chain(&[255u8; 8 * 4 * 4], 8, 4, ColorSpace::Srgb);
+        assert_eq!(chain.levels.iter().map(Vec::len).collect::<Vec<_>>(), vec![128, 32, 8, 4]);
+
+        let texture = GpuTexture::new(
+            8,
+            4,
+            Some(MipChain {
+                format: wgpu::TextureFormat::Bc7RgbaUnormSrgb,
+                levels: vec![vec![0; 2 * 16]],
+            }),
+            Some(chain),
+        );
+        let selected = texture.select(wgpu::Features::empty()).unwrap();
+        assert_eq!(selected.format, wgpu::TextureFormat::Rgba8UnormSrgb);
+        let selected = texture.select(wgpu::Features::TEXTURE_COMPRESSION_BC).unwrap();
+        assert_eq!(selected.format, wgpu::TextureFormat::Bc7RgbaUnormSrgb);
+    }
+}
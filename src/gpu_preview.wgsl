@@ -0,0 +1,73 @@
+// GPU 纹理预览着色器
+//
+// 用一个覆盖整个视口的三角形绘制纹理，按 uv_rect 选择可见区域，
+// 按 letterbox 保持宽高比，并模拟 CPU 预览的通道开关
+
+struct Uniforms {
+    // 可见区域：xy 为左上角，zw 为宽高（归一化纹理坐标）
+    uv_rect: vec4<f32>,
+    // RGBA 通道开关（1.0 显示，0.0 隐藏）
+    channels: vec4<f32>,
+    // x: mip 层级，y: 是否自动选择 mip，z: 渲染目标是否为 sRGB 格式
+    params: vec4<f32>,
+    // xy: 图像占控件的比例（letterbox）
+    letterbox: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var source: texture_2d<f32>;
+@group(0) @binding(2) var source_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let xy = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+
+    var out: VertexOutput;
+    out.position = vec4<f32>(xy * 2.0 - 1.0, 0.0, 1.0);
+    out.local = vec2<f32>(xy.x, 1.0 - xy.y);
+    return out;
+}
+
+fn srgb_encode(c: vec3<f32>) -> vec3<f32> {
+    let c0 = clamp(c, vec3<f32>(0.0), vec3<f32>(1.0));
+    return select(1.055 * pow(c0, vec3<f32>(1.0 / 2.4)) - 0.055, c0 * 12.92, c0 <= vec3<f32>(0.0031308));
+}
+
+fn srgb_decode(c: vec3<f32>) -> vec3<f32> {
+    return select(pow((c + 0.055) / 1.055, vec3<f32>(2.4)), c / 12.92, c <= vec3<f32>(0.04045));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let local = (in.local - 0.5) / uniforms.letterbox.xy + 0.5;
+    let uv = uniforms.uv_rect.xy + local * uniforms.uv_rect.zw;
+
+    // 两种采样都在统一控制流中执行，再按设置选择
+    let automatic = textureSample(source, source_sampler, uv);
+    let fixed_level = textureSampleLevel(source, source_sampler, uv, uniforms.params.x);
+    let texel = select(fixed_level, automatic, uniforms.params.y > 0.5);
+
+    if (any(local < vec2<f32>(0.0)) || any(local > vec2<f32>(1.0))) {
+        return vec4<f32>(0.0);
+    }
+
+    // 纹理格式已区分 _SRGB / _UNORM，采样结果都是线性值
+    var color: vec4<f32>;
+    if (all(uniforms.channels == vec4<f32>(0.0, 0.0, 0.0, 1.0))) {
+        // 与 CPU 预览一致：Alpha 原值作为 sRGB 编码的灰度显示
+        color = vec4<f32>(srgb_decode(texel.aaa), 1.0);
+    } else {
+        color = vec4<f32>(texel.rgb * uniforms.channels.rgb, select(1.0, texel.a, uniforms.channels.a > 0.5));
+    }
+
+    // 非 sRGB 渲染目标不会自动编码，需要手动转换
+    if (uniforms.params.z < 0.5) {
+        color = vec4<f32>(srgb_encode(color.rgb), color.a);
+    }
+    return color;
+}
@@ -0,0 +1,419 @@
+//! KTX2 写入模块
+//!
+//! 按 KTX 2.0 规范生成文件：
+//! 头部 → 层级索引 → DFD → 键值数据 → 层级数据（从最小层级到最大层级）
+//!
+//! 与 `ktx2_generator` 的旧布局不同，层级索引紧跟在 80 字节头部之后
+//!
+//! 除 RGBA8 外还可以写入已编码好的 BC1 / BC3 / BC4 / BC5 / BC7 块数据，DFD 按 KDFWS 的对应颜色模型生成
+//!
+//! 原生环境可以用 Zstandard 超级压缩每个层级（WASM 构建只带纯 Rust 的解码器，不能写入）
+
+use std::borrow::Cow;
+use std::num::NonZeroU8;
+
+use ktx2::{
+    ChannelTypeQualifiers, ColorModel, ColorPrimaries, DataFormatFlags, DfdBlockHeaderBasic,
+    DfdHeader, Format, Header, Index, LevelIndex, SampleInformation, SupercompressionScheme, TransferFunction,
+};
+
+/// 写入 KTXwriter 键的工具名称
+const WRITER_NAME: &str = concat!("iced_web_app ", env!("CARGO_PKG_VERSION"));
+
+/// KDFWS 中 Alpha 通道的编号（RGBSDA 和 BC3 颜色模型相同）
+const CHANNEL_ALPHA: u8 = 15;
+
+/// 像素数据格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PixelFormat {
+    #[default]
+    Rgba8,
+    Bc1,
+    Bc3,
+    Bc4,
+    Bc5,
+    Bc7,
+}
+
+impl PixelFormat {
+    /// 每个纹素块的宽高
+    pub fn block_size(self) -> u32 {
+        match self {
+            PixelFormat::Rgba8 => 1,
+            _ => 4,
+        }
+    }
+
+    /// 每个纹素块的字节数
+    pub fn block_bytes(self) -> usize {
+        match self {
+            PixelFormat::Rgba8 => 4,
+            PixelFormat::Bc1 | PixelFormat::Bc4 => 8,
+            PixelFormat::Bc3 | PixelFormat::Bc5 | PixelFormat::Bc7 => 16,
+        }
+    }
+
+    /// 是否有 `_SRGB` 变体（BC4 / BC5 只有 UNORM）
+    pub fn has_srgb(self) -> bool {
+        !matches!(self, PixelFormat::Bc4 | PixelFormat::Bc5)
+    }
+
+    /// 一个层级的字节数
+    pub fn level_size(self, width: u32, height: u32) -> usize {
+        let block = self.block_size();
+        width.div_ceil(block) as usize * height.div_ceil(block) as usize * self.block_bytes()
+    }
+
+    /// 对应的 vkFormat
+    pub fn vk_format(self, srgb: bool) -> Format {
+        match (self, srgb) {
+            (PixelFormat::Rgba8, true) => Format::R8G8B8A8_SRGB,
+            (PixelFormat::Rgba8, false) => Format::R8G8B8A8_UNORM,
+            (PixelFormat::Bc1, true) => Format::BC1_RGB_SRGB_BLOCK,
+            (PixelFormat::Bc1, false) => Format::BC1_RGB_UNORM_BLOCK,
+            (PixelFormat::Bc3, true) => Format::BC3_SRGB_BLOCK,
+            (PixelFormat::Bc3, false) => Format::BC3_UNORM_BLOCK,
+            (PixelFormat::Bc4, _) => Format::BC4_UNORM_BLOCK,
+            (PixelFormat::Bc5, _) => Format::BC5_UNORM_BLOCK,
+            (PixelFormat::Bc7, true) => Format::BC7_SRGB_BLOCK,
+            (PixelFormat::Bc7, false) => Format::BC7_UNORM_BLOCK,
+        }
+    }
+
+    /// DFD 的颜色模型
+    fn color_model(self) -> ColorModel {
+        match self {
+            PixelFormat::Rgba8 => ColorModel::RGBSDA,
+            PixelFormat::Bc1 => ColorModel::BC1A,
+            PixelFormat::Bc3 => ColorModel::BC3,
+            PixelFormat::Bc4 => ColorModel::BC4,
+            PixelFormat::Bc5 => ColorModel::BC5,
+            PixelFormat::Bc7 => ColorModel::BC7,
+        }
+    }
+
+    /// DFD 采样：（位偏移, 位长度, 通道类型, 上界）
+    fn samples(self) -> Vec<(u16, u8, u8, u32)> {
+        match self {
+            PixelFormat::Rgba8 => [0u8, 1, 2, CHANNEL_ALPHA]
+                .iter()
+                .enumerate()
+                .map(|(i, &channel)| (i as u16 * 8, 8, channel, 255))
+                .collect(),
+            PixelFormat::Bc1 | PixelFormat::Bc4 => vec![(0, 64, 0, u32::MAX)],
+            PixelFormat::Bc3 => vec![(0, 64, CHANNEL_ALPHA, u32::MAX), (64, 64, 0, u32::MAX)],
+            PixelFormat::Bc5 => vec![(0, 64, 0, u32::MAX), (64, 64, 1, u32::MAX)],
+            PixelFormat::Bc7 => vec![(0, 128, 0, u32::MAX)],
+        }
+    }
+}
+
+/// 待写入的 KTX2 纹理
+#[derive(Debug, Clone)]
+pub struct Ktx2Writer {
+    width: u32,
+    height: u32,
+    format: PixelFormat,
+    srgb: bool,
+    /// RGB 是否已预乘 Alpha（写入 DFD 标志）
+    premultiplied: bool,
+    /// Zstandard 压缩级别，`None` 为不超级压缩
+    zstd_level: Option<i32>,
+    /// mip 层级数据，下标 0 为最大层级
+    levels: Vec<Vec<u8>>,
+    key_values: Vec<(String, Vec<u8>)>,
+}
+
+impl Ktx2Writer {
+    /// 创建 RGBA8 纹理，`srgb` 决定使用 `R8G8B8A8_SRGB` 还是 `R8G8B8A8_UNORM`
+    pub fn rgba8(width: u32, height: u32, srgb: bool) -> Self {
+        Self::new(width, height, PixelFormat::Rgba8, srgb)
+    }
+
+    /// 创建指定格式的纹理，没有 sRGB 变体的格式忽略 `srgb`
+    pub fn new(width: u32, height: u32, format: PixelFormat, srgb: bool) -> Self {
+        Self {
+            width,
+            height,
+            format,
+            srgb: srgb && format.has_srgb(),
+            premultiplied: false,
+            zstd_level: None,
+            levels: Vec::new(),
+            key_values: vec![("KTXwriter".to_string(), nul_terminated(WRITER_NAME))],
+        }
+    }
+
+    /// 标记像素数据已预乘 Alpha
+    pub fn premultiplied(mut self, premultiplied: bool) -> Self {
+        self.premultiplied = premultiplied;
+        self
+    }
+
+    /// 用指定级别的 Zstandard 超级压缩每个层级，`None` 为不压缩
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn zstd(mut self, level: Option<i32>) -> Self {
+        self.zstd_level = level;
+        self
+    }
+
+    /// 追加一个 mip 层级（按从大到小的顺序调用）
+    pub fn level(mut self, data: Vec<u8>) -> Self {
+        self.levels.push(data);
+        self
+    }
+
+    /// 添加键值数据，字符串值需自行包含结尾的 NUL
+    pub fn key_value(mut self, key: &str, value: Vec<u8>) -> Self {
+        self.key_values.retain(|(k, _)| k != key);
+        self.key_values.push((key.to_string(), value));
+        self
+    }
+
+    /// 生成完整的 KTX2 文件
+    pub fn write(&self) -> Result<Vec<u8>, String> {
+        if self.levels.is_empty() {
+            return Err("KTX2 texture has no levels".to_string());
+        }
+        for (i, level) in self.levels.iter().enumerate() {
+            let expected = self.format.level_size((self.width >> i).max(1), (self.height >> i).max(1));
+            if level.len() != expected {
+                return Err(format!(
+                    "Level {} has {} bytes, expected {}",
+                    i,
+                    level.len(),
+                    expected
+                ));
+            }
+        }
+
+        let dfd = self.data_format_descriptor();
+        let kvd = self.key_value_data();
+
+        let level_count = self.levels.len();
+        let dfd_offset = Header::LENGTH + level_count * LevelIndex::LENGTH;
+        let kvd_offset = dfd_offset + dfd.len();
+        let mut data_offset = kvd_offset + kvd.len();
+
+        // 层级数据从最小层级开始存放，未超级压缩时每个层级按 lcm(纹素块字节数, 4) 对齐
+        let alignment = match self.supercompression_scheme() {
+            None => lcm(self.format.block_bytes(), 4),
+            Some(_) => 1,
+        };
+        let mut level_index = vec![
+            LevelIndex {
+                byte_offset: 0,
+                byte_length: 0,
+                uncompressed_byte_length: 0,
+            };
+            level_count
+        ];
+        let mut level_data = Vec::new();
+        for (i, level) in self.levels.iter().enumerate().rev() {
+            let padding = align(data_offset, alignment) - data_offset;
+            level_data.resize(level_data.len() + padding, 0);
+            data_offset += padding;
+
+            let stored = self.supercompress(level)?;
+            level_index[i] = LevelIndex {
+                byte_offset: data_offset as u64,
+                byte_length: stored.len() as u64,
+                uncompressed_byte_length: level.len() as u64,
+            };
+            level_data.extend_from_slice(&stored);
+            data_offset += stored.len();
+        }
+
+        let header = Header {
+            format: Some(self.format()),
+            type_size: 1,
+            pixel_width: self.width,
+            pixel_height: self.height,
+            pixel_depth: 0,
+            layer_count: 0,
+            face_count: 1,
+            level_count: level_count as u32,
+            supercompression_scheme: self.supercompression_scheme(),
+            index: Index {
+                dfd_byte_offset: dfd_offset as u32,
+                dfd_byte_length: dfd.len() as u32,
+                kvd_byte_offset: if kvd.is_empty() { 0 } else { kvd_offset as u32 },
+                kvd_byte_length: kvd.len() as u32,
+                sgd_byte_offset: 0,
+                sgd_byte_length: 0,
+            },
+        };
+
+        let mut out = Vec::with_capacity(data_offset);
+        out.extend_from_slice(&header.as_bytes());
+        for index in &level_index {
+            out.extend_from_slice(&index.as_bytes());
+        }
+        out.extend_from_slice(&dfd);
+        out.extend_from_slice(&kvd);
+        out.extend_from_slice(&level_data);
+
+        Ok(out)
+    }
+
+    fn format(&self) -> Format {
+        self.format.vk_format(self.srgb)
+    }
+
+    fn supercompression_scheme(&self) -> Option<SupercompressionScheme> {
+        self.zstd_level.map(|_| SupercompressionScheme::Zstandard)
+    }
+
+    /// 按超级压缩设置处理一个层级的数据
+    fn supercompress<'a>(&self, level: &'a [u8]) -> Result<Cow<'a, [u8]>, String> {
+        match self.zstd_level {
+            #[cfg(not(target_arch = "wasm32"))]
+            Some(zstd_level) => zstd::bulk::compress(level, zstd_level)
+                .map(Cow::Owned)
+                .map_err(|e| format!("Zstandard compression failed: {}", e)),
+            _ => Ok(Cow::Borrowed(level)),
+        }
+    }
+
+    /// 生成基本数据格式描述符（含 dfdTotalSize）
+    fn data_format_descriptor(&self) -> Vec<u8> {
+        let block = NonZeroU8::new(self.format.block_size() as u8).unwrap();
+        let one = NonZeroU8::new(1).unwrap();
+        // 超级压缩后的数据没有固定的块大小，bytesPlane0 写 0（unsized）
+        let bytes_plane0 = match self.supercompression_scheme() {
+            None => self.format.block_bytes() as u8,
+            Some(_) => 0,
+        };
+        let basic = DfdBlockHeaderBasic {
+            color_model: Some(self.format.color_model()),
+            color_primaries: Some(ColorPrimaries::BT709),
+            transfer_function: Some(if self.srgb {
+                TransferFunction::SRGB
+            } else {
+                TransferFunction::Linear
+            }),
+            flags: if self.premultiplied {
+                DataFormatFlags::ALPHA_PREMULTIPLIED
+            } else {
+                DataFormatFlags::STRAIGHT_ALPHA
+            },
+            texel_block_dimensions: [block, block, one, one],
+            bytes_planes: [bytes_plane0, 0, 0, 0, 0, 0, 0, 0],
+        };
+
+        let samples: Vec<SampleInformation> = self
+            .format
+            .samples()
+            .into_iter()
+            .map(|(bit_offset, bit_length, channel_type, upper)| SampleInformation {
+                bit_offset,
+                bit_length: NonZeroU8::new(bit_length).unwrap(),
+                channel_type,
+                // sRGB 传输函数只作用于颜色通道，Alpha 始终是线性的
+                channel_type_qualifiers: if channel_type == CHANNEL_ALPHA && self.srgb {
+                    ChannelTypeQualifiers::LINEAR
+                } else {
+                    ChannelTypeQualifiers::empty()
+                },
+                sample_positions: [0; 4],
+                lower: 0,
+                upper,
+            })
+            .collect();
+
+        let block_size = DfdHeader::LENGTH + DfdBlockHeaderBasic::LENGTH + samples.len() * SampleInformation::LENGTH;
+        let total_size = 4 + block_size;
+
+        let mut dfd = Vec::with_capacity(total_size);
+        dfd.extend_from_slice(&(total_size as u32).to_le_bytes());
+        dfd.extend_from_slice(&DfdHeader::BASIC.as_bytes(block_size as u16));
+        dfd.extend_from_slice(&basic.as_bytes());
+        for sample in &samples {
+            dfd.extend_from_slice(&sample.as_bytes());
+        }
+        dfd
+    }
+
+    /// 生成键值数据，按键名排序，每项按 4 字节对齐
+    fn key_value_data(&self) -> Vec<u8> {
+        let mut entries: Vec<&(String, Vec<u8>)> = self.key_values.iter().collect();
+        entries.sort_by(|a, b| a.0.as_bytes().cmp(b.0.as_bytes()));
+
+        let mut kvd = Vec::new();
+        for (key, value) in entries {
+            let length = key.len() + 1 + value.len();
+            kvd.extend_from_slice(&(length as u32).to_le_bytes());
+            kvd.extend_from_slice(key.as_bytes());
+            kvd.push(0);
+            kvd.extend_from_slice(value);
+            kvd.resize(align(kvd.len(), 4), 0);
+        }
+        kvd
+    }
+}
+
+/// 字符串值转为以 NUL 结尾的字节
+pub fn nul_terminated(value: &str) -> Vec<u8> {
+    let mut bytes = value.as_bytes().to_vec();
+    bytes.push(0);
+    bytes
+}
+
+fn align(offset: usize, alignment: usize) -> usize {
+    offset.div_ceil(alignment) * alignment
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_with_ktx2_reader() {
+        let level0 = vec![200u8; 4 * 4 * 4];
+        let level1 = vec![100u8; 2 * 2 * 4];
+        let bytes = Ktx2Writer::rgba8(4, 4, true)
+            .level(level0.clone())
+            .level(level1.clone())
+            .write()
+            .unwrap();
+
+        let reader = ktx2::Reader::new(&bytes).unwrap();
+        let header = reader.header();
+        assert_eq!(header.format, Some(Format::R8G8B8A8_SRGB));
+        assert_eq!(header.level_count, 2);
+
+        let levels: Vec<&[u8]> = reader.levels().map(|level| level.data).collect();
+        assert_eq!(levels, vec![&level0[..], &level1[..]]);
+
+        let writer = reader.key_value_data().find(|(key, _)| *key == "KTXwriter");
+        assert!(writer.is_some());
+    }
+
+    #[test]
+    fn test_zstd_supercompression() {
+        let level0 = vec![7u8; 8 * 8 * 4];
+        let level1 = vec![9u8; 4 * 4 * 4];
+        let bytes = Ktx2Writer::rgba8(8, 8, false)
+            .zstd(Some(19))
+            .level(level0.clone())
+            .level(level1.clone())
+            .write()
+            .unwrap();
+
+        let reader = ktx2::Reader::new(&bytes).unwrap();
+        assert_eq!(reader.header().supercompression_scheme, Some(SupercompressionScheme::Zstandard));
+        for (level, original) in reader.levels().zip([&level0, &level1]) {
+            assert!(level.data.len() < original.len());
+            assert_eq!(level.uncompressed_byte_length, original.len() as u64);
+            assert_eq!(zstd::bulk::decompress(level.data, original.len()).unwrap(), *original);
+        }
+    }
+}
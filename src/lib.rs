@@ -0,0 +1,14 @@
+//! 应用和 `ktx2tool` 共用的纹理处理模块
+//!
+//! 加载、解码、写入、对比和导出纹理的代码放在这里，两个二进制都依赖本库；
+//! 界面相关的模块（控件、画廊、GPU 预览、设置等）留在应用的二进制中
+
+pub mod alpha;
+pub mod assets;
+pub mod color_space;
+pub mod compare;
+pub mod decode;
+pub mod export;
+pub mod ktx2_writer;
+pub mod metadata;
+pub mod texture;
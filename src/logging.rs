@@ -0,0 +1,165 @@
+//! 日志模块
+//!
+//! 基于 `log` crate 的日志后端：
+//! - WASM 环境输出到 `console.debug/info/warn/error`
+//! - 原生环境输出到 stderr
+//!
+//! 过滤规则与 env_logger 相同，例如 `info,input=warn,texture=debug`，
+//! 原生环境从 `RUST_LOG` 环境变量读取，WASM 环境从 URL 参数 `?log=` 读取
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// 应用状态（计数器、面板开关等）
+pub const APP: &str = "app";
+/// 鼠标、触摸、键盘输入
+pub const INPUT: &str = "input";
+/// 窗口事件
+pub const WINDOW: &str = "window";
+/// 纹理加载、解码和对比
+pub const TEXTURE: &str = "texture";
+
+/// 未配置时的过滤规则：输入事件只在 debug 级别输出，不会刷屏
+const DEFAULT_FILTER: &str = "info";
+
+/// 原生环境下读取过滤规则的环境变量
+#[cfg(not(target_arch = "wasm32"))]
+const ENV_VAR: &str = "RUST_LOG";
+
+/// WASM 环境下读取过滤规则的 URL 参数
+#[cfg(target_arch = "wasm32")]
+const URL_PARAM: &str = "log";
+
+/// 日志过滤规则
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    /// 未匹配任何 target 时的级别
+    default: LevelFilter,
+    /// 按 target 前缀指定的级别
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Filter {
+    /// 解析 `level,target=level,...` 格式的规则，无法识别的部分会被忽略
+    pub fn parse(spec: &str) -> Self {
+        let mut filter = Self {
+            default: LevelFilter::Info,
+            targets: Vec::new(),
+        };
+
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((target, level)) => {
+                    if let Ok(level) = level.trim().parse() {
+                        filter.targets.push((target.trim().to_string(), level));
+                    }
+                }
+                None => {
+                    if let Ok(level) = directive.parse() {
+                        filter.default = level;
+                    }
+                }
+            }
+        }
+
+        filter
+    }
+
+    /// 给定 target 的级别，取最长的匹配前缀
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| target.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    /// 所有规则中最详细的级别，用于 `log::set_max_level`
+    fn max_level(&self) -> LevelFilter {
+        self.targets
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, std::cmp::max)
+    }
+}
+
+/// 控制台日志后端
+struct ConsoleLogger {
+    filter: Filter,
+}
+
+impl Log for ConsoleLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!("[{} {}] {}", record.level(), record.target(), record.args());
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            use log::Level;
+
+            let line = line.into();
+            match record.level() {
+                Level::Error => web_sys::console::error_1(&line),
+                Level::Warn => web_sys::console::warn_1(&line),
+                Level::Info => web_sys::console::info_1(&line),
+                Level::Debug | Level::Trace => web_sys::console::debug_1(&line),
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            eprintln!("{}", line);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// 安装日志后端，只应在启动时调用一次
+pub fn init() {
+    let spec = configured_spec().unwrap_or_else(|| DEFAULT_FILTER.to_string());
+    let filter = Filter::parse(&spec);
+    let max_level = filter.max_level();
+
+    let logger: &'static ConsoleLogger = Box::leak(Box::new(ConsoleLogger { filter }));
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(max_level);
+        log::debug!(target: APP, "Logging initialized with filter \"{}\"", spec);
+    }
+}
+
+/// 读取用户配置的过滤规则
+#[cfg(not(target_arch = "wasm32"))]
+fn configured_spec() -> Option<String> {
+    std::env::var(ENV_VAR).ok()
+}
+
+/// 读取用户配置的过滤规则
+#[cfg(target_arch = "wasm32")]
+fn configured_spec() -> Option<String> {
+    let search = web_sys::window()?.location().search().ok()?;
+    web_sys::UrlSearchParams::new_with_str(&search)
+        .ok()?
+        .get(URL_PARAM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_parse() {
+        let filter = Filter::parse("warn, texture=debug,texture::ktx2=trace,bogus=loud");
+        assert_eq!(filter.level_for(INPUT), LevelFilter::Warn);
+        assert_eq!(filter.level_for(TEXTURE), LevelFilter::Debug);
+        assert_eq!(filter.level_for("texture::ktx2"), LevelFilter::Trace);
+        assert_eq!(filter.max_level(), LevelFilter::Trace);
+    }
+}
@@ -1,19 +1,73 @@
-mod texture;
+mod animation;
+mod event_log;
+mod file_input;
+mod gallery;
+mod gpu_preview;
+mod logging;
+mod settings;
+mod shortcuts;
+mod stats;
+mod touch;
+mod viewer;
 
-use iced::widget::{button, column, container, text, image as iced_image};
-use iced::{Center, Element, Length, Subscription, Task, Font};
+use std::sync::Arc;
+
+use iced_web_app::{alpha, assets, color_space, compare, decode, export, texture};
+use iced::widget::{button, checkbox, column, container, opaque, row, scrollable, shader, slider, stack, text, tooltip, image as iced_image};
+use iced::{Center, Element, Length, Size, Subscription, Task, Font};
 use iced::event::{self, Event};
+use iced::keyboard;
 use iced::mouse;
+use iced::touch as iced_touch;
+use iced::time::{self, Duration, Instant};
 use iced::window;
-use texture::TextureLoader;
+use animation::{LoopMode, Player};
+use assets::{Capabilities, Platform};
+use compare::{CompareMode, TextureComparison};
+use event_log::{EventCategory, EventLog};
+use export::{ExportFormat, ExportedFile};
+use file_input::LocalFile;
+use gallery::{Gallery, Thumbnail, ThumbnailResult};
+use gpu_preview::{GpuTexture, Preview};
+use settings::Settings;
+use shortcuts::{Action, ShortcutMap};
+use stats::TextureStats;
+use texture::{SourceFormat, TextureLoader};
+use touch::TouchTracker;
+use viewer::ViewerState;
 
 // Default font
 const DEFAULT_FONT: Font = Font::DEFAULT;
 
+/// 直方图图像高度（像素）
+const HISTOGRAM_HEIGHT: u32 = 100;
+
+/// 画廊网格的可见高度（像素）
+const GALLERY_HEIGHT: f32 = 420.0;
+
+/// 纹理预览的默认宽度（像素）
+const PREVIEW_WIDTH: f32 = 300.0;
+
+/// 设置有变化时的写入间隔，避免拖动窗口大小时频繁写入存储
+const SETTINGS_SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 内置示例 KTX2 纹理的来源（与 `Settings::last_texture` 的含义一致）
+#[cfg(target_arch = "wasm32")]
+const BUNDLED_KTX2: &str = "1.ktx2";
+#[cfg(not(target_arch = "wasm32"))]
+const BUNDLED_KTX2: &str = "public/1.ktx2";
+
 pub fn main() -> iced::Result {
-    iced::application(Counter::new, Counter::update, Counter::view)
+    logging::init();
+
+    // 关闭窗口前先保存设置，由 `CloseRequested` 处理后再关闭
+    let mut application = iced::application(Counter::new, Counter::update, Counter::view)
         .subscription(Counter::subscription)
-        .run()
+        .exit_on_close_request(false);
+    if let Some((width, height)) = Settings::load().window_size {
+        application = application.window_size(Size::new(width, height));
+    }
+    application.run()
 }
 
 #[derive(Default)]
@@ -21,8 +75,39 @@ struct Counter {
     value: i64,
     mouse_position: Option<mouse::Cursor>,
     mouse_buttons: String,
-    last_event: String,
+    touch: TouchTracker,
+    event_log: EventLog,
+    show_event_log: bool,
     texture_loader: TextureLoader,
+    /// 多帧纹理（KTX2 数组、GIF、APNG）的播放器
+    player: Option<Player>,
+    /// 当前纹理的文件名，用于生成导出文件名
+    texture_name: Option<String>,
+    /// 当前纹理的来源，下次启动时重新打开
+    last_texture: Option<String>,
+    window_size: Option<Size>,
+    /// 最近一次保存的设置，只在变化时写入
+    saved_settings: Settings,
+    gallery: Option<Gallery>,
+    show_gallery: bool,
+    /// 启动时从资源清单读取的纹理（每个纹理已选好受支持的变体）
+    assets: Option<Gallery>,
+    comparison: TextureComparison,
+    show_info: bool,
+    stats: Option<TextureStats>,
+    histogram: Option<iced_image::Handle>,
+    show_stats: bool,
+    viewer: ViewerState,
+    shortcuts: ShortcutMap,
+    /// 按当前 mip 层级和通道设置渲染后的预览图像及其尺寸
+    display: Option<(iced_image::Handle, u32, u32)>,
+    /// GPU 预览使用的纹理（KTX2 原始数据或 CPU 解码结果）
+    gpu_texture: Option<Arc<GpuTexture>>,
+    /// 是否用 wgpu 着色器代替 CPU 渲染的预览
+    gpu_preview: bool,
+    /// GPU 预览是否自动选择 mip 层级
+    gpu_auto_lod: bool,
+    show_help: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -30,36 +115,114 @@ enum Message {
     Increment,
     Decrement,
     EventOccurred(Event),
+    /// 只在 WASM 中由 JS 加载示例图像后发出
+    #[cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
     TextureLoaded(Vec<u8>),
     LoadTexture,
     Ktx2TextureLoaded(Vec<u8>),
     LoadKtx2Texture,
+    CompareModeSelected(CompareMode),
+    CompareWipeChanged(f32),
+    CompareAmplifyChanged(f32),
+    OpenFile,
+    FileLoaded(Result<LocalFile, String>),
+    ToggleInfoPanel,
+    ToggleStatsPanel,
+    Shortcut(Action),
+    ToggleEventLog,
+    EventFilterToggled(EventCategory, bool),
+    EventLogPaused(bool),
+    ClearEventLog,
+    ExportTexture(ExportFormat),
+    ExportFinished(Result<Option<String>, String>),
+    OpenGallery,
+    GalleryLoaded(Option<Result<Gallery, String>>),
+    AssetsLoaded(Option<Result<Gallery, String>>),
+    OpenAssets,
+    GalleryScrolled(scrollable::Viewport),
+    ThumbnailLoaded((String, ThumbnailResult)),
+    GalleryItemSelected(usize),
+    CloseGallery,
+    AnimationTick(Instant),
+    TogglePlayback,
+    StepFrame(i32),
+    FpsChanged(f32),
+    LoopModeSelected(LoopMode),
+    GpuPreviewToggled(bool),
+    GpuAutoLodToggled(bool),
+    SaveSettings,
 }
 
 impl Counter {
     fn new() -> (Self, Task<Message>) {
-        (Self::default(), Task::none())
+        let settings = Settings::load();
+
+        let mut counter = Self {
+            value: settings.counter,
+            window_size: settings.window_size.map(|(width, height)| Size::new(width, height)),
+            ..Self::default()
+        };
+        counter.comparison.mode = settings.compare_mode;
+        counter.viewer.set_fit_to_window(settings.fit_to_window);
+        counter.viewer.set_zoom(settings.zoom);
+
+        let task = match &settings.last_texture {
+            Some(source) => {
+                log::info!(target: logging::APP, "Restoring last texture: {}", source);
+                Task::perform(file_input::open_source(source.clone()), Message::FileLoaded)
+            }
+            None => Task::none(),
+        };
+        counter.saved_settings = settings;
+
+        (counter, Task::batch([task, Task::perform(load_assets(), Message::AssetsLoaded)]))
+    }
+
+    /// 当前状态对应的设置
+    fn settings(&self) -> Settings {
+        Settings {
+            last_texture: self.last_texture.clone(),
+            zoom: self.viewer.zoom(),
+            fit_to_window: self.viewer.fit_to_window(),
+            compare_mode: self.comparison.mode,
+            counter: self.value,
+            window_size: self.window_size.map(|size| (size.width, size.height)),
+        }
+    }
+
+    /// 设置有变化时写入存储
+    fn persist_settings(&mut self) {
+        let settings = self.settings();
+        if settings == self.saved_settings {
+            return;
+        }
+        if let Err(e) = settings.save() {
+            log::warn!(target: logging::APP, "Failed to save settings: {}", e);
+        }
+        self.saved_settings = settings;
     }
 
     fn update(&mut self, message: Message) -> Task<Message> {
         match message {
             Message::Increment => {
                 self.value += 1;
-                log_to_console("Increment clicked", &format!("New value: {}", self.value));
+                log::debug!(target: logging::APP, "Increment clicked, new value: {}", self.value);
             }
             Message::Decrement => {
                 self.value -= 1;
-                log_to_console("Decrement clicked", &format!("New value: {}", self.value));
+                log::debug!(target: logging::APP, "Decrement clicked, new value: {}", self.value);
             }
             Message::EventOccurred(event) => {
-                self.handle_event(event);
+                return self.handle_event(event);
             }
             Message::TextureLoaded(data) => {
                 if let Err(e) = self.texture_loader.load_from_png_bytes(&data) {
-                    log_to_console("Texture load error", &e);
+                    log::error!(target: logging::TEXTURE, "Texture load error: {}", e);
+                    self.event_log.record(EventCategory::Texture, format!("Texture load error: {}", e));
                 } else {
-                    log_to_console("Texture loaded",
-                        &format!("Size: {:?}", self.texture_loader.dimensions()));
+                    self.texture_name = Some("1.png".to_string());
+                    self.last_texture = Some("1.png".to_string());
+                    self.on_image_loaded(&data);
                 }
             }
             Message::LoadTexture => {
@@ -70,7 +233,7 @@ impl Counter {
                 }
                 #[cfg(not(target_arch = "wasm32"))]
                 {
-                    log_to_console("Load texture", "Not supported on native");
+                    log::warn!(target: logging::TEXTURE, "Load texture is not supported on native");
                 }
             }
             Message::LoadKtx2Texture => {
@@ -82,28 +245,438 @@ impl Counter {
                 #[cfg(not(target_arch = "wasm32"))]
                 {
                     // 在原生环境中，从文件系统加载
-                    match std::fs::read("public/1.ktx2") {
+                    match std::fs::read(BUNDLED_KTX2) {
                         Ok(data) => {
                             return Task::perform(async move { data }, Message::Ktx2TextureLoaded);
                         }
                         Err(e) => {
-                            log_to_console("Load KTX2 error", &format!("无法读取文件: {}", e));
+                            log::error!(target: logging::TEXTURE, "无法读取文件 {}: {}", BUNDLED_KTX2, e);
                         }
                     }
                 }
             }
             Message::Ktx2TextureLoaded(data) => {
                 if let Err(e) = self.texture_loader.load_from_ktx2_bytes(&data) {
-                    log_to_console("KTX2 load error", &e);
+                    if !self.load_gpu_only(&data) {
+                        log::error!(target: logging::TEXTURE, "KTX2 load error: {}", e);
+                        self.event_log.record(EventCategory::Texture, format!("KTX2 load error: {}", e));
+                    }
                 } else {
-                    log_to_console("KTX2 texture loaded",
-                        &format!("Size: {:?}", self.texture_loader.dimensions()));
+                    self.texture_name = Some("1.ktx2".to_string());
+                    self.last_texture = Some(BUNDLED_KTX2.to_string());
+                    self.on_ktx2_loaded(&data);
+                }
+            }
+            Message::CompareModeSelected(mode) => {
+                self.comparison.mode = mode;
+                log::info!(target: logging::TEXTURE, "Compare mode: {}", mode.label());
+            }
+            Message::CompareWipeChanged(wipe) => {
+                self.comparison.set_wipe(wipe);
+            }
+            Message::CompareAmplifyChanged(amplify) => {
+                self.comparison.set_amplify(amplify);
+            }
+            Message::OpenFile => {
+                #[cfg(target_arch = "wasm32")]
+                {
+                    file_input::open_picker();
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    return Task::future(file_input::pick_file())
+                        .and_then(|file| Task::done(Message::FileLoaded(Ok(file))));
+                }
+            }
+            Message::FileLoaded(Ok(file)) => {
+                self.load_local_file(file);
+            }
+            Message::FileLoaded(Err(e)) => {
+                log::error!(target: logging::TEXTURE, "File load error: {}", e);
+                self.event_log.record(EventCategory::Texture, format!("File load error: {}", e));
+            }
+            Message::ToggleInfoPanel => {
+                self.show_info = !self.show_info;
+            }
+            Message::ToggleStatsPanel => {
+                self.show_stats = !self.show_stats;
+            }
+            Message::Shortcut(action) => {
+                return self.apply_shortcut(action);
+            }
+            Message::ToggleEventLog => {
+                self.show_event_log = !self.show_event_log;
+            }
+            Message::EventFilterToggled(category, shown) => {
+                self.event_log.set_shown(category, shown);
+            }
+            Message::EventLogPaused(paused) => {
+                self.event_log.set_paused(paused);
+            }
+            Message::ClearEventLog => {
+                self.event_log.clear();
+            }
+            Message::ExportTexture(format) => {
+                return self.export_view(format);
+            }
+            Message::ExportFinished(Ok(Some(path))) => {
+                log::info!(target: logging::TEXTURE, "Exported texture to {}", path);
+                self.event_log.record(EventCategory::Texture, format!("Exported: {}", path));
+            }
+            Message::ExportFinished(Ok(None)) => {
+                log::debug!(target: logging::TEXTURE, "Export cancelled");
+            }
+            Message::ExportFinished(Err(e)) => {
+                log::error!(target: logging::TEXTURE, "Export error: {}", e);
+                self.event_log.record(EventCategory::Texture, format!("Export error: {}", e));
+            }
+            Message::OpenGallery => {
+                return Task::perform(gallery::open(), Message::GalleryLoaded);
+            }
+            Message::GalleryLoaded(Some(Ok(gallery))) => {
+                log::info!(target: logging::TEXTURE,
+                    "Gallery {}: {} textures", gallery.title, gallery.entries().len());
+                self.gallery = Some(gallery);
+                self.show_gallery = true;
+                // 滚动前先为第一屏生成缩略图
+                return self.request_thumbnails(0.0, GALLERY_HEIGHT);
+            }
+            Message::GalleryLoaded(Some(Err(e))) => {
+                log::error!(target: logging::TEXTURE, "Gallery error: {}", e);
+                self.event_log.record(EventCategory::Texture, format!("Gallery error: {}", e));
+            }
+            Message::GalleryLoaded(None) => {
+                log::debug!(target: logging::TEXTURE, "Gallery cancelled");
+            }
+            Message::AssetsLoaded(Some(Ok(assets))) => {
+                log::info!(target: logging::TEXTURE, "Asset manifest: {} textures", assets.entries().len());
+                self.assets = Some(assets);
+            }
+            Message::AssetsLoaded(Some(Err(e))) => {
+                log::error!(target: logging::TEXTURE, "Asset manifest error: {}", e);
+                self.event_log.record(EventCategory::Texture, format!("Asset manifest error: {}", e));
+            }
+            Message::AssetsLoaded(None) => {}
+            Message::OpenAssets => {
+                if let Some(assets) = &self.assets {
+                    self.gallery = Some(assets.clone());
+                    self.show_gallery = true;
+                    return self.request_thumbnails(0.0, GALLERY_HEIGHT);
+                }
+            }
+            Message::GalleryScrolled(viewport) => {
+                return self.request_thumbnails(viewport.absolute_offset().y, viewport.bounds().height);
+            }
+            Message::ThumbnailLoaded((source, result)) => {
+                if let Err(e) = &result {
+                    log::warn!(target: logging::TEXTURE, "Thumbnail error for {}: {}", source, e);
+                }
+                if let Some(gallery) = &mut self.gallery {
+                    gallery.set_thumbnail(&source, result);
+                }
+            }
+            Message::GalleryItemSelected(index) => {
+                if let Some(source) = self.gallery.as_ref().and_then(|gallery| gallery.source(index)) {
+                    self.show_gallery = false;
+                    return Task::perform(file_input::open_source(source.to_string()), Message::FileLoaded);
+                }
+            }
+            Message::CloseGallery => {
+                self.show_gallery = false;
+            }
+            Message::AnimationTick(now) => {
+                if self.player.as_mut().is_some_and(|player| player.tick(now)) {
+                    self.refresh_display();
+                }
+            }
+            Message::TogglePlayback => {
+                if let Some(player) = &mut self.player {
+                    player.toggle_playing();
+                }
+            }
+            Message::StepFrame(delta) => {
+                if let Some(player) = &mut self.player {
+                    player.step(delta);
+                    self.refresh_display();
                 }
             }
+            Message::FpsChanged(fps) => {
+                if let Some(player) = &mut self.player {
+                    player.set_fps(fps);
+                }
+            }
+            Message::LoopModeSelected(loop_mode) => {
+                if let Some(player) = &mut self.player {
+                    player.set_loop_mode(loop_mode);
+                }
+            }
+            Message::GpuPreviewToggled(enabled) => {
+                self.gpu_preview = enabled;
+                self.refresh_display();
+            }
+            Message::GpuAutoLodToggled(enabled) => {
+                self.gpu_auto_lod = enabled;
+            }
+            Message::SaveSettings => {
+                self.persist_settings();
+            }
+        }
+        Task::none()
+    }
+
+    /// 为可见区域内尚未生成的条目请求缩略图
+    fn request_thumbnails(&mut self, scroll_y: f32, viewport_height: f32) -> Task<Message> {
+        let Some(gallery) = &mut self.gallery else {
+            return Task::none();
+        };
+
+        let range = gallery.visible_range(scroll_y, viewport_height);
+        Task::batch(
+            gallery
+                .request_thumbnails(range)
+                .into_iter()
+                .map(|source| Task::perform(gallery::load_thumbnail(source), Message::ThumbnailLoaded)),
+        )
+    }
+
+    /// 导出预览中当前显示的图像（已应用 mip 层级和通道开关）
+    fn export_view(&self, format: ExportFormat) -> Task<Message> {
+        let Some((rgba, width, height)) = self.current_pixels() else {
+            return Task::done(Message::ExportFinished(Err("No texture loaded".to_string())));
+        };
+
+        let (width, height, pixels) = self.viewer.render(rgba, width, height);
+        let data = match export::encode(&pixels, width, height, format, self.texture_loader.color_space()) {
+            Ok(data) => data,
+            Err(e) => return Task::done(Message::ExportFinished(Err(e))),
+        };
+        let file = ExportedFile {
+            name: export::file_name(self.texture_name.as_deref(), self.viewer.mip_level(), format),
+            format,
+            data,
+        };
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let name = file.name.clone();
+            Task::done(Message::ExportFinished(export::download(&file).map(|_| Some(name))))
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Task::perform(export::save(file), Message::ExportFinished)
+        }
+    }
+
+    /// 执行快捷键对应的动作
+    fn apply_shortcut(&mut self, action: Action) -> Task<Message> {
+        log::debug!(target: logging::INPUT, "Shortcut: {}", action.description());
+        self.event_log.record(EventCategory::Keyboard, action.description());
+
+        match action {
+            Action::Increment => return self.update(Message::Increment),
+            Action::Decrement => return self.update(Message::Decrement),
+            Action::OpenFile => return self.update(Message::OpenFile),
+            Action::ZoomIn => self.viewer.zoom_in(),
+            Action::ZoomOut => self.viewer.zoom_out(),
+            Action::PanLeft => self.viewer.pan(-1.0, 0.0),
+            Action::PanRight => self.viewer.pan(1.0, 0.0),
+            Action::PanUp => self.viewer.pan(0.0, -1.0),
+            Action::PanDown => self.viewer.pan(0.0, 1.0),
+            Action::FitToWindow => self.viewer.toggle_fit(),
+            Action::ToggleHelp => self.show_help = !self.show_help,
+            Action::TogglePlayback => return self.update(Message::TogglePlayback),
+            Action::PrevFrame => return self.update(Message::StepFrame(-1)),
+            Action::NextFrame => return self.update(Message::StepFrame(1)),
+            Action::PrevMip => {
+                self.viewer.prev_mip();
+                self.refresh_display();
+            }
+            Action::NextMip => {
+                let level_count = match &self.gpu_texture {
+                    Some(texture) if self.gpu_preview => texture.level_count(),
+                    _ => self
+                        .texture_loader
+                        .dimensions()
+                        .map_or(1, |(width, height)| viewer::mip_count(width, height)),
+                };
+                self.viewer.next_mip(level_count);
+                self.refresh_display();
+            }
+            Action::ToggleRed | Action::ToggleGreen | Action::ToggleBlue | Action::ToggleAlpha => {
+                let channel = match action {
+                    Action::ToggleRed => 0,
+                    Action::ToggleGreen => 1,
+                    Action::ToggleBlue => 2,
+                    _ => 3,
+                };
+                self.viewer.toggle_channel(channel);
+                self.refresh_display();
+            }
         }
         Task::none()
     }
 
+    /// 按文件头识别格式，并交给对应的 TextureLoader 方法加载
+    fn load_local_file(&mut self, file: LocalFile) {
+        let details = format!("File loaded: {} ({} bytes)", file.name, file.data.len());
+        log::info!(target: logging::TEXTURE, "{}", details);
+        self.event_log.record(EventCategory::Texture, details);
+
+        let loaded = self.texture_loader.load_from_bytes(&file.data);
+        if loaded.is_ok() {
+            self.texture_name = Some(file.name.clone());
+            if file.source.is_some() {
+                self.last_texture = file.source.clone();
+            }
+        }
+
+        match loaded {
+            Ok(SourceFormat::Ktx2) => self.on_ktx2_loaded(&file.data),
+            Ok(SourceFormat::Image(_)) => self.on_image_loaded(&file.data),
+            Err(_) if self.load_gpu_only(&file.data) => {
+                self.texture_name = Some(file.name.clone());
+                if file.source.is_some() {
+                    self.last_texture = file.source.clone();
+                }
+            }
+            Err(e) => {
+                let details = format!("{}: {}", file.name, e);
+                log::error!(target: logging::TEXTURE, "File load error: {}", details);
+                self.event_log.record(EventCategory::Texture, format!("File load error: {}", details));
+            }
+        }
+    }
+
+    /// 普通图像加载完成，作为对比的参考纹理
+    fn on_image_loaded(&mut self, data: &[u8]) {
+        let details = format!("Size: {:?}", self.texture_loader.dimensions());
+        log::info!(target: logging::TEXTURE, "Texture loaded: {}", details);
+        self.event_log.record(EventCategory::Texture, format!("Texture loaded: {}", details));
+        self.comparison.left = self.texture_loader.clone();
+        self.comparison.refresh();
+        self.refresh_stats();
+        self.load_animation(data);
+        self.load_gpu_texture(data);
+        self.viewer.reset_mip();
+        self.refresh_display();
+    }
+
+    /// KTX2 纹理加载完成，作为对比的待测纹理
+    fn on_ktx2_loaded(&mut self, data: &[u8]) {
+        let details = format!("Size: {:?}", self.texture_loader.dimensions());
+        log::info!(target: logging::TEXTURE, "KTX2 texture loaded: {}", details);
+        self.event_log.record(EventCategory::Texture, format!("KTX2 texture loaded: {}", details));
+        self.comparison.right = self.texture_loader.clone();
+        self.comparison.refresh();
+        self.refresh_stats();
+        self.load_animation(data);
+        self.load_gpu_texture(data);
+        self.viewer.reset_mip();
+        self.refresh_display();
+    }
+
+    /// 重新统计当前纹理并生成直方图
+    fn refresh_stats(&mut self) {
+        self.stats = self.texture_loader.data().map(TextureStats::compute);
+        self.histogram = self.stats.as_ref().map(|stats| {
+            iced_image::Handle::from_rgba(256, HISTOGRAM_HEIGHT, stats.histogram_image(HISTOGRAM_HEIGHT))
+        });
+    }
+
+    /// 多帧纹理创建播放器，单帧纹理清除播放器
+    fn load_animation(&mut self, data: &[u8]) {
+        self.player = match animation::decode(data) {
+            Ok(animation) => animation.map(Player::new),
+            Err(e) => {
+                log::warn!(target: logging::TEXTURE, "Animation decode error: {}", e);
+                None
+            }
+        };
+        if let Some(player) = &self.player {
+            log::info!(target: logging::TEXTURE, "Animation: {}", player.status());
+        }
+    }
+
+    /// 为 GPU 预览准备纹理：KTX2 尽量保留原始格式，其他格式使用 CPU 解码结果
+    fn load_gpu_texture(&mut self, data: &[u8]) {
+        let texture = match texture::sniff_format(data) {
+            Some(SourceFormat::Ktx2) => GpuTexture::from_ktx2(data, self.texture_loader.data()),
+            _ => self
+                .current_pixels()
+                .map(|(rgba, width, height)| {
+                    GpuTexture::from_rgba(rgba, width, height, self.texture_loader.color_space())
+                })
+                .ok_or_else(|| "No texture data".to_string()),
+        };
+
+        self.gpu_texture = match texture {
+            Ok(texture) => Some(Arc::new(texture)),
+            Err(e) => {
+                log::warn!(target: logging::TEXTURE, "GPU preview unavailable: {}", e);
+                None
+            }
+        };
+    }
+
+    /// CPU 无法解码的 KTX2（例如 BC7）只能在 GPU 预览中查看
+    ///
+    /// 成功时清空 CPU 侧的纹理并切换到 GPU 预览
+    fn load_gpu_only(&mut self, data: &[u8]) -> bool {
+        if texture::sniff_format(data) != Some(SourceFormat::Ktx2) {
+            return false;
+        }
+        let Ok(texture) = GpuTexture::from_ktx2(data, None) else {
+            return false;
+        };
+
+        let details = format!("{} (no CPU decoder, GPU preview only)", texture.status());
+        log::info!(target: logging::TEXTURE, "KTX2 texture loaded: {}", details);
+        self.event_log.record(EventCategory::Texture, format!("KTX2 texture loaded: {}", details));
+
+        self.texture_loader = TextureLoader::new();
+        self.player = None;
+        self.gpu_texture = Some(Arc::new(texture));
+        self.gpu_preview = true;
+        self.refresh_stats();
+        self.viewer.reset_mip();
+        self.refresh_display();
+        true
+    }
+
+    /// 当前显示的原始像素：动画的当前帧或静态纹理，返回 (RGBA8, 宽, 高)
+    fn current_pixels(&self) -> Option<(&[u8], u32, u32)> {
+        if let Some(player) = &self.player {
+            let (width, height) = player.dimensions();
+            return Some((player.frame(), width, height));
+        }
+        let (width, height) = self.texture_loader.dimensions()?;
+        Some((self.texture_loader.data()?, width, height))
+    }
+
+    /// 按当前帧、mip 层级和通道设置重新生成预览图像
+    ///
+    /// GPU 预览在着色器中处理 mip 层级和通道，只需在动画换帧时重新上传
+    fn refresh_display(&mut self) {
+        if self.gpu_preview {
+            if self.player.is_some() {
+                let color_space = self.texture_loader.color_space();
+                self.gpu_texture = self.current_pixels().map(|(rgba, width, height)| {
+                    Arc::new(GpuTexture::from_rgba(rgba, width, height, color_space))
+                });
+            }
+            return;
+        }
+
+        // 线性数据先转换为 sRGB 编码再显示
+        let color_space = self.texture_loader.color_space();
+        self.display = self.current_pixels().map(|(rgba, width, height)| {
+            let rgba = color_space::to_display(rgba, color_space);
+            let (width, height, pixels) = self.viewer.render(&rgba, width, height);
+            (iced_image::Handle::from_rgba(width, height, pixels), width, height)
+        });
+    }
+
     fn view(&self) -> Element<'_, Message> {
         let mouse_info = if let Some(cursor) = self.mouse_position {
             match cursor.position() {
@@ -115,8 +688,40 @@ impl Counter {
         };
 
         // 创建图像 widget
-        let texture_view: Element<'_, Message> = if let Some(handle) = self.texture_loader.as_iced_handle() {
-            container(iced_image(handle).width(Length::Fixed(300.0))).into()
+        let preview_width = if self.viewer.fit_to_window() {
+            Length::Fill
+        } else {
+            Length::Fixed(PREVIEW_WIDTH)
+        };
+        let texture_view: Element<'_, Message> = if self.comparison.mode != CompareMode::Off {
+            self.compare_view()
+        } else if let (true, Some(texture)) = (self.gpu_preview, &self.gpu_texture) {
+            let preview = Preview::new(texture.clone(), &self.viewer, self.gpu_auto_lod);
+            let height = PREVIEW_WIDTH / preview.aspect_ratio();
+            column![
+                shader(preview).width(preview_width).height(Length::Fixed(height)),
+                text(format!("{} | {}", self.viewer.status(), self.texture_loader.color_space().label()))
+                    .size(14)
+                    .font(DEFAULT_FONT),
+                text(texture.status()).size(14).font(DEFAULT_FONT),
+                self.playback_view(),
+            ]
+            .spacing(5)
+            .align_x(Center)
+            .into()
+        } else if let Some((handle, width, height)) = &self.display {
+            column![
+                iced_image(handle.clone())
+                    .crop(self.viewer.crop_region(*width, *height))
+                    .width(preview_width),
+                text(format!("{} | {}", self.viewer.status(), self.texture_loader.color_space().label()))
+                    .size(14)
+                    .font(DEFAULT_FONT),
+                self.playback_view(),
+            ]
+            .spacing(5)
+            .align_x(Center)
+            .into()
         } else {
             container(text("No texture loaded")
                 .size(14)
@@ -128,7 +733,25 @@ impl Counter {
             .into()
         };
 
-        container(
+        let info_panel: Element<'_, Message> = if self.show_info {
+            self.info_view()
+        } else {
+            column![].into()
+        };
+
+        let stats_panel: Element<'_, Message> = if self.show_stats {
+            self.stats_view()
+        } else {
+            column![].into()
+        };
+
+        let event_log_panel: Element<'_, Message> = if self.show_event_log {
+            self.event_log_view()
+        } else {
+            column![].into()
+        };
+
+        let content = container(
             column![
                 text("🖱️ Mouse Event & Texture Demo")
                     .size(30)
@@ -139,6 +762,9 @@ impl Counter {
                 text(self.mouse_buttons.clone())
                     .size(16)
                     .font(DEFAULT_FONT),
+                text(self.touch.describe())
+                    .size(16)
+                    .font(DEFAULT_FONT),
                 text(format!("Counter: {}", self.value))
                     .size(50)
                     .font(DEFAULT_FONT),
@@ -150,13 +776,60 @@ impl Counter {
                     .on_press(Message::LoadTexture),
                 button("🎨 Load KTX2 Texture (1.ktx2)")
                     .on_press(Message::LoadKtx2Texture),
+                button("📂 Open File...")
+                    .on_press(Message::OpenFile),
+                button("🖼️ Gallery...")
+                    .on_press(Message::OpenGallery),
+                button(text(match &self.assets {
+                    Some(assets) => format!("🗂️ Assets ({})", assets.entries().len()),
+                    None => "🗂️ Assets".to_string(),
+                }))
+                .on_press_maybe(self.assets.as_ref().map(|_| Message::OpenAssets)),
+                text("Or drop a PNG / KTX2 file onto the window")
+                    .size(14)
+                    .font(DEFAULT_FONT),
                 text("Texture Preview:")
                     .size(18)
                     .font(DEFAULT_FONT),
+                row(CompareMode::ALL.iter().map(|mode| {
+                    button(text(mode.label()).size(14))
+                        .on_press(Message::CompareModeSelected(*mode))
+                        .into()
+                }))
+                .spacing(10),
                 texture_view,
-                text(format!("Last event: {}", self.last_event))
-                    .size(14)
-                    .font(DEFAULT_FONT)
+                row![
+                    checkbox(self.gpu_preview)
+                        .label("🎮 GPU preview (wgpu)")
+                        .on_toggle(Message::GpuPreviewToggled),
+                    checkbox(self.gpu_auto_lod)
+                        .label("Automatic mip selection")
+                        .on_toggle(Message::GpuAutoLodToggled),
+                ]
+                .spacing(20),
+                row(ExportFormat::ALL.iter().map(|format| {
+                    button(text(format!("💾 Save as {}", format.label())).size(14))
+                        .on_press(Message::ExportTexture(*format))
+                        .into()
+                }))
+                .spacing(10),
+                button(if self.show_info { "ℹ️ Hide Info" } else { "ℹ️ Show Info" })
+                    .on_press(Message::ToggleInfoPanel),
+                info_panel,
+                button(if self.show_stats { "📊 Hide Statistics" } else { "📊 Show Statistics" })
+                    .on_press(Message::ToggleStatsPanel),
+                stats_panel,
+                button("⌨️ Keyboard Shortcuts (?)")
+                    .on_press(Message::Shortcut(Action::ToggleHelp)),
+                text(format!(
+                    "Last event: {}",
+                    self.event_log.last().map(|entry| entry.line()).unwrap_or_default()
+                ))
+                .size(14)
+                .font(DEFAULT_FONT),
+                button(if self.show_event_log { "📜 Hide Event Log" } else { "📜 Show Event Log" })
+                    .on_press(Message::ToggleEventLog),
+                event_log_panel,
             ]
             .spacing(20)
             .padding(20)
@@ -165,33 +838,256 @@ impl Counter {
         .center_x(Length::Fill)
         .center_y(Length::Fill)
         .width(Length::Fill)
-        .height(Length::Fill)
+        .height(Length::Fill);
+
+        if self.show_help {
+            stack![content, self.help_view()].into()
+        } else if let (true, Some(gallery)) = (self.show_gallery, &self.gallery) {
+            stack![content, gallery_view(gallery)].into()
+        } else {
+            content.into()
+        }
+    }
+
+    /// 动画播放控制：逐帧、播放/暂停、帧率和循环模式
+    fn playback_view(&self) -> Element<'_, Message> {
+        let Some(player) = &self.player else {
+            return column![].into();
+        };
+
+        let transport = row![
+            button("⏮️").on_press(Message::StepFrame(-1)),
+            button(if player.is_playing() { "⏸️ Pause" } else { "▶️ Play" })
+                .on_press(Message::TogglePlayback),
+            button("⏭️").on_press(Message::StepFrame(1)),
+            slider(animation::MIN_FPS..=animation::MAX_FPS, player.fps(), Message::FpsChanged)
+                .step(1.0f32)
+                .width(Length::Fixed(150.0)),
+        ]
+        .spacing(10)
+        .align_y(Center);
+
+        let loop_modes = row(LoopMode::ALL.iter().map(|&loop_mode| {
+            checkbox(player.loop_mode() == loop_mode)
+                .label(loop_mode.label())
+                .on_toggle(move |_| Message::LoopModeSelected(loop_mode))
+                .into()
+        }))
+        .spacing(10);
+
+        column![transport, loop_modes, text(player.status()).size(14).font(DEFAULT_FONT)]
+            .spacing(5)
+            .align_x(Center)
+            .into()
+    }
+
+    /// 事件日志面板：类别过滤、暂停/清空、可滚动的事件列表
+    fn event_log_view(&self) -> Element<'_, Message> {
+        let log = &self.event_log;
+
+        let filters = row(EventCategory::ALL.iter().map(|&category| {
+            checkbox(log.is_shown(category))
+                .label(category.label())
+                .on_toggle(move |shown| Message::EventFilterToggled(category, shown))
+                .into()
+        }))
+        .spacing(10);
+
+        let controls = row![
+            button(if log.is_paused() { "▶️ Resume" } else { "⏸️ Pause" })
+                .on_press(Message::EventLogPaused(!log.is_paused())),
+            button("🗑️ Clear").on_press(Message::ClearEventLog),
+            text(format!("{} events", log.len())).size(14).font(DEFAULT_FONT),
+        ]
+        .spacing(10)
+        .align_y(Center);
+
+        let entries = log
+            .visible()
+            .map(|entry| text(entry.line()).size(13).font(DEFAULT_FONT).into());
+
+        column![
+            filters,
+            controls,
+            container(scrollable(column(entries).spacing(2).padding(10)).height(Length::Fixed(200.0)))
+                .width(Length::Fixed(500.0)),
+        ]
+        .spacing(10)
+        .align_x(Center)
         .into()
     }
 
+    /// 快捷键帮助浮层，列出当前生效的绑定
+    fn help_view(&self) -> Element<'_, Message> {
+        let bindings = self.shortcuts.bindings().iter().map(|binding| {
+            row![
+                text(binding.label())
+                    .size(14)
+                    .font(DEFAULT_FONT)
+                    .width(Length::Fixed(120.0)),
+                text(binding.action.description()).size(14).font(DEFAULT_FONT),
+            ]
+            .into()
+        });
+
+        opaque(
+            container(
+                container(
+                    column![
+                        text("Keyboard Shortcuts").size(20).font(DEFAULT_FONT),
+                        column(bindings).spacing(4),
+                        button("Close").on_press(Message::Shortcut(Action::ToggleHelp)),
+                    ]
+                    .spacing(10)
+                    .padding(20),
+                )
+                .style(container::rounded_box),
+            )
+            .center_x(Length::Fill)
+            .center_y(Length::Fill),
+        )
+    }
+
+    /// 纹理元数据面板
+    fn info_view(&self) -> Element<'_, Message> {
+        let lines = match self.texture_loader.info() {
+            Some(info) => info.lines(),
+            None => vec!["No texture metadata".to_string()],
+        };
+
+        container(
+            scrollable(
+                column(lines.into_iter().map(|line| text(line).size(13).font(DEFAULT_FONT).into()))
+                    .spacing(2)
+                    .padding(10),
+            )
+            .height(Length::Fixed(200.0)),
+        )
+        .width(Length::Fixed(500.0))
+        .into()
+    }
+
+    /// 直方图和统计面板
+    fn stats_view(&self) -> Element<'_, Message> {
+        let (Some(stats), Some(histogram)) = (&self.stats, &self.histogram) else {
+            return text("No texture statistics").size(14).font(DEFAULT_FONT).into();
+        };
+
+        column![
+            iced_image(histogram.clone())
+                .width(Length::Fixed(512.0))
+                .height(Length::Fixed(HISTOGRAM_HEIGHT as f32 * 2.0))
+                .content_fit(iced::ContentFit::Fill)
+                .filter_method(iced_image::FilterMethod::Nearest),
+            column(stats.lines().into_iter().map(|line| text(line).size(13).font(DEFAULT_FONT).into()))
+                .spacing(2),
+        ]
+        .spacing(10)
+        .align_x(Center)
+        .into()
+    }
+
+    /// 对比模式下的纹理预览
+    fn compare_view(&self) -> Element<'_, Message> {
+        let comparison = &self.comparison;
+
+        let metrics = match comparison.metrics() {
+            Some(Ok(metrics)) => format!(
+                "PSNR: {:.2} dB | SSIM: {:.4} | Max diff: {}",
+                metrics.psnr, metrics.ssim, metrics.max_diff
+            ),
+            Some(Err(e)) => format!("Compare error: {}", e),
+            None => "Load both PNG and KTX2 textures to compare".to_string(),
+        };
+
+        let content: Element<'_, Message> = match comparison.mode {
+            CompareMode::Off | CompareMode::SideBySide => row![
+                labeled_preview(comparison.left.as_iced_handle(), "PNG"),
+                labeled_preview(comparison.right.as_iced_handle(), "KTX2"),
+            ]
+            .spacing(20)
+            .into(),
+            CompareMode::Wipe => column![
+                labeled_preview(comparison.wipe_handle(), "PNG | KTX2"),
+                slider(0.0..=1.0, comparison.wipe(), Message::CompareWipeChanged)
+                    .step(0.01f32)
+                    .width(Length::Fixed(300.0)),
+            ]
+            .spacing(10)
+            .align_x(Center)
+            .into(),
+            CompareMode::Difference => column![
+                labeled_preview(comparison.difference_handle(), "|PNG - KTX2|"),
+                text(format!("Amplify: {:.0}x", comparison.amplify()))
+                    .size(14)
+                    .font(DEFAULT_FONT),
+                slider(1.0..=50.0, comparison.amplify(), Message::CompareAmplifyChanged)
+                    .step(1.0f32)
+                    .width(Length::Fixed(300.0)),
+            ]
+            .spacing(10)
+            .align_x(Center)
+            .into(),
+        };
+
+        column![content, text(metrics).size(14).font(DEFAULT_FONT)]
+            .spacing(10)
+            .align_x(Center)
+            .into()
+    }
+
     fn subscription(&self) -> Subscription<Message> {
-        event::listen().map(Message::EventOccurred)
+        // 只在播放时订阅定时器，按当前帧率触发
+        let playback = match &self.player {
+            Some(player) if player.is_playing() => time::every(player.frame_interval()).map(Message::AnimationTick),
+            _ => Subscription::none(),
+        };
+
+        // 设置有未保存的变化时才订阅写入定时器
+        let save_settings = if self.settings() != self.saved_settings {
+            time::every(SETTINGS_SAVE_INTERVAL).map(|_| Message::SaveSettings)
+        } else {
+            Subscription::none()
+        };
+
+        Subscription::batch([
+            event::listen().map(Message::EventOccurred),
+            file_input::subscription().map(Message::FileLoaded),
+            playback,
+            save_settings,
+        ])
     }
 
-    fn handle_event(&mut self, event: Event) {
+    fn handle_event(&mut self, event: Event) -> Task<Message> {
         match event {
             Event::Mouse(mouse_event) => {
                 self.handle_mouse_event(mouse_event);
             }
             Event::Window(window_event) => {
-                self.handle_window_event(window_event);
+                return self.handle_window_event(window_event);
+            }
+            Event::Touch(touch_event) => {
+                self.handle_touch_event(touch_event);
+            }
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) => {
+                if let Some(action) = self.shortcuts.action_for(&key, modifiers) {
+                    return self.apply_shortcut(action);
+                }
             }
             _ => {}
         }
+        Task::none()
     }
 
     fn handle_mouse_event(&mut self, mouse_event: mouse::Event) {
         match mouse_event {
             mouse::Event::CursorMoved { position } => {
                 self.mouse_position = Some(mouse::Cursor::Available(position));
-                let pos_str = format!("({}, {})", position.x, position.y);
-                self.last_event = format!("Mouse moved: {}", pos_str);
-                log_to_console("🖱️ Mouse move", &pos_str);
+                // 光标移动频率很高，只合并记录到事件日志，不输出到控制台
+                self.event_log.record_coalesced(
+                    EventCategory::Mouse,
+                    format!("Mouse moved: ({}, {})", position.x, position.y),
+                );
             }
             mouse::Event::ButtonPressed(button) => {
                 let button_name = match button {
@@ -203,8 +1099,8 @@ impl Counter {
                     mouse::Button::Other(_) => "Other",
                 };
                 self.mouse_buttons = format!("🖱️ Pressed: {}", button_name);
-                self.last_event = format!("Mouse pressed: {}", button_name);
-                log_to_console("🖱️ Mouse press", button_name);
+                self.event_log.record(EventCategory::Mouse, format!("Mouse pressed: {}", button_name));
+                log::debug!(target: logging::INPUT, "Mouse press: {}", button_name);
             }
             mouse::Event::ButtonReleased(button) => {
                 let button_name = match button {
@@ -216,17 +1112,17 @@ impl Counter {
                     mouse::Button::Other(_) => "Other",
                 };
                 self.mouse_buttons = format!("🖱️ Released: {}", button_name);
-                self.last_event = format!("Mouse released: {}", button_name);
-                log_to_console("🖱️ Mouse release", button_name);
+                self.event_log.record(EventCategory::Mouse, format!("Mouse released: {}", button_name));
+                log::debug!(target: logging::INPUT, "Mouse release: {}", button_name);
             }
             mouse::Event::CursorEntered => {
-                self.last_event = "Mouse entered window".to_string();
-                log_to_console("🖱️ Mouse enter", "Cursor entered window");
+                self.event_log.record(EventCategory::Mouse, "Mouse entered window");
+                log::debug!(target: logging::INPUT, "Cursor entered window");
             }
             mouse::Event::CursorLeft => {
                 self.mouse_position = None;
-                self.last_event = "Mouse left window".to_string();
-                log_to_console("🖱️ Mouse leave", "Cursor left window");
+                self.event_log.record(EventCategory::Mouse, "Mouse left window");
+                log::debug!(target: logging::INPUT, "Cursor left window");
             }
             mouse::Event::WheelScrolled { delta } => {
                 let delta_str = match delta {
@@ -237,40 +1133,172 @@ impl Counter {
                         format!("({:.1}, {:.1}) pixels", x, y)
                     }
                 };
-                self.last_event = format!("Wheel scrolled: {}", delta_str);
-                log_to_console("🖱️ Wheel scroll", &delta_str);
+                self.event_log.record(EventCategory::Mouse, format!("Wheel scrolled: {}", delta_str));
+                log::debug!(target: logging::INPUT, "Wheel scroll: {}", delta_str);
             }
         }
     }
 
-    fn handle_window_event(&mut self, window_event: window::Event) {
+    fn handle_touch_event(&mut self, touch_event: iced_touch::Event) {
+        let (action, finger, position) = match touch_event {
+            iced_touch::Event::FingerPressed { id, position } => ("pressed", id, position),
+            iced_touch::Event::FingerMoved { id, position } => ("moved", id, position),
+            iced_touch::Event::FingerLifted { id, position } => ("lifted", id, position),
+            iced_touch::Event::FingerLost { id, position } => ("lost", id, position),
+        };
+        let details = format!(
+            "Finger {}: {} ({:.1}, {:.1})",
+            action,
+            touch::finger_label(finger),
+            position.x,
+            position.y
+        );
+        if matches!(touch_event, iced_touch::Event::FingerMoved { .. }) {
+            self.event_log.record_coalesced(EventCategory::Touch, details);
+        } else {
+            log::debug!(target: logging::INPUT, "{}", details);
+            self.event_log.record(EventCategory::Touch, details);
+        }
+
+        // 双指捏合缩放、双指平移
+        if let Some(gesture) = self.touch.handle(touch_event) {
+            self.viewer.zoom_by(gesture.zoom);
+            self.viewer
+                .drag(gesture.pan.x / PREVIEW_WIDTH, gesture.pan.y / PREVIEW_WIDTH);
+        }
+    }
+
+    fn handle_window_event(&mut self, window_event: window::Event) -> Task<Message> {
         match window_event {
             window::Event::Resized(size) => {
-                self.last_event = format!("Window resized: {}x{}", size.width, size.height);
-                log_to_console("🪟 Window resize", &self.last_event);
+                self.window_size = Some(size);
+                let details = format!("Window resized: {}x{}", size.width, size.height);
+                log::debug!(target: logging::WINDOW, "{}", details);
+                self.event_log.record(EventCategory::Window, details);
             }
             window::Event::CloseRequested => {
-                self.last_event = "Window close requested".to_string();
-                log_to_console("🪟 Window close", "Close requested");
+                self.event_log.record(EventCategory::Window, "Window close requested");
+                log::info!(target: logging::WINDOW, "Window close requested");
+                self.persist_settings();
+                return window::latest().and_then(window::close);
             }
             window::Event::Focused => {
-                self.last_event = "Window focused".to_string();
-                log_to_console("🪟 Window focus", "Gained focus");
+                self.event_log.record(EventCategory::Window, "Window focused");
+                log::debug!(target: logging::WINDOW, "Window gained focus");
+            }
+            window::Event::FileHovered(path) => {
+                let details = format!("File hovered: {}", path.display());
+                log::debug!(target: logging::WINDOW, "{}", details);
+                self.event_log.record(EventCategory::Window, details);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            window::Event::FileDropped(path) => {
+                log::info!(target: logging::WINDOW, "File dropped: {}", path.display());
+                self.event_log.record(EventCategory::Window, format!("File dropped: {}", path.display()));
+                return Task::perform(file_input::read_path(path), Message::FileLoaded);
             }
             _ => {}
         }
+        Task::none()
     }
 }
 
-fn log_to_console(event: &str, details: &str) {
-    #[cfg(target_arch = "wasm32")]
-    {
-        web_sys::console::log_1(&format!("{}: {}", event, details).into());
+/// 检测当前环境支持的压缩格式后读取资源清单
+async fn load_assets() -> Option<Result<Gallery, String>> {
+    let capabilities = Capabilities {
+        platform: Platform::CURRENT,
+        compression: gpu_preview::supported_compression().await,
+    };
+    log::info!(target: logging::TEXTURE, "Texture capabilities: {:?}", capabilities);
+    gallery::open_assets(capabilities).await
+}
+
+/// 画廊浮层：缩略图网格，点击后在检查器中打开
+fn gallery_view(gallery: &Gallery) -> Element<'_, Message> {
+    let tiles: Vec<Element<'_, Message>> = gallery
+        .entries()
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| {
+            let size = Length::Fixed(gallery::THUMBNAIL_SIZE as f32);
+            let preview: Element<'_, Message> = match &entry.thumbnail {
+                Thumbnail::Ready(handle) => iced_image(handle.clone()).width(size).height(size).into(),
+                Thumbnail::Failed(e) => tooltip(
+                    container(text("⚠️").size(24)).center(size),
+                    container(text(e).size(12).font(DEFAULT_FONT)).padding(6).style(container::rounded_box),
+                    tooltip::Position::Bottom,
+                )
+                .into(),
+                Thumbnail::Pending | Thumbnail::Loading => {
+                    container(text("…").size(24)).center(size).into()
+                }
+            };
+
+            button(
+                column![preview, text(&entry.name).size(12).font(DEFAULT_FONT)]
+                    .spacing(4)
+                    .align_x(Center),
+            )
+            .on_press(Message::GalleryItemSelected(index))
+            .width(Length::Fixed(gallery::THUMBNAIL_SIZE as f32 + 24.0))
+            .height(Length::Fixed(gallery::TILE_HEIGHT - 10.0))
+            .into()
+        })
+        .collect();
+
+    let mut rows = Vec::new();
+    let mut tiles = tiles.into_iter().peekable();
+    while tiles.peek().is_some() {
+        let tile_row: Vec<Element<'_, Message>> = tiles.by_ref().take(gallery::COLUMNS).collect();
+        rows.push(row(tile_row).spacing(10).height(Length::Fixed(gallery::TILE_HEIGHT)).into());
     }
 
-    #[cfg(not(target_arch = "wasm32"))]
-    {
-        println!("{}: {}", event, details);
+    let grid: Element<'_, Message> = if rows.is_empty() {
+        text("No textures found").size(14).font(DEFAULT_FONT).into()
+    } else {
+        scrollable(column(rows))
+            .on_scroll(Message::GalleryScrolled)
+            .height(Length::Fixed(GALLERY_HEIGHT))
+            .into()
+    };
+
+    opaque(
+        container(
+            container(
+                column![
+                    text(format!("🖼️ {} ({} textures)", gallery.title, gallery.entries().len()))
+                        .size(18)
+                        .font(DEFAULT_FONT),
+                    grid,
+                    button("Close").on_press(Message::CloseGallery),
+                ]
+                .spacing(10)
+                .padding(20)
+                .align_x(Center),
+            )
+            .style(container::rounded_box),
+        )
+        .center_x(Length::Fill)
+        .center_y(Length::Fill),
+    )
+}
+
+/// 带标签的纹理预览，纹理未加载时显示占位文本
+fn labeled_preview(handle: Option<iced_image::Handle>, label: &str) -> Element<'_, Message> {
+    match handle {
+        Some(handle) => column![
+            text(label).size(14).font(DEFAULT_FONT),
+            iced_image(handle).width(Length::Fixed(300.0)),
+        ]
+        .spacing(5)
+        .align_x(Center)
+        .into(),
+        None => container(text(format!("{}: not loaded", label)).size(14).font(DEFAULT_FONT))
+            .width(Length::Fixed(300.0))
+            .height(Length::Fixed(200.0))
+            .center_x(Length::Fixed(300.0))
+            .center_y(Length::Fixed(200.0))
+            .into(),
     }
 }
 
@@ -290,7 +1318,7 @@ fn load_texture_from_js() -> Task<Message> {
             let response_value = match JsFuture::from(promise).await {
                 Ok(val) => val,
                 Err(e) => {
-                    log_to_console("Fetch error", &format!("{:?}", e));
+                    log::error!(target: logging::TEXTURE, "Fetch error: {:?}", e);
                     return vec![];
                 }
             };
@@ -302,7 +1330,7 @@ fn load_texture_from_js() -> Task<Message> {
             let array_buffer = match JsFuture::from(array_buffer_promise).await {
                 Ok(buf) => buf,
                 Err(e) => {
-                    log_to_console("ArrayBuffer error", &format!("{:?}", e));
+                    log::error!(target: logging::TEXTURE, "ArrayBuffer error: {:?}", e);
                     return vec![];
                 }
             };
@@ -311,19 +1339,13 @@ fn load_texture_from_js() -> Task<Message> {
             let mut vec = vec![0; u8_array.length() as usize];
             u8_array.copy_to(&mut vec);
 
-            log_to_console("Texture fetch", &format!("Loaded {} bytes", vec.len()));
+            log::info!(target: logging::TEXTURE, "Texture fetch: Loaded {} bytes", vec.len());
             vec
         },
         Message::TextureLoaded,
     )
 }
 
-/// 占位函数（非 WASM 平台）
-#[cfg(not(target_arch = "wasm32"))]
-fn load_texture_from_js() -> Task<Message> {
-    Task::none()
-}
-
 /// 从 JavaScript 加载 KTX2 纹理（仅 WASM）
 #[cfg(target_arch = "wasm32")]
 fn load_ktx2_from_js() -> Task<Message> {
@@ -340,7 +1362,7 @@ fn load_ktx2_from_js() -> Task<Message> {
             let response_value = match JsFuture::from(promise).await {
                 Ok(val) => val,
                 Err(e) => {
-                    log_to_console("KTX2 Fetch error", &format!("{:?}", e));
+                    log::error!(target: logging::TEXTURE, "KTX2 Fetch error: {:?}", e);
                     return vec![];
                 }
             };
@@ -352,7 +1374,7 @@ fn load_ktx2_from_js() -> Task<Message> {
             let array_buffer = match JsFuture::from(array_buffer_promise).await {
                 Ok(buf) => buf,
                 Err(e) => {
-                    log_to_console("KTX2 ArrayBuffer error", &format!("{:?}", e));
+                    log::error!(target: logging::TEXTURE, "KTX2 ArrayBuffer error: {:?}", e);
                     return vec![];
                 }
             };
@@ -361,15 +1383,9 @@ fn load_ktx2_from_js() -> Task<Message> {
             let mut vec = vec![0; u8_array.length() as usize];
             u8_array.copy_to(&mut vec);
 
-            log_to_console("KTX2 fetch", &format!("Loaded {} bytes", vec.len()));
+            log::info!(target: logging::TEXTURE, "KTX2 fetch: Loaded {} bytes", vec.len());
             vec
         },
         Message::Ktx2TextureLoaded,
     )
 }
-
-/// 占位函数（非 WASM 平台）
-#[cfg(not(target_arch = "wasm32"))]
-fn load_ktx2_from_js() -> Task<Message> {
-    Task::none()
-}
@@ -0,0 +1,422 @@
+//! 纹理元数据模块
+//!
+//! 收集已加载纹理的容器信息，用于查看器中的信息面板
+//! KTX2 文件会解析头部、层级索引、DFD 和键值数据
+
+use image::ImageFormat;
+use serde::Serialize;
+
+use crate::decode;
+
+/// KVD 二进制值在面板中最多显示的字节数
+const MAX_VALUE_PREVIEW: usize = 32;
+
+/// 单个 mip 层级的信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LevelInfo {
+    /// 层级编号（0 为最大层级）
+    pub level: u32,
+    /// 该层级的像素尺寸
+    pub dimensions: (u32, u32),
+    /// 文件中的字节偏移
+    pub byte_offset: u64,
+    /// 文件中的字节长度（超级压缩后）
+    pub byte_length: u64,
+    /// 超级压缩前的字节长度
+    pub uncompressed_byte_length: u64,
+}
+
+impl LevelInfo {
+    /// 相对未压缩 RGBA8 的压缩比
+    pub fn compression_ratio(&self) -> f64 {
+        let rgba8 = self.dimensions.0 as f64 * self.dimensions.1 as f64 * 4.0;
+        if self.byte_length == 0 {
+            0.0
+        } else {
+            rgba8 / self.byte_length as f64
+        }
+    }
+}
+
+/// DFD 基本描述块中的颜色信息
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DfdInfo {
+    pub color_model: String,
+    pub color_primaries: String,
+    pub transfer_function: String,
+    pub premultiplied_alpha: bool,
+    /// 纹素块的宽、高、深、第四维
+    pub texel_block_dimensions: [u8; 4],
+    /// 每个平面的字节数
+    pub bytes_planes: [u8; 8],
+    pub samples: Vec<SampleInfo>,
+}
+
+/// DFD 中的一个采样
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SampleInfo {
+    pub channel: String,
+    pub bit_offset: u16,
+    pub bit_length: u8,
+    /// 通道限定符：linear、exponent、signed、float
+    pub qualifiers: Vec<&'static str>,
+    pub lower: u32,
+    pub upper: u32,
+}
+
+impl SampleInfo {
+    /// 简短描述，例如 `R:8@0`
+    pub fn label(&self) -> String {
+        format!("{}:{}@{}", self.channel, self.bit_length, self.bit_offset)
+    }
+}
+
+/// KTX2 头部中各数据段的位置
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct IndexInfo {
+    pub dfd_byte_offset: u32,
+    pub dfd_byte_length: u32,
+    pub kvd_byte_offset: u32,
+    pub kvd_byte_length: u32,
+    pub sgd_byte_offset: u64,
+    pub sgd_byte_length: u64,
+}
+
+/// 一项键值数据
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct KeyValueInfo {
+    pub key: String,
+    /// 文本值（去掉结尾的 NUL）或完整的十六进制二进制值
+    pub value: String,
+    /// 值是否为二进制
+    pub binary: bool,
+}
+
+impl KeyValueInfo {
+    /// 以 NUL 结尾的 UTF-8 文本按文本保存，其余按十六进制保存
+    pub fn new(key: &str, value: &[u8]) -> Self {
+        let text = value.strip_suffix(&[0]).unwrap_or(value);
+        if let Ok(text) = std::str::from_utf8(text)
+            && !text.chars().any(|c| c.is_control())
+        {
+            return Self {
+                key: key.to_string(),
+                value: text.to_string(),
+                binary: false,
+            };
+        }
+
+        Self {
+            key: key.to_string(),
+            value: value.iter().map(|byte| format!("{:02x}", byte)).collect(),
+            binary: true,
+        }
+    }
+
+    /// 面板中显示的值：文本带引号，二进制值按字节分隔并截断
+    pub fn display_value(&self) -> String {
+        if self.binary {
+            format_hex_preview(&self.value)
+        } else {
+            format!("\"{}\"", self.value)
+        }
+    }
+}
+
+/// 已加载纹理的完整元数据
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TextureInfo {
+    /// 容器格式（KTX2、PNG、JPEG ...）
+    pub container: String,
+    /// 文件大小
+    pub file_size: usize,
+    /// VkFormat 名称（仅 KTX2）
+    pub vk_format: Option<String>,
+    /// 类型大小（仅 KTX2）
+    pub type_size: Option<u32>,
+    /// 像素宽、高、深
+    pub dimensions: (u32, u32, u32),
+    pub level_count: u32,
+    pub layer_count: u32,
+    pub face_count: u32,
+    /// 超级压缩方案
+    pub supercompression: String,
+    /// 数据段位置（仅 KTX2）
+    pub index: Option<IndexInfo>,
+    /// 每个 mip 层级的字节信息
+    pub levels: Vec<LevelInfo>,
+    /// 数据格式描述符
+    pub dfd: Option<DfdInfo>,
+    /// 键值元数据
+    pub key_values: Vec<KeyValueInfo>,
+}
+
+impl TextureInfo {
+    /// 从普通图像文件生成元数据
+    pub fn from_image(format: ImageFormat, dimensions: (u32, u32), file_size: usize) -> Self {
+        Self {
+            container: format!("{:?}", format).to_uppercase(),
+            file_size,
+            vk_format: None,
+            type_size: None,
+            dimensions: (dimensions.0, dimensions.1, 0),
+            level_count: 1,
+            layer_count: 0,
+            face_count: 1,
+            supercompression: "None".to_string(),
+            index: None,
+            levels: vec![LevelInfo {
+                level: 0,
+                dimensions,
+                byte_offset: 0,
+                byte_length: file_size as u64,
+                uncompressed_byte_length: dimensions.0 as u64 * dimensions.1 as u64 * 4,
+            }],
+            dfd: None,
+            key_values: Vec::new(),
+        }
+    }
+
+    /// 解析 KTX2 文件的元数据
+    pub fn from_ktx2(bytes: &[u8]) -> Result<Self, String> {
+        let reader = ktx2::Reader::new(bytes)
+            .map_err(|e| format!("Failed to create KTX2 reader: {:?}", e))?;
+        let header = reader.header();
+
+        let levels = reader
+            .levels()
+            .enumerate()
+            .map(|(i, level)| LevelInfo {
+                level: i as u32,
+                dimensions: (
+                    decode::level_extent(header.pixel_width, i as u32),
+                    decode::level_extent(header.pixel_height, i as u32),
+                ),
+                byte_offset: level_byte_offset(bytes, i),
+                byte_length: level.data.len() as u64,
+                uncompressed_byte_length: level.uncompressed_byte_length,
+            })
+            .collect();
+
+        let dfd = reader
+            .dfd_blocks()
+            .find(|block| block.header.vendor_id == 0 && block.header.descriptor_type == 0)
+            .and_then(|block| ktx2::DfdBlockBasic::parse(block.data).ok())
+            .map(|basic| DfdInfo {
+                color_model: describe(basic.header.color_model),
+                color_primaries: describe(basic.header.color_primaries),
+                transfer_function: describe(basic.header.transfer_function),
+                premultiplied_alpha: basic
+                    .header
+                    .flags
+                    .contains(ktx2::DataFormatFlags::ALPHA_PREMULTIPLIED),
+                texel_block_dimensions: basic.header.texel_block_dimensions.map(|dim| dim.get()),
+                bytes_planes: basic.header.bytes_planes,
+                samples: basic
+                    .sample_information()
+                    .map(|sample| SampleInfo {
+                        channel: channel_name(sample.channel_type).to_string(),
+                        bit_offset: sample.bit_offset,
+                        bit_length: sample.bit_length.get(),
+                        qualifiers: qualifier_names(sample.channel_type_qualifiers),
+                        lower: sample.lower,
+                        upper: sample.upper,
+                    })
+                    .collect(),
+            });
+
+        let key_values = reader
+            .key_value_data()
+            .map(|(key, value)| KeyValueInfo::new(key, value))
+            .collect();
+
+        let index = header.index;
+
+        Ok(Self {
+            container: "KTX2".to_string(),
+            file_size: bytes.len(),
+            vk_format: Some(match header.format {
+                Some(format) => format!("{:?}", format),
+                None => "UNDEFINED".to_string(),
+            }),
+            type_size: Some(header.type_size),
+            dimensions: (header.pixel_width, header.pixel_height, header.pixel_depth),
+            level_count: header.level_count,
+            layer_count: header.layer_count,
+            face_count: header.face_count,
+            supercompression: header
+                .supercompression_scheme
+                .map_or("None".to_string(), |scheme| format!("{:?}", scheme)),
+            index: Some(IndexInfo {
+                dfd_byte_offset: index.dfd_byte_offset,
+                dfd_byte_length: index.dfd_byte_length,
+                kvd_byte_offset: index.kvd_byte_offset,
+                kvd_byte_length: index.kvd_byte_length,
+                sgd_byte_offset: index.sgd_byte_offset,
+                sgd_byte_length: index.sgd_byte_length,
+            }),
+            levels,
+            dfd,
+            key_values,
+        })
+    }
+
+    /// 整个文件相对未压缩 RGBA8 基础层级的压缩比
+    pub fn compression_ratio(&self) -> f64 {
+        let (width, height, _) = self.dimensions;
+        if self.file_size == 0 {
+            0.0
+        } else {
+            width as f64 * height.max(1) as f64 * 4.0 / self.file_size as f64
+        }
+    }
+
+    /// 按面板显示顺序生成文本行
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!("Container: {}", self.container),
+            format!(
+                "File size: {} bytes ({:.2}x vs RGBA8)",
+                self.file_size,
+                self.compression_ratio()
+            ),
+        ];
+
+        if let Some(vk_format) = &self.vk_format {
+            lines.push(format!("vkFormat: {}", vk_format));
+        }
+        if let Some(type_size) = self.type_size {
+            lines.push(format!("Type size: {}", type_size));
+        }
+
+        let (width, height, depth) = self.dimensions;
+        lines.push(format!("Dimensions: {}x{}x{}", width, height, depth));
+        lines.push(format!(
+            "Levels: {} | Layers: {} | Faces: {}",
+            self.level_count, self.layer_count, self.face_count
+        ));
+        lines.push(format!("Supercompression: {}", self.supercompression));
+
+        if let Some(index) = &self.index {
+            lines.push(format!(
+                "DFD: {} bytes at {} | KVD: {} bytes at {} | SGD: {} bytes at {}",
+                index.dfd_byte_length,
+                index.dfd_byte_offset,
+                index.kvd_byte_length,
+                index.kvd_byte_offset,
+                index.sgd_byte_length,
+                index.sgd_byte_offset
+            ));
+        }
+
+        for level in &self.levels {
+            lines.push(format!(
+                "  Level {} ({}x{}): {} bytes at {}, uncompressed {} bytes, {:.2}x",
+                level.level,
+                level.dimensions.0,
+                level.dimensions.1,
+                level.byte_length,
+                level.byte_offset,
+                level.uncompressed_byte_length,
+                level.compression_ratio()
+            ));
+        }
+
+        if let Some(dfd) = &self.dfd {
+            lines.push(format!("Color model: {}", dfd.color_model));
+            lines.push(format!("Color primaries: {}", dfd.color_primaries));
+            lines.push(format!("Transfer function: {}", dfd.transfer_function));
+            lines.push(format!("Premultiplied alpha: {}", dfd.premultiplied_alpha));
+            let [x, y, z, _] = dfd.texel_block_dimensions;
+            lines.push(format!("Texel block: {}x{}x{}, {} bytes", x, y, z, dfd.bytes_planes[0]));
+            if !dfd.samples.is_empty() {
+                let samples: Vec<String> = dfd.samples.iter().map(SampleInfo::label).collect();
+                lines.push(format!("Samples: {}", samples.join(" ")));
+            }
+        }
+
+        if !self.key_values.is_empty() {
+            lines.push("Key/value data:".to_string());
+            for entry in &self.key_values {
+                lines.push(format!("  {} = {}", entry.key, entry.display_value()));
+            }
+        }
+
+        lines
+    }
+}
+
+/// 格式化 ktx2 crate 的伪枚举，`None` 表示未指定
+fn describe<T: std::fmt::Debug>(value: Option<T>) -> String {
+    match value {
+        Some(value) => format!("{:?}", value),
+        None => "Unspecified".to_string(),
+    }
+}
+
+/// 读取层级索引中第 `level` 项的 byteOffset
+fn level_byte_offset(bytes: &[u8], level: usize) -> u64 {
+    let start = ktx2::Header::LENGTH + level * ktx2::LevelIndex::LENGTH;
+    bytes
+        .get(start..start + ktx2::LevelIndex::LENGTH)
+        .and_then(|index| index.try_into().ok())
+        .map_or(0, |index| ktx2::LevelIndex::from_bytes(index).byte_offset)
+}
+
+/// 通道限定符名称
+fn qualifier_names(qualifiers: ktx2::ChannelTypeQualifiers) -> Vec<&'static str> {
+    [
+        (ktx2::ChannelTypeQualifiers::LINEAR, "linear"),
+        (ktx2::ChannelTypeQualifiers::EXPONENT, "exponent"),
+        (ktx2::ChannelTypeQualifiers::SIGNED, "signed"),
+        (ktx2::ChannelTypeQualifiers::FLOAT, "float"),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| qualifiers.contains(*flag))
+    .map(|(_, name)| name)
+    .collect()
+}
+
+/// RGBSDA 颜色模型下的通道名称
+fn channel_name(channel_type: u8) -> &'static str {
+    match channel_type {
+        0 => "R",
+        1 => "G",
+        2 => "B",
+        13 => "S",
+        14 => "D",
+        15 => "A",
+        _ => "?",
+    }
+}
+
+/// 十六进制值的预览，超出部分只显示总字节数
+fn format_hex_preview(hex: &str) -> String {
+    let bytes: Vec<&str> = hex
+        .as_bytes()
+        .chunks(2)
+        .map(|byte| std::str::from_utf8(byte).unwrap_or("??"))
+        .collect();
+    let preview = bytes[..bytes.len().min(MAX_VALUE_PREVIEW)].join(" ");
+    if bytes.len() > MAX_VALUE_PREVIEW {
+        format!("{} ... ({} bytes)", preview, bytes.len())
+    } else {
+        preview
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_value_display() {
+        assert_eq!(KeyValueInfo::new("KTXwriter", b"toktx v4.3\0").display_value(), "\"toktx v4.3\"");
+        assert_eq!(KeyValueInfo::new("custom", &[0x01, 0xff]).display_value(), "01 ff");
+
+        // JSON 中保留完整的值，只有面板显示会截断
+        let long = KeyValueInfo::new("custom", &[0xab; 40]);
+        assert_eq!(long.value, "ab".repeat(40));
+        assert_eq!(long.display_value(), format!("{} ... (40 bytes)", vec!["ab"; 32].join(" ")));
+    }
+}
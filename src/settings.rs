@@ -0,0 +1,129 @@
+//! 设置持久化模块
+//!
+//! 保存上次打开的纹理、缩放、视图模式、计数器和窗口大小，下次启动时恢复
+//! - 原生环境：写入配置目录下的 `iced_web_app/settings.json`
+//! - WASM 环境：写入 `localStorage`
+
+use serde::{Deserialize, Serialize};
+
+use crate::compare::CompareMode;
+
+/// localStorage 中保存设置的键
+#[cfg(target_arch = "wasm32")]
+const STORAGE_KEY: &str = "iced_web_app.settings";
+
+/// 配置目录下的设置文件
+#[cfg(not(target_arch = "wasm32"))]
+const SETTINGS_FILE: &str = "iced_web_app/settings.json";
+
+/// 需要跨会话保存的应用设置
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// 上次打开的纹理：原生环境为文件路径，WASM 环境为 URL
+    pub last_texture: Option<String>,
+    /// 预览缩放倍数
+    pub zoom: f32,
+    /// 预览是否适应窗口
+    pub fit_to_window: bool,
+    /// 对比模式
+    pub compare_mode: CompareMode,
+    /// 计数器值
+    pub counter: i64,
+    /// 窗口大小（逻辑像素）
+    pub window_size: Option<(f32, f32)>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            last_texture: None,
+            zoom: 1.0,
+            fit_to_window: false,
+            compare_mode: CompareMode::Off,
+            counter: 0,
+            window_size: None,
+        }
+    }
+}
+
+impl Settings {
+    /// 读取保存的设置，不存在或无法解析时返回默认值
+    pub fn load() -> Self {
+        let Some(json) = read_storage() else {
+            return Self::default();
+        };
+
+        serde_json::from_str(&json).unwrap_or_else(|e| {
+            log::warn!(target: crate::logging::APP, "Ignoring invalid settings: {}", e);
+            Self::default()
+        })
+    }
+
+    /// 保存设置
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        write_storage(&json)
+    }
+}
+
+/// 设置文件路径：`$XDG_CONFIG_HOME`、`~/.config` 或 `%APPDATA%`
+#[cfg(not(target_arch = "wasm32"))]
+fn settings_path() -> Option<std::path::PathBuf> {
+    use std::path::PathBuf;
+
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    Some(config_dir.join(SETTINGS_FILE))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_storage() -> Option<String> {
+    std::fs::read_to_string(settings_path()?).ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write_storage(json: &str) -> Result<(), String> {
+    let path = settings_path().ok_or("No config directory available")?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("无法创建目录 {}: {}", dir.display(), e))?;
+    }
+    std::fs::write(&path, json).map_err(|e| format!("无法写入文件 {}: {}", path.display(), e))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read_storage() -> Option<String> {
+    local_storage()?.get_item(STORAGE_KEY).ok()?
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write_storage(json: &str) -> Result<(), String> {
+    local_storage()
+        .ok_or("localStorage is not available")?
+        .set_item(STORAGE_KEY, json)
+        .map_err(|e| format!("Failed to write localStorage: {:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let settings: Settings = serde_json::from_str(r#"{"counter": 7, "compare_mode": "Wipe"}"#).unwrap();
+        assert_eq!(settings.counter, 7);
+        assert_eq!(settings.compare_mode, CompareMode::Wipe);
+        assert_eq!(settings.zoom, 1.0);
+        assert_eq!(settings.last_texture, None);
+    }
+}
@@ -0,0 +1,204 @@
+//! 键盘快捷键模块
+//!
+//! 提供可重新绑定的快捷键表，把 iced 键盘事件映射为应用动作，
+//! 并为帮助面板生成按键说明
+
+use iced::keyboard::{Key, Modifiers, key::Named};
+
+/// 快捷键触发的应用动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Action {
+    Increment,
+    Decrement,
+    ZoomIn,
+    ZoomOut,
+    PanLeft,
+    PanRight,
+    PanUp,
+    PanDown,
+    PrevMip,
+    NextMip,
+    ToggleRed,
+    ToggleGreen,
+    ToggleBlue,
+    ToggleAlpha,
+    OpenFile,
+    FitToWindow,
+    TogglePlayback,
+    PrevFrame,
+    NextFrame,
+    ToggleHelp,
+}
+
+impl Action {
+    /// 帮助面板中显示的说明
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Increment => "Increment counter",
+            Action::Decrement => "Decrement counter",
+            Action::ZoomIn => "Zoom in",
+            Action::ZoomOut => "Zoom out",
+            Action::PanLeft => "Pan left",
+            Action::PanRight => "Pan right",
+            Action::PanUp => "Pan up",
+            Action::PanDown => "Pan down",
+            Action::PrevMip => "Previous (larger) mip level",
+            Action::NextMip => "Next (smaller) mip level",
+            Action::ToggleRed => "Toggle red channel",
+            Action::ToggleGreen => "Toggle green channel",
+            Action::ToggleBlue => "Toggle blue channel",
+            Action::ToggleAlpha => "Toggle alpha channel",
+            Action::OpenFile => "Open file",
+            Action::FitToWindow => "Fit to window",
+            Action::TogglePlayback => "Play / pause animation",
+            Action::PrevFrame => "Previous animation frame",
+            Action::NextFrame => "Next animation frame",
+            Action::ToggleHelp => "Show / hide this help",
+        }
+    }
+}
+
+/// 单个快捷键绑定
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    /// 按键（字符按键统一用小写）
+    pub key: Key,
+    /// 是否需要 Ctrl（macOS 上为 Cmd）
+    pub command: bool,
+    /// 触发的动作
+    pub action: Action,
+}
+
+impl Binding {
+    fn matches(&self, key: &Key, modifiers: Modifiers) -> bool {
+        if self.command != modifiers.command() {
+            return false;
+        }
+        match (&self.key, key) {
+            (Key::Character(bound), Key::Character(pressed)) => bound.eq_ignore_ascii_case(pressed),
+            (bound, pressed) => bound == pressed,
+        }
+    }
+
+    /// 按键的可读名称，例如 `Ctrl+O`
+    pub fn label(&self) -> String {
+        let key = match &self.key {
+            Key::Character(c) => c.to_uppercase(),
+            Key::Named(named) => format!("{:?}", named),
+            Key::Unidentified => "?".to_string(),
+        };
+        if self.command { format!("Ctrl+{}", key) } else { key }
+    }
+}
+
+/// 快捷键表
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortcutMap {
+    bindings: Vec<Binding>,
+}
+
+impl ShortcutMap {
+    /// 空的快捷键表
+    pub fn empty() -> Self {
+        Self { bindings: Vec::new() }
+    }
+
+    /// 绑定按键；同一按键组合已有绑定时会被替换
+    pub fn bind(&mut self, key: Key, command: bool, action: Action) {
+        let key = normalize(key);
+        self.bindings
+            .retain(|binding| !(binding.key == key && binding.command == command));
+        self.bindings.push(Binding { key, command, action });
+        // 稳定排序：同一动作的多个按键保持绑定先后顺序
+        self.bindings.sort_by_key(|binding| binding.action);
+    }
+
+    /// 查找按键对应的动作
+    pub fn action_for(&self, key: &Key, modifiers: Modifiers) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.matches(key, modifiers))
+            .map(|binding| binding.action)
+    }
+
+    /// 当前生效的所有绑定，按动作顺序排列
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+}
+
+impl Default for ShortcutMap {
+    fn default() -> Self {
+        let mut map = Self::empty();
+
+        map.bind(Key::Character("+".into()), false, Action::Increment);
+        map.bind(Key::Character("=".into()), false, Action::Increment);
+        map.bind(Key::Character("-".into()), false, Action::Decrement);
+        map.bind(Key::Character("+".into()), true, Action::ZoomIn);
+        map.bind(Key::Character("=".into()), true, Action::ZoomIn);
+        map.bind(Key::Character("-".into()), true, Action::ZoomOut);
+        map.bind(Key::Named(Named::ArrowLeft), false, Action::PanLeft);
+        map.bind(Key::Named(Named::ArrowRight), false, Action::PanRight);
+        map.bind(Key::Named(Named::ArrowUp), false, Action::PanUp);
+        map.bind(Key::Named(Named::ArrowDown), false, Action::PanDown);
+        map.bind(Key::Named(Named::PageUp), false, Action::PrevMip);
+        map.bind(Key::Named(Named::PageDown), false, Action::NextMip);
+        map.bind(Key::Character("r".into()), false, Action::ToggleRed);
+        map.bind(Key::Character("g".into()), false, Action::ToggleGreen);
+        map.bind(Key::Character("b".into()), false, Action::ToggleBlue);
+        map.bind(Key::Character("a".into()), false, Action::ToggleAlpha);
+        map.bind(Key::Character("o".into()), true, Action::OpenFile);
+        map.bind(Key::Character("f".into()), false, Action::FitToWindow);
+        map.bind(Key::Named(Named::Space), false, Action::TogglePlayback);
+        map.bind(Key::Character(",".into()), false, Action::PrevFrame);
+        map.bind(Key::Character(".".into()), false, Action::NextFrame);
+        map.bind(Key::Character("?".into()), false, Action::ToggleHelp);
+        map.bind(Key::Named(Named::F1), false, Action::ToggleHelp);
+
+        map
+    }
+}
+
+/// 字符按键统一转为小写，便于忽略 Shift 匹配
+fn normalize(key: Key) -> Key {
+    match key {
+        Key::Character(c) => Key::Character(c.to_lowercase().into()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_bindings() {
+        let map = ShortcutMap::default();
+        assert_eq!(
+            map.action_for(&Key::Character("O".into()), Modifiers::CTRL),
+            Some(Action::OpenFile)
+        );
+        assert_eq!(
+            map.action_for(&Key::Character("o".into()), Modifiers::empty()),
+            None
+        );
+        assert_eq!(
+            map.action_for(&Key::Named(Named::PageDown), Modifiers::empty()),
+            Some(Action::NextMip)
+        );
+    }
+
+    #[test]
+    fn test_rebind_replaces_existing() {
+        let mut map = ShortcutMap::default();
+        map.bind(Key::Character("f".into()), false, Action::ZoomIn);
+        assert_eq!(
+            map.action_for(&Key::Character("f".into()), Modifiers::empty()),
+            Some(Action::ZoomIn)
+        );
+
+        // 重新绑定后仍按动作顺序排列
+        let actions: Vec<Action> = map.bindings().iter().map(|binding| binding.action).collect();
+        assert!(actions.is_sorted());
+    }
+}
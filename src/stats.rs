@@ -0,0 +1,215 @@
+//! 纹理统计模块
+//!
+//! 对 `TextureLoader::data()` 返回的 RGBA8 数据做质量检查：
+//! 每通道直方图、最小/最大/平均值/标准差、全透明和全不透明像素数、常量通道检测
+
+/// 通道名称，顺序与 RGBA8 数据一致
+pub const CHANNEL_NAMES: [&str; 4] = ["R", "G", "B", "A"];
+
+/// 直方图图像中每个通道的绘制颜色
+const CHANNEL_COLORS: [[u8; 3]; 4] = [[230, 60, 60], [60, 200, 60], [70, 110, 240], [180, 180, 180]];
+
+/// 单个通道的统计结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChannelStats {
+    pub histogram: [u32; 256],
+    pub min: u8,
+    pub max: u8,
+    pub mean: f64,
+    pub stddev: f64,
+}
+
+impl ChannelStats {
+    /// 通道中所有像素取值相同
+    pub fn is_constant(&self) -> bool {
+        self.min == self.max
+    }
+}
+
+/// 整张纹理的统计结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureStats {
+    /// R、G、B、A 四个通道
+    pub channels: [ChannelStats; 4],
+    /// 像素总数
+    pub pixel_count: usize,
+    /// Alpha 为 0 的像素数
+    pub transparent_pixels: usize,
+    /// Alpha 为 255 的像素数
+    pub opaque_pixels: usize,
+}
+
+impl TextureStats {
+    /// 统计 RGBA8 像素数据
+    pub fn compute(rgba: &[u8]) -> Self {
+        let mut histograms = [[0u32; 256]; 4];
+        let mut transparent_pixels = 0;
+        let mut opaque_pixels = 0;
+
+        for pixel in rgba.chunks_exact(4) {
+            for (c, &value) in pixel.iter().enumerate() {
+                histograms[c][value as usize] += 1;
+            }
+            match pixel[3] {
+                0 => transparent_pixels += 1,
+                255 => opaque_pixels += 1,
+                _ => {}
+            }
+        }
+
+        let pixel_count = rgba.len() / 4;
+        Self {
+            channels: histograms.map(|histogram| channel_stats(histogram, pixel_count)),
+            pixel_count,
+            transparent_pixels,
+            opaque_pixels,
+        }
+    }
+
+    /// 取值恒定的通道名称
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn constant_channels(&self) -> Vec<&'static str> {
+        self.channels
+            .iter()
+            .zip(CHANNEL_NAMES)
+            .filter(|(channel, _)| channel.is_constant())
+            .map(|(_, name)| name)
+            .collect()
+    }
+
+    /// 按面板显示顺序生成文本行
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .channels
+            .iter()
+            .zip(CHANNEL_NAMES)
+            .map(|(channel, name)| {
+                format!(
+                    "{}: min {} | max {} | mean {:.2} | stddev {:.2}",
+                    name, channel.min, channel.max, channel.mean, channel.stddev
+                )
+            })
+            .collect();
+
+        let percent = |count: usize| {
+            if self.pixel_count == 0 {
+                0.0
+            } else {
+                count as f64 * 100.0 / self.pixel_count as f64
+            }
+        };
+        lines.push(format!(
+            "Transparent: {} ({:.1}%) | Opaque: {} ({:.1}%)",
+            self.transparent_pixels,
+            percent(self.transparent_pixels),
+            self.opaque_pixels,
+            percent(self.opaque_pixels)
+        ));
+
+        let constant: Vec<String> = self
+            .channels
+            .iter()
+            .zip(CHANNEL_NAMES)
+            .filter(|(channel, _)| channel.is_constant())
+            .map(|(channel, name)| format!("{}={}", name, channel.min))
+            .collect();
+        if constant.is_empty() {
+            lines.push("Constant channels: none".to_string());
+        } else {
+            lines.push(format!("Constant channels: {}", constant.join(", ")));
+        }
+
+        lines
+    }
+
+    /// 绘制 256 x `height` 的直方图图像（RGBA8）
+    ///
+    /// 四个通道叠加绘制，高度按最高的柱子归一化
+    pub fn histogram_image(&self, height: u32) -> Vec<u8> {
+        let height = height.max(1) as usize;
+        let mut pixels = vec![0u8; 256 * height * 4];
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[24, 24, 24, 255]);
+        }
+
+        let peak = self
+            .channels
+            .iter()
+            .flat_map(|channel| channel.histogram.iter())
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1) as f64;
+
+        for (channel, color) in self.channels.iter().zip(CHANNEL_COLORS) {
+            for (x, &count) in channel.histogram.iter().enumerate() {
+                let bar = ((count as f64 / peak) * height as f64).round() as usize;
+                for y in height - bar.min(height)..height {
+                    let offset = (y * 256 + x) * 4;
+                    // 加色混合，重叠部分会变亮
+                    for c in 0..3 {
+                        pixels[offset + c] = pixels[offset + c].saturating_add(color[c] / 2);
+                    }
+                }
+            }
+        }
+
+        pixels
+    }
+}
+
+fn channel_stats(histogram: [u32; 256], pixel_count: usize) -> ChannelStats {
+    let min = histogram.iter().position(|&count| count > 0).unwrap_or(0) as u8;
+    let max = histogram.iter().rposition(|&count| count > 0).unwrap_or(0) as u8;
+
+    let (mean, stddev) = if pixel_count == 0 {
+        (0.0, 0.0)
+    } else {
+        let n = pixel_count as f64;
+        let sum: f64 = histogram
+            .iter()
+            .enumerate()
+            .map(|(value, &count)| value as f64 * count as f64)
+            .sum();
+        let mean = sum / n;
+        let variance: f64 = histogram
+            .iter()
+            .enumerate()
+            .map(|(value, &count)| {
+                let d = value as f64 - mean;
+                d * d * count as f64
+            })
+            .sum::<f64>()
+            / n;
+        (mean, variance.sqrt())
+    };
+
+    ChannelStats {
+        histogram,
+        min,
+        max,
+        mean,
+        stddev,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_stats() {
+        // 两个像素：一个全透明黑色，一个不透明白色，B 通道恒为 7
+        let rgba = [0, 0, 7, 0, 255, 255, 7, 255];
+        let stats = TextureStats::compute(&rgba);
+
+        assert_eq!(stats.pixel_count, 2);
+        assert_eq!(stats.transparent_pixels, 1);
+        assert_eq!(stats.opaque_pixels, 1);
+        assert_eq!(stats.channels[0].min, 0);
+        assert_eq!(stats.channels[0].max, 255);
+        assert!((stats.channels[0].mean - 127.5).abs() < 1e-9);
+        assert!((stats.channels[0].stddev - 127.5).abs() < 1e-9);
+        assert_eq!(stats.constant_channels(), vec!["B"]);
+    }
+}
@@ -3,15 +3,49 @@
 //! 提供纹理加载、转换和管理的功能
 //! 支持 PNG、JPEG 和 KTX2 格式
 
+use std::borrow::Cow;
+
 use iced::widget::image::Handle;
 use image::{GenericImageView, ImageFormat};
 
+use crate::alpha;
+use crate::color_space::{self, ColorSpace};
+use crate::decode;
+use crate::metadata::TextureInfo;
+
+/// KTX2 文件标识符：«KTX 20»\r\n\x1A\n
+pub const KTX2_IDENTIFIER: [u8; 12] = [
+    0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+];
+
+/// 纹理源格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFormat {
+    /// KTX2 容器
+    Ktx2,
+    /// image crate 支持的普通图像格式（PNG、JPEG 等）
+    Image(ImageFormat),
+}
+
+/// 根据文件头识别纹理格式
+pub fn sniff_format(bytes: &[u8]) -> Option<SourceFormat> {
+    if bytes.starts_with(&KTX2_IDENTIFIER) {
+        return Some(SourceFormat::Ktx2);
+    }
+    image::guess_format(bytes).ok().map(SourceFormat::Image)
+}
+
 /// 纹理加载器
+#[derive(Clone)]
 pub struct TextureLoader {
     /// 图像数据
     image_data: Option<Vec<u8>>,
     /// 图像尺寸
     dimensions: Option<(u32, u32)>,
+    /// 源文件元数据
+    info: Option<TextureInfo>,
+    /// 像素数据的色彩空间
+    color_space: ColorSpace,
 }
 
 impl TextureLoader {
@@ -20,25 +54,49 @@ impl TextureLoader {
         Self {
             image_data: None,
             dimensions: None,
+            info: None,
+            color_space: ColorSpace::Srgb,
         }
     }
 
     /// 从字节数据加载 PNG 图像
     pub fn load_from_png_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
-        let reader = ImageFormat::Png;
-        let img = image::load_from_memory_with_format(bytes, reader)
-            .map_err(|e| format!("Failed to load PNG: {}", e))?;
+        self.load_from_image_bytes(bytes, ImageFormat::Png)
+    }
+
+    /// 从字节数据加载普通图像（PNG、JPEG 等）
+    pub fn load_from_image_bytes(&mut self, bytes: &[u8], format: ImageFormat) -> Result<(), String> {
+        let img = image::load_from_memory_with_format(bytes, format)
+            .map_err(|e| format!("Failed to load {:?}: {}", format, e))?;
 
         self.dimensions = Some(img.dimensions());
         self.image_data = Some(img.to_rgba8().to_vec());
+        self.info = Some(TextureInfo::from_image(format, img.dimensions(), bytes.len()));
+        // 只有 PNG 能声明色彩空间，其他格式按 sRGB 处理
+        self.color_space = match format {
+            ImageFormat::Png => color_space::detect_png(bytes).unwrap_or_default(),
+            _ => ColorSpace::Srgb,
+        };
 
         Ok(())
     }
 
+    /// 自动识别格式并加载，返回识别出的格式
+    pub fn load_from_bytes(&mut self, bytes: &[u8]) -> Result<SourceFormat, String> {
+        let format = sniff_format(bytes).ok_or("Unrecognized texture format")?;
+        match format {
+            SourceFormat::Ktx2 => self.load_from_ktx2_bytes(bytes)?,
+            SourceFormat::Image(image_format) => self.load_from_image_bytes(bytes, image_format)?,
+        }
+        Ok(format)
+    }
+
     /// 从字节数据加载 KTX2 纹理
     ///
     /// 注意：此方法使用纯 Rust 的 ktx2 crate，无原生依赖
     /// 适用于 WASM 环境
+    ///
+    /// 块压缩和 16 位 / 浮点格式由 `decode` 模块在 CPU 上解码为 RGBA8
     pub fn load_from_ktx2_bytes(&mut self, bytes: &[u8]) -> Result<(), String> {
         // 使用纯 Rust 的 ktx2 库解析
         let reader = ktx2::Reader::new(bytes)
@@ -46,46 +104,36 @@ impl TextureLoader {
 
         // 获取纹理信息
         let header = reader.header();
-        let width = header.pixel_width;
-        let height = if header.pixel_height > 0 {
-            header.pixel_height
-        } else {
-            1
+        let width = decode::level_extent(header.pixel_width, 0);
+        let height = decode::level_extent(header.pixel_height, 0);
+        let format = header
+            .format
+            .ok_or("Basis Universal (UASTC / ETC1S) textures cannot be decoded yet")?;
+        let too_large = || format!("KTX2 texture {}x{} is too large", width, height);
+        let image_size = decode::image_size(format, width, height)
+            .ok_or_else(|| format!("Unsupported KTX2 format: {:?}", format))?;
+        let level_size = decode::level_size(&header, 0).ok_or_else(too_large)?;
+
+        // 优先按规范读取层级索引（紧跟在头部之后）
+        // 纹理数组/立方体贴图只显示第一层的第一个面，动画播放见 `animation` 模块
+        let expected_size = (width as usize)
+            .checked_mul(height as usize * 4)
+            .ok_or_else(too_large)?; // RGBA8
+        let mut texture_data = match reader.levels().next() {
+            Some(level) if header.supercompression_scheme.is_some() || level.data.len() as u64 == level_size => {
+                let data = decode::inflate(
+                    header.supercompression_scheme,
+                    level.data,
+                    level.uncompressed_byte_length,
+                    level_size,
+                )?;
+                let image = data.get(..image_size).ok_or("KTX2 level is shorter than one image")?;
+                decode::decode(format, image, width, height)?.into_rgba8()
+            }
+            _ => legacy_level_data(bytes, &header)?,
         };
 
-        // ktx2 crate 的 levels() 可能返回空数据，需要手动解析
-        // 从 header 读取 DFD 偏移和长度，然后找到 Level Index
-
-        let dfd_offset = header.data_format_descriptor[0] as usize;
-        let dfd_length = header.data_format_descriptor[1] as usize;
-        let level_index_offset = dfd_offset + dfd_length;
-
-        // Level Index 包含 3 个 u64 值
-        if level_index_offset + 24 > bytes.len() {
-            return Err("KTX2 file too short for Level Index".to_string());
-        }
-
-        let index_data = &bytes[level_index_offset..level_index_offset + 24];
-        let byte_offset = u64::from_le_bytes(index_data[0..8].try_into().unwrap()) as usize;
-        let uncompressed_length = u64::from_le_bytes(index_data[16..24].try_into().unwrap()) as usize;
-
-        // 验证偏移
-        if byte_offset >= bytes.len() {
-            return Err(format!("Invalid KTX2 byteOffset: {}", byte_offset));
-        }
-
-        // 读取纹理数据
-        let data_start = byte_offset;
-        let data_end = byte_offset + uncompressed_length;
-
-        if data_end > bytes.len() {
-            return Err("KTX2 data extends beyond file".to_string());
-        }
-
-        let texture_data = bytes[data_start..data_end].to_vec();
-
         // 验证数据大小
-        let expected_size = width as usize * height as usize * 4; // RGBA8
         if texture_data.len() != expected_size {
             return Err(format!(
                 "KTX2 data size mismatch: expected {} bytes, got {} bytes",
@@ -94,18 +142,27 @@ impl TextureLoader {
             ));
         }
 
+        let color_space = color_space::detect_ktx2(bytes).unwrap_or_default();
+
+        // 预乘 Alpha 的数据还原为直通 Alpha，后续显示、统计和导出都按直通处理
+        if alpha::is_premultiplied_ktx2(bytes) {
+            alpha::unpremultiply(&mut texture_data, color_space);
+        }
+
         self.dimensions = Some((width, height));
         self.image_data = Some(texture_data);
+        self.info = TextureInfo::from_ktx2(bytes).ok();
+        self.color_space = color_space;
 
         Ok(())
     }
 
-    /// 获取 iced 图像句柄
+    /// 获取 iced 图像句柄（线性数据会先转换为 sRGB 编码）
     pub fn as_iced_handle(&self) -> Option<Handle> {
-        let data = self.image_data.as_ref()?;
+        let data = self.display_data()?;
         let dims = self.dimensions?;
 
-        Some(Handle::from_rgba(dims.0, dims.1, data.clone()))
+        Some(Handle::from_rgba(dims.0, dims.1, data.into_owned()))
     }
 
     /// 获取图像尺寸
@@ -113,10 +170,58 @@ impl TextureLoader {
         self.dimensions
     }
 
-    /// 获取图像数据
+    /// 获取图像数据（按 `color_space()` 编码）
     pub fn data(&self) -> Option<&[u8]> {
         self.image_data.as_deref()
     }
+
+    /// 获取用于显示的 sRGB 编码图像数据
+    pub fn display_data(&self) -> Option<Cow<'_, [u8]>> {
+        Some(color_space::to_display(self.data()?, self.color_space))
+    }
+
+    /// 获取像素数据的色彩空间
+    pub fn color_space(&self) -> ColorSpace {
+        self.color_space
+    }
+
+    /// 获取源文件元数据
+    pub fn info(&self) -> Option<&TextureInfo> {
+        self.info.as_ref()
+    }
+}
+
+/// 读取旧版 `ktx2_generator` 生成的文件
+///
+/// 旧布局把层级索引放在 DFD 之后，ktx2 crate 的 levels() 会返回空数据，
+/// 需要从 header 读取 DFD 偏移和长度后手动解析
+fn legacy_level_data(bytes: &[u8], header: &ktx2::Header) -> Result<Vec<u8>, String> {
+    let dfd_offset = header.index.dfd_byte_offset as usize;
+    let dfd_length = header.index.dfd_byte_length as usize;
+    let level_index_offset = dfd_offset + dfd_length;
+
+    // Level Index 包含 3 个 u64 值
+    if level_index_offset + 24 > bytes.len() {
+        return Err("KTX2 file too short for Level Index".to_string());
+    }
+
+    let index_data = &bytes[level_index_offset..level_index_offset + 24];
+    let byte_offset = u64::from_le_bytes(index_data[0..8].try_into().unwrap()) as usize;
+    let uncompressed_length = u64::from_le_bytes(index_data[16..24].try_into().unwrap()) as usize;
+
+    // 验证偏移
+    if byte_offset >= bytes.len() {
+        return Err(format!("Invalid KTX2 byteOffset: {}", byte_offset));
+    }
+
+    // 读取纹理数据
+    let data_start = byte_offset;
+    let data_end = byte_offset
+        .checked_add(uncompressed_length)
+        .filter(|&end| end <= bytes.len())
+        .ok_or("KTX2 data extends beyond file")?;
+
+    Ok(bytes[data_start..data_end].to_vec())
 }
 
 impl Default for TextureLoader {
@@ -135,4 +240,16 @@ mod tests {
         assert!(loader.image_data.is_none());
         assert!(loader.dimensions.is_none());
     }
+
+    #[test]
+    fn test_sniff_format() {
+        let mut ktx2 = KTX2_IDENTIFIER.to_vec();
+        ktx2.extend_from_slice(&[0; 68]);
+        assert_eq!(sniff_format(&ktx2), Some(SourceFormat::Ktx2));
+        assert_eq!(
+            sniff_format(b"\x89PNG\r\n\x1a\n\0\0\0\0"),
+            Some(SourceFormat::Image(ImageFormat::Png))
+        );
+        assert_eq!(sniff_format(b"not a texture"), None);
+    }
 }
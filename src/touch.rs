@@ -0,0 +1,131 @@
+//! 触摸事件模块
+//!
+//! 跟踪 `Event::Touch` 中每根手指的位置，并把双指操作识别为
+//! 捏合缩放和双指平移手势
+
+use std::collections::BTreeMap;
+
+use iced::touch::{self, Finger};
+use iced::{Point, Vector};
+
+/// 双指手势在一次事件中的变化量
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gesture {
+    /// 缩放倍率（> 1.0 为放大）
+    pub zoom: f32,
+    /// 双指中点的位移（逻辑像素）
+    pub pan: Vector,
+}
+
+/// 手指跟踪器
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TouchTracker {
+    /// 当前按下的手指及其位置
+    fingers: BTreeMap<u64, Point>,
+}
+
+impl TouchTracker {
+    /// 处理触摸事件
+    ///
+    /// 恰好两根手指移动时返回相对上一次位置的手势变化
+    pub fn handle(&mut self, event: touch::Event) -> Option<Gesture> {
+        match event {
+            touch::Event::FingerPressed { id, position } => {
+                self.fingers.insert(id.0, position);
+                None
+            }
+            touch::Event::FingerMoved { id, position } => {
+                let before = self.pinch();
+                self.fingers.insert(id.0, position);
+                let after = self.pinch();
+
+                match (before, after) {
+                    (Some((old_mid, old_dist)), Some((new_mid, new_dist))) if old_dist > 0.0 => {
+                        Some(Gesture {
+                            zoom: new_dist / old_dist,
+                            pan: new_mid - old_mid,
+                        })
+                    }
+                    _ => None,
+                }
+            }
+            touch::Event::FingerLifted { id, .. } | touch::Event::FingerLost { id, .. } => {
+                self.fingers.remove(&id.0);
+                None
+            }
+        }
+    }
+
+    /// 显示用文本，格式与鼠标信息一致
+    pub fn describe(&self) -> String {
+        if self.fingers.is_empty() {
+            return "Touch: No fingers".to_string();
+        }
+
+        let fingers: Vec<String> = self
+            .fingers
+            .iter()
+            .map(|(id, pos)| format!("#{} ({:.1}, {:.1})", id, pos.x, pos.y))
+            .collect();
+        format!("Touch: {}", fingers.join(" "))
+    }
+
+    /// 双指中点和间距，手指数不为 2 时返回 `None`
+    fn pinch(&self) -> Option<(Point, f32)> {
+        if self.fingers.len() != 2 {
+            return None;
+        }
+        let mut points = self.fingers.values();
+        let a = *points.next()?;
+        let b = *points.next()?;
+        let mid = Point::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+        Some((mid, a.distance(b)))
+    }
+}
+
+/// 格式化手指编号
+pub fn finger_label(finger: Finger) -> String {
+    format!("#{}", finger.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(tracker: &mut TouchTracker, id: u64, x: f32, y: f32) {
+        tracker.handle(touch::Event::FingerPressed {
+            id: Finger(id),
+            position: Point::new(x, y),
+        });
+    }
+
+    #[test]
+    fn test_pinch_gesture() {
+        let mut tracker = TouchTracker::default();
+        press(&mut tracker, 1, 0.0, 0.0);
+        press(&mut tracker, 2, 100.0, 0.0);
+
+        let gesture = tracker
+            .handle(touch::Event::FingerMoved {
+                id: Finger(2),
+                position: Point::new(200.0, 0.0),
+            })
+            .expect("two fingers should produce a gesture");
+        assert!((gesture.zoom - 2.0).abs() < 1e-6);
+        assert!((gesture.pan.x - 50.0).abs() < 1e-6);
+
+        tracker.handle(touch::Event::FingerLifted {
+            id: Finger(1),
+            position: Point::ORIGIN,
+        });
+        assert_eq!(tracker.describe(), "Touch: #2 (200.0, 0.0)");
+        assert!(
+            tracker
+                .handle(touch::Event::FingerMoved {
+                    id: Finger(2),
+                    position: Point::new(0.0, 0.0),
+                })
+                .is_none()
+        );
+    }
+}
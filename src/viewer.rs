@@ -0,0 +1,257 @@
+//! 纹理预览视图状态
+//!
+//! 管理预览区域的缩放、平移、mip 层级、通道开关和适应窗口模式，
+//! 并负责把原始 RGBA8 数据转换为实际显示的图像
+
+use iced::Rectangle;
+use image::imageops::FilterType;
+
+/// 最小缩放倍数（1.0 表示显示整张纹理）
+const MIN_ZOOM: f32 = 1.0;
+/// 最大缩放倍数
+const MAX_ZOOM: f32 = 32.0;
+/// 每次缩放的倍率
+const ZOOM_STEP: f32 = 1.25;
+/// 每次平移的距离（相对于可见区域的比例）
+const PAN_STEP: f32 = 0.1;
+
+/// 预览视图状态
+#[derive(Debug, Clone, PartialEq)]
+pub struct ViewerState {
+    /// 缩放倍数
+    zoom: f32,
+    /// 可见区域中心（归一化坐标，0.0 ~ 1.0）
+    center: (f32, f32),
+    /// 当前显示的 mip 层级
+    mip_level: u32,
+    /// R、G、B、A 通道是否显示
+    channels: [bool; 4],
+    /// 是否铺满可用宽度
+    fit_to_window: bool,
+}
+
+impl ViewerState {
+    /// 创建默认视图状态
+    pub fn new() -> Self {
+        Self {
+            zoom: MIN_ZOOM,
+            center: (0.5, 0.5),
+            mip_level: 0,
+            channels: [true; 4],
+            fit_to_window: false,
+        }
+    }
+
+    pub fn fit_to_window(&self) -> bool {
+        self.fit_to_window
+    }
+
+    pub fn zoom(&self) -> f32 {
+        self.zoom
+    }
+
+    pub fn mip_level(&self) -> u32 {
+        self.mip_level
+    }
+
+    pub fn channels(&self) -> [bool; 4] {
+        self.channels
+    }
+
+    /// 放大
+    pub fn zoom_in(&mut self) {
+        self.set_zoom(self.zoom * ZOOM_STEP);
+    }
+
+    /// 缩小
+    pub fn zoom_out(&mut self) {
+        self.set_zoom(self.zoom / ZOOM_STEP);
+    }
+
+    /// 设置缩放倍数，并保证可见区域不超出纹理
+    pub fn set_zoom(&mut self, zoom: f32) {
+        self.zoom = zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+        self.clamp_center();
+    }
+
+    /// 按可见区域的比例平移，`dx`/`dy` 为步数（负数向左/上）
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        let step = PAN_STEP / self.zoom;
+        self.center.0 += dx * step;
+        self.center.1 += dy * step;
+        self.clamp_center();
+    }
+
+    /// 按倍率缩放（用于捏合手势）
+    pub fn zoom_by(&mut self, factor: f32) {
+        self.set_zoom(self.zoom * factor);
+    }
+
+    /// 拖动预览，`dx`/`dy` 为相对可见区域大小的比例
+    ///
+    /// 内容跟随手指移动，因此中心点反向移动
+    pub fn drag(&mut self, dx: f32, dy: f32) {
+        self.center.0 -= dx / self.zoom;
+        self.center.1 -= dy / self.zoom;
+        self.clamp_center();
+    }
+
+    /// 切换到下一个（更小的）mip 层级
+    pub fn next_mip(&mut self, level_count: u32) {
+        self.mip_level = (self.mip_level + 1).min(level_count.saturating_sub(1));
+    }
+
+    /// 切换到上一个（更大的）mip 层级
+    pub fn prev_mip(&mut self) {
+        self.mip_level = self.mip_level.saturating_sub(1);
+    }
+
+    /// 纹理变化后重置 mip 层级
+    pub fn reset_mip(&mut self) {
+        self.mip_level = 0;
+    }
+
+    /// 切换通道显示，`channel` 为 0..4 对应 RGBA
+    pub fn toggle_channel(&mut self, channel: usize) {
+        if let Some(enabled) = self.channels.get_mut(channel) {
+            *enabled = !*enabled;
+        }
+    }
+
+    /// 切换适应窗口模式，同时重置缩放和平移
+    pub fn toggle_fit(&mut self) {
+        self.fit_to_window = !self.fit_to_window;
+        self.zoom = MIN_ZOOM;
+        self.center = (0.5, 0.5);
+    }
+
+    /// 设置适应窗口模式（用于恢复保存的设置）
+    pub fn set_fit_to_window(&mut self, fit: bool) {
+        if self.fit_to_window != fit {
+            self.toggle_fit();
+        }
+    }
+
+    /// 计算给定尺寸图像的可见区域（用于 `Image::crop`）
+    pub fn crop_region(&self, width: u32, height: u32) -> Rectangle<u32> {
+        let visible_w = (width as f32 / self.zoom).max(1.0);
+        let visible_h = (height as f32 / self.zoom).max(1.0);
+        let x = (self.center.0 * width as f32 - visible_w / 2.0).max(0.0);
+        let y = (self.center.1 * height as f32 - visible_h / 2.0).max(0.0);
+
+        Rectangle {
+            x: x as u32,
+            y: y as u32,
+            width: visible_w.round() as u32,
+            height: visible_h.round() as u32,
+        }
+    }
+
+    /// 生成当前 mip 层级和通道设置下的显示图像
+    ///
+    /// 返回 (宽, 高, RGBA8 数据)
+    pub fn render(&self, rgba: &[u8], width: u32, height: u32) -> (u32, u32, Vec<u8>) {
+        let (width, height, pixels) = downsample(rgba, width, height, self.mip_level);
+        let pixels = apply_channels(&pixels, self.channels);
+        (width, height, pixels)
+    }
+
+    /// 状态栏文本
+    pub fn status(&self) -> String {
+        let channels: String = self
+            .channels
+            .iter()
+            .zip(['R', 'G', 'B', 'A'])
+            .map(|(&enabled, name)| if enabled { name } else { '-' })
+            .collect();
+        format!(
+            "Zoom: {:.2}x | Mip: {} | Channels: {}{}",
+            self.zoom,
+            self.mip_level,
+            channels,
+            if self.fit_to_window { " | Fit" } else { "" }
+        )
+    }
+
+    fn clamp_center(&mut self) {
+        let half = 0.5 / self.zoom;
+        self.center.0 = self.center.0.clamp(half, 1.0 - half);
+        self.center.1 = self.center.1.clamp(half, 1.0 - half);
+    }
+}
+
+impl Default for ViewerState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 完整 mip 链的层级数
+pub fn mip_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+/// 在 CPU 上生成指定 mip 层级（双线性缩小）
+pub fn downsample(rgba: &[u8], width: u32, height: u32, level: u32) -> (u32, u32, Vec<u8>) {
+    if level == 0 {
+        return (width, height, rgba.to_vec());
+    }
+
+    let target_w = (width >> level).max(1);
+    let target_h = (height >> level).max(1);
+    match image::RgbaImage::from_raw(width, height, rgba.to_vec()) {
+        Some(img) => {
+            let resized = image::imageops::resize(&img, target_w, target_h, FilterType::Triangle);
+            (target_w, target_h, resized.into_raw())
+        }
+        None => (width, height, rgba.to_vec()),
+    }
+}
+
+/// 按通道开关过滤像素
+///
+/// 只显示 Alpha 时以灰度显示 Alpha；关闭 Alpha 时强制不透明
+pub fn apply_channels(rgba: &[u8], channels: [bool; 4]) -> Vec<u8> {
+    if channels == [true; 4] {
+        return rgba.to_vec();
+    }
+
+    let alpha_only = channels == [false, false, false, true];
+    let mut out = Vec::with_capacity(rgba.len());
+    for pixel in rgba.chunks_exact(4) {
+        if alpha_only {
+            out.extend_from_slice(&[pixel[3], pixel[3], pixel[3], 255]);
+        } else {
+            for c in 0..3 {
+                out.push(if channels[c] { pixel[c] } else { 0 });
+            }
+            out.push(if channels[3] { pixel[3] } else { 255 });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crop_region_stays_inside() {
+        let mut viewer = ViewerState::new();
+        viewer.set_zoom(2.0);
+        for _ in 0..20 {
+            viewer.pan(1.0, 1.0);
+        }
+        let region = viewer.crop_region(100, 50);
+        assert_eq!((region.width, region.height), (50, 25));
+        assert!(region.x + region.width <= 100);
+        assert!(region.y + region.height <= 50);
+    }
+
+    #[test]
+    fn test_mip_count() {
+        assert_eq!(mip_count(1, 1), 1);
+        assert_eq!(mip_count(256, 128), 9);
+        assert_eq!(mip_count(3412, 1362), 12);
+    }
+}